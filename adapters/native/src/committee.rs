@@ -0,0 +1,233 @@
+use k256::schnorr::{
+    Signature, SigningKey, VerifyingKey as SchnorrVerifyingKey,
+    signature::{Signer, Verifier},
+};
+use sha2::{Digest, Sha256};
+use zkaleido::{
+    DataFormatError, ProgramId, ProofReceiptWithMetadata, VerifyingKey, ZkVm, ZkVmError,
+    ZkVmResult, ZkVmTypedVerifier, ZkVmVerifyingKeyError,
+};
+
+use crate::proof::{NativeProofReceipt, signing_message};
+
+/// Domain separator hashed into the program id of a committee.
+const COMMITTEE_DOMAIN: &[u8] = b"zkaleido-native-committee-v1";
+
+/// Length of the public key of a member.
+const KEY_LEN: usize = 32;
+
+/// Length of a Schnorr signature.
+const SIGNATURE_LEN: usize = 64;
+
+/// Length of an entry of a committee proof: the member index followed by its signature.
+const ENTRY_LEN: usize = 2 + SIGNATURE_LEN;
+
+/// A committee of operators attesting to execution results, of which `threshold` must sign.
+///
+/// This stands in for a zk proof on networks where a committee attests to execution instead.
+/// A committee is identified by its members and threshold: its verifying key encodes both, and
+/// its [`ProgramId`] is a hash of that key. The proof of a committee receipt is the set of
+/// member signatures over the same message a single-key
+/// [`NativeHost`](crate::NativeHost) signs, each prefixed with the index of the member as a
+/// little-endian `u16`.
+///
+/// A committee verifies receipts on its own, so it can be registered in a
+/// [`VerifierRegistry`](zkaleido::VerifierRegistry) under its program id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Committee {
+    members: Vec<SchnorrVerifyingKey>,
+    threshold: usize,
+}
+
+impl Committee {
+    /// Creates a committee of `members`, of which `threshold` must sign a receipt.
+    ///
+    /// Fails if `threshold` is zero or larger than the number of members, or if a member is
+    /// listed twice.
+    pub fn new(members: Vec<SchnorrVerifyingKey>, threshold: usize) -> ZkVmResult<Self> {
+        if threshold == 0 || threshold > members.len() {
+            return Err(invalid_committee(format!(
+                "threshold {threshold} is not between 1 and the committee size {}",
+                members.len()
+            )));
+        }
+        if members.len() > usize::from(u16::MAX) {
+            return Err(invalid_committee(format!(
+                "committee of {} members is too large",
+                members.len()
+            )));
+        }
+        if (1..members.len()).any(|i| members[..i].contains(&members[i])) {
+            return Err(invalid_committee("a member is listed twice".to_string()));
+        }
+        Ok(Self { members, threshold })
+    }
+
+    /// Decodes a committee from its verifying key, as returned by [`vk`](Self::vk).
+    pub fn from_vk(vk: &VerifyingKey) -> ZkVmResult<Self> {
+        let Some((threshold, keys)) = vk.as_bytes().split_first_chunk::<4>() else {
+            return Err(ZkVmVerifyingKeyError::InvalidVerifyingKeySize.into());
+        };
+        if !keys.len().is_multiple_of(KEY_LEN) {
+            return Err(ZkVmVerifyingKeyError::InvalidVerifyingKeySize.into());
+        }
+        let members = keys
+            .chunks(KEY_LEN)
+            .map(|key| {
+                SchnorrVerifyingKey::from_bytes(key)
+                    .map_err(|e| invalid_committee(format!("invalid member key: {e}")))
+            })
+            .collect::<ZkVmResult<_>>()?;
+        Self::new(members, u32::from_le_bytes(*threshold) as usize)
+    }
+
+    /// Returns the public keys of the members.
+    pub fn members(&self) -> &[SchnorrVerifyingKey] {
+        &self.members
+    }
+
+    /// Returns the number of members that must sign a receipt.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the verifying key of the committee: the threshold as a little-endian `u32`,
+    /// followed by the public key of every member.
+    pub fn vk(&self) -> VerifyingKey {
+        let mut vk = Vec::with_capacity(4 + KEY_LEN * self.members.len());
+        vk.extend_from_slice(&(self.threshold as u32).to_le_bytes());
+        for member in &self.members {
+            vk.extend_from_slice(&member.to_bytes());
+        }
+        VerifyingKey::new(vk)
+    }
+
+    /// Returns the program id of the committee, the SHA-256 hash of its domain-separated
+    /// verifying key.
+    pub fn program_id(&self) -> ProgramId {
+        let mut hasher = Sha256::new();
+        hasher.update(COMMITTEE_DOMAIN);
+        hasher.update(self.vk().as_bytes());
+        ProgramId(hasher.finalize().into())
+    }
+
+    /// Pairs every operator with its member index, sorted by index.
+    ///
+    /// Fails if an operator is not a member, or if there are fewer distinct operators than the
+    /// threshold.
+    pub(crate) fn index_operators(
+        &self,
+        operators: impl IntoIterator<Item = SigningKey>,
+    ) -> ZkVmResult<Vec<(u16, SigningKey)>> {
+        let mut indexed = operators
+            .into_iter()
+            .map(|operator| {
+                let key = operator.verifying_key();
+                let index = self
+                    .members
+                    .iter()
+                    .position(|member| member == key)
+                    .ok_or_else(|| {
+                        ZkVmError::Other("operator is not a committee member".to_string())
+                    })?;
+                Ok((index as u16, operator))
+            })
+            .collect::<ZkVmResult<Vec<_>>>()?;
+        indexed.sort_by_key(|(index, _)| *index);
+        indexed.dedup_by_key(|(index, _)| *index);
+        if indexed.len() < self.threshold {
+            return Err(ZkVmError::Other(format!(
+                "{} operators cannot meet the committee threshold of {}",
+                indexed.len(),
+                self.threshold
+            )));
+        }
+        Ok(indexed)
+    }
+
+    /// Signs `message` with every operator, as paired by
+    /// [`index_operators`](Self::index_operators).
+    pub(crate) fn sign(operators: &[(u16, SigningKey)], message: &[u8]) -> Vec<u8> {
+        let mut proof = Vec::with_capacity(ENTRY_LEN * operators.len());
+        for (index, operator) in operators {
+            let signature: Signature = operator.sign(message);
+            proof.extend_from_slice(&index.to_le_bytes());
+            proof.extend_from_slice(&signature.to_bytes());
+        }
+        proof
+    }
+
+    /// Verifies that at least [`threshold`](Self::threshold) distinct members signed `receipt`.
+    ///
+    /// Every signature in the receipt must be valid, even beyond the threshold.
+    pub(crate) fn verify_receipt(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        let metadata = receipt.metadata();
+        if *metadata.zkvm() != ZkVm::Native {
+            return Err(ZkVmError::ProofVerificationError(format!(
+                "expected a native receipt, got a {} receipt",
+                metadata.zkvm()
+            )));
+        }
+        if *metadata.program_id() != self.program_id() {
+            return Err(ZkVmError::ProofVerificationError(
+                "receipt is for another program".to_string(),
+            ));
+        }
+
+        let proof = receipt.receipt().proof().as_bytes();
+        if !proof.len().is_multiple_of(ENTRY_LEN) {
+            return Err(ZkVmError::ProofVerificationError(
+                "malformed committee signatures".to_string(),
+            ));
+        }
+        let message = signing_message(metadata, receipt.receipt().public_values());
+        let mut previous = None;
+        for entry in proof.chunks(ENTRY_LEN) {
+            let (index, signature) = entry.split_at(2);
+            let index = u16::from_le_bytes([index[0], index[1]]);
+            if previous.is_some_and(|previous| index <= previous) {
+                return Err(ZkVmError::ProofVerificationError(
+                    "committee signatures are not sorted by member".to_string(),
+                ));
+            }
+            previous = Some(index);
+
+            let member = self.members.get(usize::from(index)).ok_or_else(|| {
+                ZkVmError::ProofVerificationError(format!("unknown committee member {index}"))
+            })?;
+            let signature = Signature::try_from(signature).map_err(|e| {
+                ZkVmError::ProofVerificationError(format!("invalid signature: {e}"))
+            })?;
+            member.verify(&message, &signature).map_err(|e| {
+                ZkVmError::ProofVerificationError(format!(
+                    "signature of committee member {index} failed to verify: {e}"
+                ))
+            })?;
+        }
+
+        let signers = proof.len() / ENTRY_LEN;
+        if signers < self.threshold {
+            return Err(ZkVmError::ProofVerificationError(format!(
+                "{signers} committee members signed, {} required",
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ZkVmTypedVerifier for Committee {
+    type ZkVmProofReceipt = NativeProofReceipt;
+
+    fn verify_inner(&self, receipt: &NativeProofReceipt) -> ZkVmResult<()> {
+        let receipt: ProofReceiptWithMetadata = receipt
+            .clone()
+            .try_into()
+            .map_err(ZkVmError::InvalidProofReceipt)?;
+        self.verify_receipt(&receipt)
+    }
+}
+
+fn invalid_committee(message: String) -> ZkVmError {
+    ZkVmVerifyingKeyError::DataFormat(DataFormatError::Other(message)).into()
+}
@@ -1,21 +1,50 @@
-use std::cell::RefCell;
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
 
-use zkaleido::{ZkVmEnv, ZkVmEnvSerde};
+use zkaleido::{
+    AggregationInput, ExecutionStats, TrackedRegion, VerifyingKey, ZkVmEnv, ZkVmEnvError,
+    ZkVmEnvResult, ZkVmEnvSerde,
+};
+
+use crate::verifier::NativeVerifier;
 
 /// Encapsulates the mutable state of the NativeMachine.
 #[derive(Debug, Clone)]
 pub struct NativeMachineState {
     /// Pointer to the current position in the input.
     pub input_ptr: usize,
+    /// Pointer to the next proof to verify.
+    pub proof_ptr: usize,
     /// Buffer to store the output.
     pub output: Vec<u8>,
+    /// Set when the guest called [`ZkVmEnv::abort`].
+    pub abort: Option<NativeAbort>,
+    /// The reads, commits and tracked regions recorded so far. The wall-clock time of the
+    /// execution is filled in by the host.
+    pub stats: ExecutionStats,
+    /// The regions opened with [`ZkVmEnv::cycle_tracker_start`] and not closed yet, with the
+    /// time they were entered.
+    pub open_regions: Vec<(String, Instant)>,
+}
+
+/// The exit code and message a guest passed to [`ZkVmEnv::abort`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeAbort {
+    /// The non-zero exit code.
+    pub exit_code: u8,
+    /// The abort message.
+    pub message: String,
 }
 
 /// A native implementation of the [`ZkVmEnv`]
 ///
-/// This uses interior mutability with [`RefCell`] to conform to the [`ZkVmEnv`] trait, which
-/// requires methods to take an immutable reference to `self`.
-#[derive(Debug, Clone)]
+/// This uses interior mutability with a [`Mutex`] to conform to the [`ZkVmEnv`] trait, which
+/// requires methods to take an immutable reference to `self`. The state is shared through an
+/// [`Arc`] so that the host can still read the output committed so far while the guest runs on
+/// a worker thread, for instance after a timeout. Cloning the machine copies its state.
+#[derive(Debug)]
 pub struct NativeMachine {
     /// A vector containing chunks of serialized input data.
     ///
@@ -23,8 +52,15 @@ pub struct NativeMachine {
     /// processed.
     pub inputs: Vec<Vec<u8>>,
 
+    /// The proofs written with [`write_proof`](Self::write_proof), in the order the guest
+    /// verifies them.
+    pub proofs: Vec<AggregationInput>,
+
+    /// The verifying keys the guest may accept proofs from. Any key is accepted if empty.
+    pub(crate) trusted_vks: Vec<VerifyingKey>,
+
     /// Encapsulated mutable state for the machine.
-    pub state: RefCell<NativeMachineState>,
+    pub state: Arc<Mutex<NativeMachineState>>,
 }
 
 impl NativeMachine {
@@ -37,18 +73,93 @@ impl NativeMachine {
     /// machine's pointer to the next input element and captures any output
     /// data produced by the machine during execution.
     pub fn new() -> Self {
-        let state = RefCell::new(NativeMachineState {
+        let state = Arc::new(Mutex::new(NativeMachineState {
             input_ptr: 0,
+            proof_ptr: 0,
             output: Vec::new(),
-        });
-        let inputs = Vec::new();
-        Self { inputs, state }
+            abort: None,
+            stats: ExecutionStats::default(),
+            open_regions: Vec::new(),
+        }));
+        Self {
+            inputs: Vec::new(),
+            proofs: Vec::new(),
+            trusted_vks: Vec::new(),
+            state,
+        }
+    }
+
+    /// Locks the state of the machine.
+    ///
+    /// A guest that panicked while holding the lock poisons it; the state is still returned, so
+    /// that the output committed before the panic can be recovered.
+    pub fn state(&self) -> MutexGuard<'_, NativeMachineState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Appends a pre-serialized byte slice to the machine's list of inputs.
     pub fn write_slice(&mut self, input: Vec<u8>) {
         self.inputs.push(input);
     }
+
+    /// Appends a proof to be verified by [`ZkVmEnv::verify_native_proof`].
+    ///
+    /// Only the proof is stored; its public values are written separately as an input.
+    pub fn write_proof(&mut self, proof: AggregationInput) {
+        self.proofs.push(proof);
+    }
+
+    /// Checks the next proof against `vk_digest` and `public_values`.
+    ///
+    /// The digest of a native verifying key is the [`ProgramId`](zkaleido::ProgramId) of the
+    /// host read as little-endian words: the 32-byte Schnorr public key of a single-key host, or
+    /// the hash of the verifying key of a [`Committee`](crate::Committee).
+    fn check_next_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]) -> Result<(), String> {
+        let proof = {
+            let mut state = self.state();
+            let proof = self
+                .proofs
+                .get(state.proof_ptr)
+                .ok_or("no more proofs to verify")?;
+            state.proof_ptr += 1;
+            proof
+        };
+
+        let digest_bytes: Vec<u8> = vk_digest
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        let verifier = NativeVerifier::from_vk(proof.vk())
+            .map_err(|e| format!("invalid verifying key: {e}"))?;
+        if verifier.program_id().0[..] != digest_bytes[..] {
+            return Err("verifying key does not match the vk digest".to_string());
+        }
+        if !self.trusted_vks.is_empty() && !self.trusted_vks.contains(proof.vk()) {
+            return Err("verifying key is not trusted".to_string());
+        }
+
+        if proof.receipt().metadata().is_dev_mode() {
+            return Err("dev-mode receipts cannot be aggregated".to_string());
+        }
+        let receipt = proof.receipt().receipt();
+        if receipt.public_values().as_bytes() != public_values {
+            return Err("public values do not match the proof".to_string());
+        }
+        verifier
+            .verify_receipt(proof.receipt())
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl Clone for NativeMachine {
+    fn clone(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            proofs: self.proofs.clone(),
+            trusted_vks: self.trusted_vks.clone(),
+            state: Arc::new(Mutex::new(self.state().clone())),
+        }
+    }
 }
 
 impl Default for NativeMachine {
@@ -59,18 +170,140 @@ impl Default for NativeMachine {
 
 impl ZkVmEnv for NativeMachine {
     fn read_buf(&self) -> Vec<u8> {
-        let mut state = self.state.borrow_mut();
-        let buf = self.inputs[state.input_ptr].clone();
+        self.try_read_buf()
+            .unwrap_or_else(|e| self.abort(e.exit_code(), &e.to_string()))
+    }
+
+    fn try_read_buf(&self) -> ZkVmEnvResult<Vec<u8>> {
+        let mut state = self.state();
+        let buf = self
+            .inputs
+            .get(state.input_ptr)
+            .ok_or(ZkVmEnvError::InputExhausted)?
+            .clone();
         state.input_ptr += 1;
-        buf
+        state.stats.reads += 1;
+        state.stats.input_bytes += buf.len() as u64;
+        Ok(buf)
     }
 
     fn commit_buf(&self, raw_output: &[u8]) {
-        self.state.borrow_mut().output.extend_from_slice(raw_output);
+        let mut state = self.state();
+        state.output.extend_from_slice(raw_output);
+        state.stats.commits += 1;
+        state.stats.output_bytes += raw_output.len() as u64;
     }
 
-    fn verify_native_proof(&self, _vk_digest: &[u32; 8], _public_values: &[u8]) {}
+    fn cycle_tracker_start(&self, label: &str) {
+        let mut state = self.state();
+        state.open_regions.push((label.to_string(), Instant::now()));
+    }
+
+    /// Adds the wall-clock time since the innermost region opened with `label` to its
+    /// [`TrackedRegion`]. Does nothing if no such region is open.
+    fn cycle_tracker_end(&self, label: &str) {
+        let mut state = self.state();
+        let Some(position) = state
+            .open_regions
+            .iter()
+            .rposition(|(open, _)| open == label)
+        else {
+            return;
+        };
+        let (label, entered) = state.open_regions.remove(position);
+        let elapsed = entered.elapsed().as_nanos() as u64;
+        let regions = &mut state.stats.regions;
+        let region = match regions.iter().position(|region| region.label == label) {
+            Some(index) => &mut regions[index],
+            None => {
+                regions.push(TrackedRegion {
+                    label,
+                    ..TrackedRegion::default()
+                });
+                regions.last_mut().expect("a region was just pushed")
+            }
+        };
+        region.entries += 1;
+        region.wall_time_nanos += elapsed;
+    }
+
+    /// Checks the Schnorr signature of the next proof written by the host, aborting the guest
+    /// if it does not verify against `vk_digest`.
+    fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]) {
+        if let Err(error) = self.try_verify_native_proof(vk_digest, public_values) {
+            self.abort(error.exit_code(), &error.to_string())
+        }
+    }
+
+    fn try_verify_native_proof(
+        &self,
+        vk_digest: &[u32; 8],
+        public_values: &[u8],
+    ) -> ZkVmEnvResult<()> {
+        self.check_next_proof(vk_digest, public_values)
+            .map_err(ZkVmEnvError::ProofVerification)
+    }
+
+    /// Records the abort in the machine state and panics, since there is no guest
+    /// process to exit.
+    fn abort(&self, exit_code: u8, message: &str) -> ! {
+        let exit_code = exit_code.max(1);
+        self.state().abort = Some(NativeAbort {
+            exit_code,
+            message: message.to_string(),
+        });
+        panic!("guest aborted with exit code {exit_code}: {message}")
+    }
 }
 
 /// Uses the default bincode-based implementations from [`ZkVmEnvSerde`].
 impl ZkVmEnvSerde for NativeMachine {}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    use super::*;
+
+    #[test]
+    fn try_reads_report_errors() {
+        let mut machine = NativeMachine::new();
+        machine.write_slice(vec![1, 2]);
+
+        assert!(matches!(
+            machine.try_read_serde::<u32>(),
+            Err(ZkVmEnvError::Decode(_))
+        ));
+        assert!(matches!(
+            machine.try_read_buf(),
+            Err(ZkVmEnvError::InputExhausted)
+        ));
+    }
+
+    #[test]
+    fn try_read_verified_reports_failed_verification() {
+        let mut machine = NativeMachine::new();
+        machine.write_slice(vec![1, 2]);
+
+        assert!(matches!(
+            machine.try_read_verified_buf(&[0; 8]),
+            Err(ZkVmEnvError::ProofVerification(_))
+        ));
+        assert_eq!(machine.state().abort, None);
+    }
+
+    #[test]
+    fn read_past_end_aborts() {
+        let machine = NativeMachine::new();
+        let result = catch_unwind(AssertUnwindSafe(|| machine.read_buf()));
+
+        assert!(result.is_err());
+        assert_eq!(
+            machine.state().abort,
+            Some(NativeAbort {
+                exit_code: ZkVmEnvError::INPUT_EXHAUSTED_EXIT_CODE,
+                message: "no more inputs to read".to_string(),
+            })
+        );
+    }
+}
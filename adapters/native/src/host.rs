@@ -1,31 +1,59 @@
-use std::{env, fmt, sync::Arc};
+use std::{
+    any::Any,
+    env, fmt,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 #[cfg(feature = "remote-prover")]
 use async_trait::async_trait;
-use k256::schnorr::{
-    Signature, SigningKey,
-    signature::{Signer, Verifier},
-};
+use k256::schnorr::{Signature, SigningKey, signature::Signer};
 use rand_core::OsRng;
 use serde::{Serialize, de::DeserializeOwned};
 use zkaleido::{
-    DataFormatError, ExecutionSummary, ProgramId, Proof, ProofMetadata, ProofReceipt,
-    ProofReceiptWithMetadata, ProofType, PublicValues, VerifyingKey, ZkVm, ZkVmError, ZkVmExecutor,
-    ZkVmHost, ZkVmOutputExtractor, ZkVmProver, ZkVmResult, ZkVmTypedVerifier, ZkVmVkProvider,
+    DataFormatError, ExecutionStats, ExecutionSummary, ProgramId, Proof, ProofMetadata,
+    ProofReceipt, ProofReceiptWithMetadata, ProofType, PublicValues, RunConfig, VerifyingKey, ZkVm,
+    ZkVmError, ZkVmExecutor, ZkVmHost, ZkVmOutputExtractor, ZkVmProver, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVkProvider,
 };
 #[cfg(feature = "remote-prover")]
 use zkaleido::{RemoteProofStatus, ZkVmRemoteProver};
 
-use crate::{env::NativeMachine, input::NativeMachineInputBuilder, proof::NativeProofReceipt};
+use crate::{
+    committee::Committee,
+    env::{NativeMachine, NativeMachineState},
+    input::NativeMachineInputBuilder,
+    key::derive_signing_key,
+    proof::{NativeProofReceipt, signing_message},
+    verifier::NativeVerifier,
+};
 
 type ProcessProofFn = dyn Fn(&NativeMachine) -> ZkVmResult<()> + Send + Sync;
 
+/// The key or keys a [`NativeHost`] signs its receipts with.
+#[derive(Clone)]
+enum NativeSigner {
+    /// A single Schnorr signing key, whose public key is the verifying key of the host.
+    Key(SigningKey),
+    /// The operators of a committee, paired with their member index.
+    Committee {
+        committee: Committee,
+        operators: Vec<(u16, SigningKey)>,
+    },
+}
+
 /// A native host that holds a reference to a proof-processing function and a Schnorr signing key.
 ///
 /// This struct can be cloned cheaply (due to the internal [`Arc`]), and used by various
 /// parts of the application to execute native proofs with Schnorr signature-based verification.
 /// The signing key is used to sign public values during proof generation and verify them during
-/// verification.
+/// verification. A host created with [`new_committee`](Self::new_committee) instead signs with
+/// the operators of a [`Committee`], and its receipts verify once enough members signed.
 #[derive(Clone)]
 pub struct NativeHost {
     /// A function wrapped in [`Arc`] and [`Box`] that processes proofs for a
@@ -36,9 +64,15 @@ pub struct NativeHost {
     /// logic without needing to replicate code or data.
     process_fn: Arc<Box<ProcessProofFn>>,
 
-    /// The Schnorr signing key used for signing public values during proof generation
-    /// and verifying signatures during proof verification.
-    schnorr_key: SigningKey,
+    /// The Schnorr signing key, or the committee operators, used for signing public values
+    /// during proof generation and verifying signatures during proof verification.
+    signer: NativeSigner,
+
+    /// The verifying keys of the proofs the guest may verify. Any key is accepted if empty.
+    trusted_vks: Vec<VerifyingKey>,
+
+    /// The wall-clock time after which execution is abandoned. Unbounded if `None`.
+    timeout: Option<Duration>,
 }
 
 impl NativeHost {
@@ -70,13 +104,92 @@ impl NativeHost {
     {
         Self {
             process_fn: Arc::new(Box::new(process_fn)),
-            schnorr_key: signing_key,
+            signer: NativeSigner::Key(signing_key),
+            trusted_vks: Vec::new(),
+            timeout: None,
         }
     }
 
+    /// Creates a new [`NativeHost`] whose receipts are signed by the `operators` of `committee`
+    /// and an infallible processing function.
+    ///
+    /// Every operator signs each receipt, so there must be at least as many operators as the
+    /// threshold of the committee, and each of them must be a member. The verifying key and
+    /// program id of the host are the ones of the committee. For a fallible processing function,
+    /// use [`new_fallible_committee`](Self::new_fallible_committee).
+    pub fn new_committee<F>(
+        committee: Committee,
+        operators: impl IntoIterator<Item = SigningKey>,
+        process_fn: F,
+    ) -> ZkVmResult<Self>
+    where
+        F: Fn(&NativeMachine) + Send + Sync + 'static,
+    {
+        Self::new_fallible_committee(
+            committee,
+            operators,
+            move |zkvm: &NativeMachine| -> ZkVmResult<()> {
+                process_fn(zkvm);
+                Ok(())
+            },
+        )
+    }
+
+    /// Creates a new [`NativeHost`] whose receipts are signed by the `operators` of `committee`
+    /// and a fallible processing function.
+    ///
+    /// See [`new_committee`](Self::new_committee) for the requirements on the operators.
+    pub fn new_fallible_committee<F>(
+        committee: Committee,
+        operators: impl IntoIterator<Item = SigningKey>,
+        process_fn: F,
+    ) -> ZkVmResult<Self>
+    where
+        F: Fn(&NativeMachine) -> ZkVmResult<()> + Send + Sync + 'static,
+    {
+        let operators = committee.index_operators(operators)?;
+        Ok(Self {
+            process_fn: Arc::new(Box::new(process_fn)),
+            signer: NativeSigner::Committee {
+                committee,
+                operators,
+            },
+            trusted_vks: Vec::new(),
+            timeout: None,
+        })
+    }
+
+    /// Creates a new [`NativeHost`] whose signing key is derived from `seed` and `label` with
+    /// [`derive_signing_key`].
+    ///
+    /// Hosts built from the same seed and label share their verifying key and program id, even
+    /// across processes, so they verify each other's receipts. Use the name of the program as
+    /// the label. For a fallible processing function, use
+    /// [`new_fallible_from_seed`](Self::new_fallible_from_seed).
+    pub fn new_from_seed<F>(seed: &[u8], label: &str, process_fn: F) -> Self
+    where
+        F: Fn(&NativeMachine) + Send + Sync + 'static,
+    {
+        Self::new(derive_signing_key(seed, label), process_fn)
+    }
+
+    /// Creates a new [`NativeHost`] whose signing key is derived from `seed` and `label` with
+    /// [`derive_signing_key`], and a fallible processing function.
+    ///
+    /// See [`new_from_seed`](Self::new_from_seed).
+    pub fn new_fallible_from_seed<F>(seed: &[u8], label: &str, process_fn: F) -> Self
+    where
+        F: Fn(&NativeMachine) -> ZkVmResult<()> + Send + Sync + 'static,
+    {
+        Self::new_fallible(derive_signing_key(seed, label), process_fn)
+    }
+
     /// Creates a new [`NativeHost`] with a freshly generated Schnorr signing key.
     ///
-    /// Handy for tests or short-lived hosts where a stable verifying key is not required.
+    /// Handy for tests or short-lived hosts where a stable verifying key is not required: the
+    /// receipts of this host cannot be verified by another process. Use
+    /// [`new_from_seed`](Self::new_from_seed) or a key loaded with
+    /// [`load_signing_key`](crate::load_signing_key) otherwise.
     /// For a fallible processing function, use
     /// [`new_fallible_with_random_key`](Self::new_fallible_with_random_key); to supply your own
     /// key, use [`new`](Self::new).
@@ -100,6 +213,41 @@ impl NativeHost {
     {
         Self::new_fallible(SigningKey::random(&mut OsRng), process_fn)
     }
+
+    /// Returns a verifier of the receipts of this host that holds no signing key.
+    ///
+    /// The same verifier can be rebuilt in another process from the verifying key of the host
+    /// with [`NativeVerifier::from_vk`].
+    pub fn verifier(&self) -> NativeVerifier {
+        match &self.signer {
+            NativeSigner::Key(key) => NativeVerifier::from_key(*key.verifying_key()),
+            NativeSigner::Committee { committee, .. } => {
+                NativeVerifier::from_committee(committee.clone())
+            }
+        }
+    }
+
+    /// Only lets the guest verify proofs signed by one of `vks`.
+    ///
+    /// By default, the guest accepts a proof from any native host, provided its verifying key
+    /// matches the vk digest passed to
+    /// [`ZkVmEnv::verify_native_proof`](zkaleido::ZkVmEnv::verify_native_proof).
+    #[must_use]
+    pub fn with_trusted_vks(mut self, vks: impl IntoIterator<Item = VerifyingKey>) -> Self {
+        self.trusted_vks = vks.into_iter().collect();
+        self
+    }
+
+    /// Abandons execution of a guest that runs for longer than `timeout`, reporting
+    /// [`ZkVmError::ExecutionTimeout`] with the output committed so far.
+    ///
+    /// The guest runs on a worker thread, which cannot be stopped: it keeps running in the
+    /// background until the processing function returns. By default, there is no timeout.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 }
 
 impl ZkVmHost for NativeHost {
@@ -110,12 +258,56 @@ impl ZkVmHost for NativeHost {
 
 impl ZkVmExecutor for NativeHost {
     type Input<'a> = NativeMachineInputBuilder;
-    fn execute<'a>(&self, native_machine: NativeMachine) -> ZkVmResult<ExecutionSummary> {
-        (self.process_fn)(&native_machine)?;
-        let output = native_machine.state.borrow().output.clone();
-        let public_values = PublicValues::new(output);
+    /// Runs the processing function on a worker thread, catching panics so that a failing guest
+    /// is reported as [`ZkVmError::GuestAborted`] instead of unwinding through the caller.
+    ///
+    /// If the guest does not finish within the timeout set with
+    /// [`with_timeout`](NativeHost::with_timeout), [`ZkVmError::ExecutionTimeout`] is returned
+    /// instead. Both errors carry the output the guest committed before it stopped.
+    ///
+    /// Native execution produces no trace, so the profiling settings of `config` are ignored.
+    /// Instead of cycles, the summary reports the [`ExecutionStats`] recorded by the machine and
+    /// the wall-clock time of the processing function.
+    fn execute<'a>(
+        &self,
+        mut native_machine: NativeMachine,
+        _config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary> {
+        native_machine.trusted_vks = self.trusted_vks.clone();
+        let state = Arc::clone(&native_machine.state);
+        let process_fn = Arc::clone(&self.process_fn);
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("native-guest".to_string())
+            .spawn(move || {
+                let start = Instant::now();
+                let result = catch_unwind(AssertUnwindSafe(|| process_fn(&native_machine)));
+                // The host stops listening after a timeout, so the send may fail.
+                let _ = sender.send((result, start.elapsed()));
+            })
+            .map_err(|e| ZkVmError::ExecutionError(format!("failed to spawn guest thread: {e}")))?;
+
+        let received = match self.timeout {
+            Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+                RecvTimeoutError::Timeout => ZkVmError::ExecutionTimeout {
+                    timeout,
+                    public_values: PublicValues::new(lock_state(&state).output.clone()),
+                },
+                RecvTimeoutError::Disconnected => guest_thread_lost(),
+            }),
+            None => receiver.recv().map_err(|_| guest_thread_lost()),
+        };
+        let (result, duration) = received?;
+
+        let state = lock_state(&state);
+        let public_values = PublicValues::new(state.output.clone());
+        result.map_err(|payload| guest_aborted(&state, &public_values, payload))??;
+        let stats = ExecutionStats {
+            wall_time_nanos: duration.as_nanos() as u64,
+            ..state.stats.clone()
+        };
         // There is no straightforward equivalent of cycles and gas for native execution
-        Ok(ExecutionSummary::new(public_values, 0, None))
+        Ok(ExecutionSummary::new(public_values, 0, None).with_stats(stats))
     }
 
     /// Returns an empty slice as there is no ELF in native mode.
@@ -123,10 +315,11 @@ impl ZkVmExecutor for NativeHost {
         &[]
     }
 
-    fn save_trace(&self, _trace_name: &str) {}
-
     fn program_id(&self) -> zkaleido::ProgramId {
-        ProgramId(self.schnorr_key.verifying_key().to_bytes().into())
+        match &self.signer {
+            NativeSigner::Key(key) => ProgramId(key.verifying_key().to_bytes().into()),
+            NativeSigner::Committee { committee, .. } => committee.program_id(),
+        }
     }
 }
 
@@ -137,25 +330,57 @@ impl ZkVmProver for NativeHost {
         &self,
         native_machine: NativeMachine,
         proof_type: ProofType,
+        config: &RunConfig,
     ) -> ZkVmResult<NativeProofReceipt> {
-        let execution_result = self.execute(native_machine)?;
+        let execution_result = self.execute(native_machine, config)?;
         let public_values = execution_result.into_public_values();
 
-        // Sign the public values using the Schnorr signing key
-        let signature = self.schnorr_key.sign(public_values.as_bytes());
-        let proof = Proof::new(signature.to_bytes().to_vec());
-        let receipt = ProofReceipt::new(proof, public_values);
+        Ok(self.sign(public_values, proof_type).try_into()?)
+    }
+
+    /// Re-signs the public values of a verified receipt as a compressed receipt. Native receipts
+    /// are signatures, so every proof type is the same size.
+    fn compress(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<ProofReceiptWithMetadata> {
+        ZkVmTypedVerifier::verify(self, receipt)?;
+        let public_values = receipt.receipt().public_values().clone();
+        Ok(self.sign(public_values, ProofType::Compressed))
+    }
+
+    /// Re-signs the public values of a verified receipt as a Groth16 receipt.
+    fn wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        ZkVmTypedVerifier::verify(self, receipt)?;
+        let public_values = receipt.receipt().public_values().clone();
+        Ok(self.sign(public_values, ProofType::Groth16))
+    }
+}
 
+impl NativeHost {
+    /// Builds a receipt of `proof_type` for `public_values`.
+    fn sign(&self, public_values: PublicValues, proof_type: ProofType) -> ProofReceiptWithMetadata {
         let version: &str = env!("CARGO_PKG_VERSION");
         let metadata = ProofMetadata::new(
             ZkVm::Native,
-            ProgramId([0u8; 32]),
+            self.program_id(),
             version.to_string(),
             proof_type,
         );
 
-        let receipt = ProofReceiptWithMetadata::new(receipt, metadata);
-        Ok(receipt.try_into()?)
+        // Sign the public values, bound to the metadata, using the Schnorr signing key or every
+        // committee operator
+        let message = signing_message(&metadata, &public_values);
+        let proof = match &self.signer {
+            NativeSigner::Key(key) => {
+                let signature: Signature = key.sign(&message);
+                Proof::new(signature.to_bytes().to_vec())
+            }
+            NativeSigner::Committee { operators, .. } => {
+                Proof::new(Committee::sign(operators, &message))
+            }
+        };
+        ProofReceiptWithMetadata::new(ProofReceipt::new(proof, public_values), metadata)
     }
 }
 
@@ -167,25 +392,19 @@ impl ZkVmTypedVerifier for NativeHost {
             .clone()
             .try_into()
             .map_err(ZkVmError::InvalidProofReceipt)?;
-        let signature = Signature::try_from(receipt.receipt().proof().as_bytes())
-            .map_err(|e| ZkVmError::ProofVerificationError(format!("invalid signature: {e}")))?;
-        // Verify the Schnorr signature over the public values
-        self.schnorr_key
-            .verifying_key()
-            .verify(receipt.receipt().public_values().as_bytes(), &signature)
-            .map_err(|e| {
-                ZkVmError::ProofVerificationError(format!("signature verification failed: {e}"))
-            })?;
-
-        Ok(())
+        // Verify the Schnorr signature, or the committee signatures, over the public values and
+        // the metadata
+        self.verifier().verify_receipt(&receipt)
     }
 }
 
 impl ZkVmVkProvider for NativeHost {
     fn vk(&self) -> VerifyingKey {
-        // Return the Schnorr public key (verifying key) as the verifying key
-        let schnorr_public_key = self.schnorr_key.verifying_key().to_bytes().to_vec();
-        VerifyingKey::new(schnorr_public_key)
+        match &self.signer {
+            // Return the Schnorr public key (verifying key) as the verifying key
+            NativeSigner::Key(key) => VerifyingKey::new(key.verifying_key().to_bytes().to_vec()),
+            NativeSigner::Committee { committee, .. } => committee.vk(),
+        }
     }
 }
 
@@ -202,6 +421,51 @@ impl ZkVmOutputExtractor for NativeHost {
     }
 }
 
+/// Locks the state shared with the guest thread, even if a panicking guest poisoned it.
+fn lock_state(state: &Mutex<NativeMachineState>) -> MutexGuard<'_, NativeMachineState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The error reported if the guest thread exits without sending its result, which only happens
+/// if dropping a panic payload panics again.
+fn guest_thread_lost() -> ZkVmError {
+    ZkVmError::ExecutionError("guest thread exited without a result".to_string())
+}
+
+/// Builds the [`ZkVmError::GuestAborted`] for a processing function that panicked, keeping the
+/// `public_values` committed before the panic.
+///
+/// If the guest called [`ZkVmEnv::abort`](zkaleido::ZkVmEnv::abort), its exit code and message
+/// are reported. Any other panic is reported with exit code `1`, like a panicking SP1 guest.
+fn guest_aborted(
+    state: &NativeMachineState,
+    public_values: &PublicValues,
+    payload: Box<dyn Any + Send>,
+) -> ZkVmError {
+    if let Some(abort) = &state.abort {
+        return ZkVmError::GuestAborted {
+            exit_code: abort.exit_code.into(),
+            message: abort.message.clone(),
+            cycles: None,
+            public_values: Some(public_values.clone()),
+        };
+    }
+
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "guest panicked".to_string()
+    };
+    ZkVmError::GuestAborted {
+        exit_code: 1,
+        message,
+        cycles: None,
+        public_values: Some(public_values.clone()),
+    }
+}
+
 impl fmt::Debug for NativeHost {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "native")
@@ -259,7 +523,7 @@ impl ZkVmRemoteProver for NativeHost {
         proof_type: ProofType,
     ) -> ZkVmResult<NativeProofId> {
         // Execute proof synchronously
-        let proof_receipt = self.prove(input, proof_type)?;
+        let proof_receipt = self.prove(input, proof_type, &RunConfig::default())?;
 
         // Encode the proof receipt as the proof ID
         Ok(NativeProofId(proof_receipt.encode()))
@@ -271,6 +535,533 @@ impl ZkVmRemoteProver for NativeHost {
     }
 
     async fn get_proof(&self, id: &NativeProofId) -> ZkVmResult<ProofReceiptWithMetadata> {
-        ProofReceiptWithMetadata::decode(&id.0)
+        Ok(ProofReceiptWithMetadata::decode(&id.0)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zkaleido::{
+        AggregationInput, AllowDevMode, CanonicalSerdeCodec, DynZkVmHost, InputFrame, MockHost,
+        VerifierRegistry, VerifierRegistryError, ZkVmEnv, ZkVmEnvCodec, ZkVmEnvError,
+        ZkVmInputBuilder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn execute_reports_guest_abort() {
+        let host =
+            NativeHost::new_with_random_key(|zkvm: &NativeMachine| zkvm.abort(7, "input rejected"));
+        match host.execute(NativeMachine::new(), &RunConfig::default()) {
+            Err(ZkVmError::GuestAborted {
+                exit_code, message, ..
+            }) => {
+                assert_eq!(exit_code, 7);
+                assert_eq!(message, "input rejected");
+            }
+            other => panic!("expected GuestAborted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn execute_reports_guest_panic() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.commit_buf(&[1, 2]);
+            panic!("boom")
+        });
+        match host.execute(NativeMachine::new(), &RunConfig::default()) {
+            Err(ZkVmError::GuestAborted {
+                exit_code,
+                message,
+                public_values,
+                ..
+            }) => {
+                assert_eq!(exit_code, 1);
+                assert_eq!(message, "boom");
+                assert_eq!(public_values, Some(PublicValues::new(vec![1, 2])));
+            }
+            other => panic!("expected GuestAborted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn execute_times_out() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.commit_buf(&[1, 2]);
+            thread::sleep(Duration::from_secs(5));
+        })
+        .with_timeout(Duration::from_millis(50));
+        match host.execute(NativeMachine::new(), &RunConfig::default()) {
+            Err(ZkVmError::ExecutionTimeout {
+                timeout,
+                public_values,
+            }) => {
+                assert_eq!(timeout, Duration::from_millis(50));
+                assert_eq!(public_values.as_bytes(), &[1, 2]);
+            }
+            other => panic!("expected ExecutionTimeout, got {other:?}"),
+        }
+
+        // A guest finishing within the timeout is unaffected.
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| zkvm.commit_buf(&[3]))
+            .with_timeout(Duration::from_secs(60));
+        let summary = host
+            .execute(NativeMachine::new(), &RunConfig::default())
+            .unwrap();
+        assert_eq!(summary.public_values().as_bytes(), &[3]);
+    }
+
+    #[test]
+    fn execute_records_io_stats() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.cycle_tracker_start("echo");
+            for _ in 0..2 {
+                zkvm.cycle_tracker_start("read");
+                let buf = zkvm.read_buf();
+                zkvm.cycle_tracker_end("read");
+                zkvm.commit_buf(&buf);
+            }
+            zkvm.cycle_tracker_end("echo");
+            // Unmatched markers are ignored.
+            zkvm.cycle_tracker_end("unknown");
+            zkvm.cycle_tracker_start("unclosed");
+        });
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        input.write_slice(vec![4]);
+
+        let summary = host.execute(input, &RunConfig::default()).unwrap();
+        let stats = summary.stats().unwrap();
+        assert_eq!((stats.reads, stats.input_bytes), (2, 4));
+        assert_eq!((stats.commits, stats.output_bytes), (2, 4));
+        let regions: Vec<_> = stats
+            .regions
+            .iter()
+            .map(|region| (region.label.as_str(), region.entries))
+            .collect();
+        assert_eq!(regions, [("read", 2), ("echo", 1)]);
+        assert!(stats.wall_time_nanos >= stats.regions[1].wall_time_nanos);
+    }
+
+    #[test]
+    fn dyn_host_runs_input_frames() {
+        let host: Box<dyn DynZkVmHost> =
+            Box::new(NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+                let buf = zkvm.read_buf();
+                zkvm.commit_buf(&buf);
+            }));
+        let frames = [InputFrame::Buf(vec![1, 2, 3])];
+        let config = RunConfig::default();
+
+        let summary = host.dyn_execute(&frames, &config).unwrap();
+        assert_eq!(summary.public_values().as_bytes(), &[1, 2, 3]);
+
+        let receipt = host.dyn_prove(&frames, ProofType::Core, &config).unwrap();
+        assert_eq!(receipt.metadata().zkvm(), &ZkVm::Native);
+        host.dyn_verify(&receipt).unwrap();
+    }
+
+    #[test]
+    fn registry_routes_receipts_by_program() {
+        let echo = |zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        };
+        let host = NativeHost::new_with_random_key(echo);
+        let other = NativeHost::new_with_random_key(echo);
+        let config = RunConfig::default();
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = host.prove(input, ProofType::Core, &config).unwrap();
+
+        let mut registry = VerifierRegistry::new();
+        registry.register(
+            ZkVm::Native,
+            host.program_id(),
+            &[ProofType::Groth16],
+            host.clone(),
+        );
+        assert!(matches!(
+            registry.verify(&receipt),
+            Err(ZkVmError::VerifierRegistry(
+                VerifierRegistryError::UnsupportedProofType { .. }
+            ))
+        ));
+
+        registry.register_host(host);
+        registry.verify(&receipt).unwrap();
+
+        let mut registry = VerifierRegistry::new();
+        registry.register_host(other);
+        assert!(matches!(
+            registry.verify(&receipt),
+            Err(ZkVmError::VerifierRegistry(
+                VerifierRegistryError::UnknownProgram { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn canonical_serde_output_is_bincode() {
+        let output = (7u32, "seven".to_string());
+        let expected = bincode::serialize(&output).unwrap();
+        let host = NativeHost::new_with_random_key(move |zkvm: &NativeMachine| {
+            zkvm.commit_with::<CanonicalSerdeCodec, _>(&(7u32, "seven".to_string()));
+        });
+
+        let summary = host
+            .execute(NativeMachine::new(), &RunConfig::default())
+            .unwrap();
+        assert_eq!(summary.public_values().as_bytes(), expected.as_slice());
+        let decoded: (u32, String) = CanonicalSerdeCodec::decode(summary.public_values()).unwrap();
+        assert_eq!(decoded, output);
+    }
+
+    #[test]
+    fn verifies_aggregated_proofs() {
+        let inner = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.commit_buf(&[1, 2, 3]);
+        });
+        let config = RunConfig::default();
+        let receipt = inner
+            .prove(NativeMachine::new(), ProofType::Core, &config)
+            .unwrap();
+        let inner_vk_digest = inner.program_id().to_vk_digest();
+
+        let aggregator = |vk_digest: [u32; 8]| {
+            NativeHost::new_with_random_key(move |zkvm: &NativeMachine| {
+                let public_values = zkvm.read_verified_buf(&vk_digest);
+                zkvm.commit_buf(&public_values);
+            })
+        };
+        let run = |host: &NativeHost, proof: &AggregationInput| {
+            let mut builder = NativeMachineInputBuilder::new();
+            builder.write_proof(proof).unwrap();
+            host.execute(builder.build().unwrap(), &config)
+        };
+        let assert_rejected = |result: ZkVmResult<ExecutionSummary>| match result {
+            Err(ZkVmError::GuestAborted { exit_code, .. }) => {
+                assert_eq!(exit_code, ZkVmEnvError::PROOF_VERIFICATION_EXIT_CODE.into());
+            }
+            other => panic!("expected GuestAborted, got {other:?}"),
+        };
+
+        let proof = AggregationInput::new(receipt.clone(), inner.vk());
+        let summary = run(&aggregator(inner_vk_digest), &proof).unwrap();
+        assert_eq!(summary.public_values().as_bytes(), &[1, 2, 3]);
+
+        // The proof was signed by another key than the one the guest expects.
+        assert_rejected(run(&aggregator([0; 8]), &proof));
+
+        // The public values were changed after signing.
+        let forged = ProofReceiptWithMetadata::new(
+            ProofReceipt::new(
+                receipt.receipt().proof().clone(),
+                PublicValues::new(vec![9]),
+            ),
+            receipt.metadata().clone(),
+        );
+        assert_rejected(run(
+            &aggregator(inner_vk_digest),
+            &AggregationInput::new(forged, inner.vk()),
+        ));
+
+        // The key is not among the trusted ones.
+        let other = NativeHost::new_with_random_key(|_: &NativeMachine| {});
+        let restricted = aggregator(inner_vk_digest).with_trusted_vks([other.vk()]);
+        assert_rejected(run(&restricted, &proof));
+        let trusted = aggregator(inner_vk_digest).with_trusted_vks([inner.vk()]);
+        run(&trusted, &proof).unwrap();
+    }
+
+    #[test]
+    fn rejects_signature_replayed_with_other_metadata() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.commit_buf(&[1, 2, 3]);
+        });
+        let other = NativeHost::new_with_random_key(|_: &NativeMachine| {});
+        let receipt = host
+            .prove(NativeMachine::new(), ProofType::Core, &RunConfig::default())
+            .unwrap();
+        ZkVmTypedVerifier::verify(&host, &receipt).unwrap();
+
+        let metadata = receipt.metadata();
+        let replayed = [
+            ProofMetadata::new(
+                ZkVm::Native,
+                metadata.program_id().clone(),
+                metadata.version(),
+                ProofType::Groth16,
+            ),
+            ProofMetadata::new(
+                ZkVm::Native,
+                metadata.program_id().clone(),
+                "0.0.0",
+                metadata.proof_type(),
+            ),
+            ProofMetadata::new(
+                ZkVm::Native,
+                other.program_id(),
+                metadata.version(),
+                metadata.proof_type(),
+            ),
+            ProofMetadata::new(
+                ZkVm::SP1,
+                metadata.program_id().clone(),
+                metadata.version(),
+                metadata.proof_type(),
+            ),
+        ];
+        for metadata in replayed {
+            let replayed = ProofReceiptWithMetadata::new(receipt.receipt().clone(), metadata);
+            assert!(matches!(
+                ZkVmTypedVerifier::verify(&host, &replayed),
+                Err(ZkVmError::ProofVerificationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn committee_receipts_require_threshold() {
+        let keys: Vec<SigningKey> = (0..3).map(|_| SigningKey::random(&mut OsRng)).collect();
+        let members = keys.iter().map(|key| *key.verifying_key()).collect();
+        let committee = Committee::new(members, 2).unwrap();
+        assert_eq!(Committee::from_vk(&committee.vk()).unwrap(), committee);
+        let echo = |zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        };
+        let host =
+            NativeHost::new_committee(committee.clone(), [keys[2].clone(), keys[0].clone()], echo)
+                .unwrap();
+        assert_eq!(host.program_id(), committee.program_id());
+        assert_eq!(host.vk(), committee.vk());
+
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = host
+            .prove(input, ProofType::Core, &RunConfig::default())
+            .unwrap();
+        ZkVmTypedVerifier::verify(&host, &receipt).unwrap();
+
+        let mut registry = VerifierRegistry::new();
+        registry.register(
+            ZkVm::Native,
+            committee.program_id(),
+            &[ProofType::Core],
+            committee.clone(),
+        );
+        registry.verify(&receipt).unwrap();
+
+        // Dropping a signature leaves a single member, below the threshold.
+        let proof = receipt.receipt().proof().as_bytes();
+        let below_threshold = ProofReceiptWithMetadata::new(
+            ProofReceipt::new(
+                Proof::new(proof[..proof.len() / 2].to_vec()),
+                receipt.receipt().public_values().clone(),
+            ),
+            receipt.metadata().clone(),
+        );
+        assert!(matches!(
+            registry.verify(&below_threshold),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+
+        // Repeating a signature does not count the member twice.
+        let repeated = ProofReceiptWithMetadata::new(
+            ProofReceipt::new(
+                Proof::new([&proof[..proof.len() / 2], &proof[..proof.len() / 2]].concat()),
+                receipt.receipt().public_values().clone(),
+            ),
+            receipt.metadata().clone(),
+        );
+        assert!(matches!(
+            registry.verify(&repeated),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+
+        assert!(NativeHost::new_committee(committee.clone(), [keys[1].clone()], echo).is_err());
+        let outsider = SigningKey::random(&mut OsRng);
+        assert!(NativeHost::new_committee(committee, [keys[0].clone(), outsider], echo).is_err());
+    }
+
+    #[test]
+    fn verifies_aggregated_committee_proofs() {
+        let keys: Vec<SigningKey> = (0..3).map(|_| SigningKey::random(&mut OsRng)).collect();
+        let members = keys.iter().map(|key| *key.verifying_key()).collect();
+        let committee = Committee::new(members, 2).unwrap();
+        let inner = NativeHost::new_committee(committee, keys[..2].to_vec(), |zkvm| {
+            zkvm.commit_buf(&[1, 2, 3]);
+        })
+        .unwrap();
+        let config = RunConfig::default();
+        let receipt = inner
+            .prove(NativeMachine::new(), ProofType::Core, &config)
+            .unwrap();
+        let inner_vk_digest = inner.program_id().to_vk_digest();
+
+        let aggregator = NativeHost::new_with_random_key(move |zkvm: &NativeMachine| {
+            let public_values = zkvm.read_verified_buf(&inner_vk_digest);
+            zkvm.commit_buf(&public_values);
+        });
+        let mut builder = NativeMachineInputBuilder::new();
+        builder
+            .write_proof(&AggregationInput::new(receipt, inner.vk()))
+            .unwrap();
+        let summary = aggregator
+            .execute(builder.build().unwrap(), &config)
+            .unwrap();
+        assert_eq!(summary.public_values().as_bytes(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn seeded_hosts_verify_each_other() {
+        let echo = |zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        };
+        let host = NativeHost::new_from_seed(b"devnet", "echo", echo);
+        // Stands in for a host rebuilt by another process from the same seed.
+        let rebuilt = NativeHost::new_from_seed(b"devnet", "echo", echo);
+        let other = NativeHost::new_from_seed(b"devnet", "other", echo);
+        assert_eq!(host.program_id(), rebuilt.program_id());
+        assert_ne!(host.program_id(), other.program_id());
+
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = host
+            .prove(input, ProofType::Core, &RunConfig::default())
+            .unwrap();
+        ZkVmTypedVerifier::verify(&rebuilt, &receipt).unwrap();
+        assert!(ZkVmTypedVerifier::verify(&other, &receipt).is_err());
+
+        // A verifier only needs the verifying key or program id of the host.
+        let verifier = NativeVerifier::from_vk(&host.vk()).unwrap();
+        assert_eq!(verifier, host.verifier());
+        assert_eq!(
+            NativeVerifier::from_program_id(&host.program_id()).unwrap(),
+            verifier
+        );
+        ZkVmTypedVerifier::verify(&verifier, &receipt).unwrap();
+    }
+
+    #[test]
+    fn dev_mode_receipts_require_explicit_opt_in() {
+        let echo = |zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        };
+        let host = NativeHost::new_with_random_key(echo);
+        let mock = MockHost::new(host.clone());
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = mock
+            .prove(input, ProofType::Groth16, &RunConfig::default())
+            .unwrap();
+        assert!(receipt.metadata().is_dev_mode());
+        assert_eq!(receipt.metadata().program_id(), &host.program_id());
+        assert_eq!(receipt.receipt().public_values().as_bytes(), &[1, 2, 3]);
+
+        // The flag survives encoding, so a decoded receipt is rejected all the same.
+        let decoded = ProofReceiptWithMetadata::decode(&receipt.encode()).unwrap();
+        assert_eq!(decoded, receipt);
+        for verify in [
+            ZkVmTypedVerifier::verify(&mock, &decoded),
+            ZkVmTypedVerifier::verify(&host, &decoded),
+        ] {
+            assert!(matches!(
+                verify,
+                Err(ZkVmError::DevModeReceipt { zkvm: ZkVm::Native })
+            ));
+        }
+
+        let mut registry = VerifierRegistry::new();
+        registry.register_host(mock.clone());
+        assert!(matches!(
+            registry.verify(&decoded),
+            Err(ZkVmError::DevModeReceipt { .. })
+        ));
+        registry.register(
+            ZkVm::Native,
+            host.program_id(),
+            &[ProofType::Groth16],
+            AllowDevMode::new(mock),
+        );
+        registry.verify(&decoded).unwrap();
+
+        // Allowing dev mode does not make a real verifier accept an empty proof.
+        assert!(AllowDevMode::new(host).verify(&decoded).is_err());
+    }
+
+    #[test]
+    fn serde_round_tripped_dev_mode_receipts_are_still_rejected() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        });
+        let mock = MockHost::new(host.clone());
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = mock
+            .prove(input, ProofType::Groth16, &RunConfig::default())
+            .unwrap();
+
+        // The serde layout drops the flag, but there is still no proof to verify.
+        let bytes = bincode::serialize(&receipt).unwrap();
+        let round_tripped: ProofReceiptWithMetadata = bincode::deserialize(&bytes).unwrap();
+        assert!(!round_tripped.metadata().is_dev_mode());
+        assert!(matches!(
+            ZkVmTypedVerifier::verify(&host, &round_tripped),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+        for verify in [
+            ZkVmTypedVerifier::verify(&mock, &round_tripped),
+            AllowDevMode::new(mock.clone()).verify(&round_tripped),
+        ] {
+            assert!(matches!(verify, Err(ZkVmError::InvalidProofReceipt(_))));
+        }
+    }
+
+    #[test]
+    fn upgrades_receipts_without_executing() {
+        let host = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            let buf = zkvm.read_buf();
+            zkvm.commit_buf(&buf);
+        });
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let core = host
+            .prove(input, ProofType::Core, &RunConfig::default())
+            .unwrap();
+
+        let compressed = host.compress(&core).unwrap();
+        assert_eq!(compressed.metadata().proof_type(), ProofType::Compressed);
+        let groth16 = host.wrap_groth16(&compressed).unwrap();
+        assert_eq!(groth16.metadata().proof_type(), ProofType::Groth16);
+        for receipt in [&compressed, &groth16] {
+            assert_eq!(
+                receipt.receipt().public_values(),
+                core.receipt().public_values()
+            );
+            ZkVmTypedVerifier::verify(&host, receipt).unwrap();
+        }
+
+        // Only receipts that verify are upgraded.
+        let other = NativeHost::new_with_random_key(|_: &NativeMachine| {});
+        assert!(other.compress(&core).is_err());
+        assert!(other.wrap_groth16(&core).is_err());
+
+        // A mock receipt is relabeled, and stays flagged as dev mode.
+        let mock = MockHost::new(host.clone());
+        let mut input = NativeMachine::new();
+        input.write_slice(vec![1, 2, 3]);
+        let receipt = mock
+            .prove(input, ProofType::Core, &RunConfig::default())
+            .unwrap();
+        let wrapped = mock.wrap_groth16(&receipt).unwrap();
+        assert_eq!(wrapped.metadata().proof_type(), ProofType::Groth16);
+        assert!(wrapped.metadata().is_dev_mode());
+        assert!(mock.compress(&core).is_err());
     }
 }
@@ -33,8 +33,9 @@ impl ZkVmInputBuilder<'_> for NativeMachineInputBuilder {
     }
 
     fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self> {
-        // For the native mode we only write the public values since the proof is expected to be
-        // empty
+        // The public values are read by the guest like any other input, while the receipt and
+        // the verifying key are kept aside for `verify_native_proof`.
+        self.0.write_proof(item.clone());
         self.write_buf(item.receipt().receipt().public_values().as_bytes())
     }
 
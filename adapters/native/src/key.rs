@@ -0,0 +1,234 @@
+//! Derivation and persistence of the signing keys of native hosts.
+//!
+//! A [`NativeHost`](crate::NativeHost) signs its receipts with a Schnorr key, and its verifying
+//! key and [`ProgramId`](zkaleido::ProgramId) are derived from it. Hosts in different processes
+//! therefore only verify each other's receipts if they use the same key: derive it from a shared
+//! seed and a per-program label with [`derive_signing_key`], or share it through a file with
+//! [`save_signing_key`] and [`load_signing_key`].
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use k256::schnorr::SigningKey;
+use rand_core::OsRng;
+use sha2::{Digest, Sha256};
+use zkaleido::fs::temp_path_for;
+
+/// Domain separator hashed into every derived signing key.
+const DERIVATION_DOMAIN: &[u8] = b"zkaleido-native-key-v1";
+
+/// Length of a serialized signing key.
+const SIGNING_KEY_LEN: usize = 32;
+
+/// Deterministically derives a signing key from `seed` and `label`.
+///
+/// The same seed and label always give the same key, so every process sharing the seed derives
+/// the same verifying key and program id for a given label. Use the name of the program as the
+/// label to give each program its own key.
+///
+/// Anyone knowing the seed can forge receipts, so keep it secret outside of tests and devnets.
+pub fn derive_signing_key(seed: &[u8], label: &str) -> SigningKey {
+    // Hashes land outside the scalar field with negligible probability; retry with a counter.
+    (0u32..)
+        .find_map(|counter| {
+            let mut hasher = Sha256::new();
+            hasher.update(DERIVATION_DOMAIN);
+            hasher.update((seed.len() as u64).to_le_bytes());
+            hasher.update(seed);
+            hasher.update((label.len() as u64).to_le_bytes());
+            hasher.update(label.as_bytes());
+            hasher.update(counter.to_le_bytes());
+            SigningKey::from_bytes(&hasher.finalize()).ok()
+        })
+        .expect("a valid signing key is derived within a few attempts")
+}
+
+/// Loads a signing key saved by [`save_signing_key`].
+///
+/// Fails with [`io::ErrorKind::InvalidData`] if the file does not hold a valid key.
+pub fn load_signing_key(path: impl AsRef<Path>) -> io::Result<SigningKey> {
+    let bytes = fs::read(path)?;
+    if bytes.len() != SIGNING_KEY_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected a {SIGNING_KEY_LEN}-byte signing key, found {} bytes",
+                bytes.len()
+            ),
+        ));
+    }
+    SigningKey::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Saves `key` to `path` as its 32 raw bytes, replacing any existing file.
+///
+/// The key is written to a temporary file next to `path`, which is then renamed into place, so
+/// readers never observe a partially written key. On Unix, the file is only readable and
+/// writable by its owner.
+pub fn save_signing_key(key: &SigningKey, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    publish_key_file(key, path, |tmp_path| fs::rename(tmp_path, path))
+}
+
+/// Loads the signing key at `path`, or generates a random key and saves it there if the file
+/// does not exist.
+///
+/// Every process pointed at the same path then signs with the same key: if several processes
+/// create the file at once, only the first key is saved and the others load it. On Unix, group
+/// and other permissions are removed from an existing file.
+pub fn load_or_create_signing_key(path: impl AsRef<Path>) -> io::Result<SigningKey> {
+    let path = path.as_ref();
+    match load_signing_key(path) {
+        Ok(key) => {
+            restrict_permissions(path)?;
+            Ok(key)
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let key = SigningKey::random(&mut OsRng);
+            // Unlike a rename, linking never replaces an existing file.
+            match publish_key_file(&key, path, |tmp_path| fs::hard_link(tmp_path, path)) {
+                Ok(()) => Ok(key),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => load_signing_key(path),
+                Err(e) => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes `key` to a new temporary file next to `path` and moves it to `path` with `publish`.
+///
+/// The temporary file is created with `create_new`, only accessible by its owner on Unix, and
+/// synced before being published. It is removed if it is still there afterwards.
+fn publish_key_file(
+    key: &SigningKey,
+    path: &Path,
+    publish: impl FnOnce(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let tmp_path = temp_path_for(path);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let result = options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(&key.to_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| publish(&tmp_path));
+    // Best effort: the temporary file is already gone after a successful rename.
+    let _ = fs::remove_file(&tmp_path);
+    result
+}
+
+/// Removes the group and other permissions of the file at `path`. Does nothing outside Unix.
+fn restrict_permissions(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)?.permissions().mode();
+        if mode & 0o077 != 0 {
+            fs::set_permissions(path, fs::Permissions::from_mode(mode & !0o077))?;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    /// Signing keys are compared through their public keys, since they do not implement
+    /// `PartialEq`.
+    fn public_key(key: &SigningKey) -> [u8; SIGNING_KEY_LEN] {
+        key.verifying_key().to_bytes().into()
+    }
+
+    fn derived(seed: &[u8], label: &str) -> [u8; SIGNING_KEY_LEN] {
+        public_key(&derive_signing_key(seed, label))
+    }
+
+    #[test]
+    fn derived_keys_are_stable_per_label() {
+        let key = derived(b"devnet", "fibonacci");
+        assert_eq!(key, derived(b"devnet", "fibonacci"));
+        assert_ne!(key, derived(b"devnet", "sha2-chain"));
+        assert_ne!(key, derived(b"testnet", "fibonacci"));
+        // The lengths are hashed, so moving bytes between seed and label changes the key.
+        assert_ne!(derived(b"ab", "c"), derived(b"a", "bc"));
+    }
+
+    #[test]
+    fn saved_keys_load_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native.key");
+
+        let created = public_key(&load_or_create_signing_key(&path).unwrap());
+        assert_eq!(
+            public_key(&load_or_create_signing_key(&path).unwrap()),
+            created
+        );
+
+        let key = derive_signing_key(b"devnet", "fibonacci");
+        save_signing_key(&key, &path).unwrap();
+        assert_eq!(
+            public_key(&load_signing_key(&path).unwrap()),
+            public_key(&key)
+        );
+
+        fs::write(&path, [1, 2, 3]).unwrap();
+        assert_eq!(
+            load_signing_key(&path).err().map(|e| e.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        // Only the key file is left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn concurrent_creations_agree_on_one_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native.key");
+
+        let keys: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| public_key(&load_or_create_signing_key(&path).unwrap())))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(keys.iter().all(|key| *key == keys[0]));
+        assert_eq!(public_key(&load_signing_key(&path).unwrap()), keys[0]);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native.key");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        save_signing_key(&derive_signing_key(b"devnet", "fibonacci"), &path).unwrap();
+        assert_eq!(mode(&path), 0o600);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        load_or_create_signing_key(&path).unwrap();
+        assert_eq!(mode(&path), 0o600);
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! In native mode, the proof statements are executed directly in Rust, producing the
 //! **expected public values**. When proving, the adapter generates a **Schnorr signature**
-//! over the public values instead of a zero-knowledge proof. This approach
+//! over the public values instead of a zero-knowledge proof, and proofs aggregated by the guest
+//! are checked by verifying that signature. This approach
 //! bypasses the usual process of ELF generation and ZKP generation used by the ZkVM, making it
 //! useful for:
 //!
@@ -16,11 +17,22 @@
 //!   before integrating a real ZKP backend.
 //! - **Prototyping**: Get immediate feedback on how your code behaves in a ZkVM-like environment
 //!   without implementing a complete proof system.
+//! - **Attestation-based test networks**: A [`Committee`] of operators signs the public values
+//!   instead of a single key, and receipts verify once a threshold of members signed.
+//! - **Multi-process tests and devnets**: Hosts derive their signing key from a shared seed with
+//!   [`NativeHost::new_from_seed`] or load it from disk with [`load_signing_key`], and processes
+//!   that only verify rebuild a [`NativeVerifier`] from the verifying key.
 
+mod committee;
 mod env;
 mod host;
 mod input;
+mod key;
 mod proof;
+mod verifier;
 
-pub use env::NativeMachine;
+pub use committee::Committee;
+pub use env::{NativeAbort, NativeMachine, NativeMachineState};
 pub use host::NativeHost;
+pub use key::{derive_signing_key, load_or_create_signing_key, load_signing_key, save_signing_key};
+pub use verifier::NativeVerifier;
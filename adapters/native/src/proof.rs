@@ -1,4 +1,11 @@
-use zkaleido::{ProofReceiptWithMetadata, ZkVmProofError};
+use k256::schnorr::{Signature, VerifyingKey, signature::Verifier};
+use zkaleido::{
+    ProofMetadata, ProofReceiptWithMetadata, PublicValues, ZkVm, ZkVmError, ZkVmProofError,
+    ZkVmResult,
+};
+
+/// Domain separator prepended to the message signed by native proofs.
+const SIGNING_DOMAIN: &[u8] = b"zkaleido-native-proof-v1";
 
 #[derive(Debug, Clone)]
 pub struct NativeProofReceipt(ProofReceiptWithMetadata);
@@ -16,3 +23,61 @@ impl TryFrom<NativeProofReceipt> for ProofReceiptWithMetadata {
         Ok(value.0)
     }
 }
+
+/// Returns the message signed by the proof of a native receipt.
+///
+/// The message binds the public values to the program id, proof type and version of the
+/// metadata, so that a signature cannot be replayed as a proof of another program or of another
+/// proof type.
+pub(crate) fn signing_message(metadata: &ProofMetadata, public_values: &PublicValues) -> Vec<u8> {
+    let version = metadata.version().as_bytes();
+    let public_values = public_values.as_bytes();
+    let mut message =
+        Vec::with_capacity(SIGNING_DOMAIN.len() + 32 + 1 + 4 + version.len() + public_values.len());
+    message.extend_from_slice(SIGNING_DOMAIN);
+    message.extend_from_slice(&metadata.program_id().0);
+    message.push(metadata.proof_type() as u8);
+    message.extend_from_slice(&(version.len() as u32).to_le_bytes());
+    message.extend_from_slice(version);
+    message.extend_from_slice(public_values);
+    message
+}
+
+/// Verifies the Schnorr signature that serves as the proof of a native receipt.
+///
+/// The receipt must come from the native zkVM and name the program of `key`, which is the
+/// program id of the native host holding the matching signing key.
+pub(crate) fn verify_signature(
+    key: &VerifyingKey,
+    receipt: &ProofReceiptWithMetadata,
+) -> ZkVmResult<()> {
+    let metadata = receipt.metadata();
+    if *metadata.zkvm() != ZkVm::Native {
+        return Err(ZkVmError::ProofVerificationError(format!(
+            "expected a native receipt, got a {} receipt",
+            metadata.zkvm()
+        )));
+    }
+    let program_id: [u8; 32] = key.to_bytes().into();
+    if metadata.program_id().0 != program_id {
+        return Err(ZkVmError::ProofVerificationError(
+            "receipt is for another program".to_string(),
+        ));
+    }
+
+    // `Signature::try_from` panics on inputs shorter than a signature.
+    let proof = receipt.receipt().proof().as_bytes();
+    if proof.len() != Signature::BYTE_SIZE {
+        return Err(ZkVmError::ProofVerificationError(format!(
+            "expected a {}-byte signature, got {} bytes",
+            Signature::BYTE_SIZE,
+            proof.len()
+        )));
+    }
+    let signature = Signature::try_from(proof)
+        .map_err(|e| ZkVmError::ProofVerificationError(format!("invalid signature: {e}")))?;
+    let message = signing_message(metadata, receipt.receipt().public_values());
+    key.verify(&message, &signature).map_err(|e| {
+        ZkVmError::ProofVerificationError(format!("signature verification failed: {e}"))
+    })
+}
@@ -0,0 +1,93 @@
+use k256::schnorr::VerifyingKey as SchnorrVerifyingKey;
+use zkaleido::{
+    DataFormatError, ProgramId, ProofReceiptWithMetadata, VerifyingKey, ZkVmError, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVerifyingKeyError,
+};
+
+use crate::{
+    committee::Committee,
+    proof::{NativeProofReceipt, verify_signature},
+};
+
+/// Length of the verifying key of a single-key host. A committee verifying key is never this
+/// long, see [`Committee::vk`].
+const SCHNORR_VK_LEN: usize = 32;
+
+/// Verifies native receipts given only the verifying key of the host that signed them.
+///
+/// Unlike a [`NativeHost`](crate::NativeHost), a `NativeVerifier` holds no signing key, so a
+/// process that only verifies can be handed the [`VerifyingKey`] of the prover, or the
+/// [`ProgramId`] it was registered under, and rebuild a verifier from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeVerifier(NativeVerifierKey);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NativeVerifierKey {
+    Key(SchnorrVerifyingKey),
+    Committee(Committee),
+}
+
+impl NativeVerifier {
+    /// Creates a verifier from the verifying key of a native host or of a [`Committee`].
+    pub fn from_vk(vk: &VerifyingKey) -> ZkVmResult<Self> {
+        if vk.as_bytes().len() != SCHNORR_VK_LEN {
+            return Committee::from_vk(vk).map(Self::from_committee);
+        }
+        let key = SchnorrVerifyingKey::from_bytes(vk.as_bytes()).map_err(|e| {
+            ZkVmVerifyingKeyError::DataFormat(DataFormatError::Other(e.to_string()))
+        })?;
+        Ok(Self::from_key(key))
+    }
+
+    /// Creates a verifier from the program id of a single-key native host, which is its
+    /// Schnorr public key.
+    ///
+    /// The program id of a [`Committee`] is a hash, so committees are rebuilt with
+    /// [`from_vk`](Self::from_vk) instead.
+    pub fn from_program_id(program_id: &ProgramId) -> ZkVmResult<Self> {
+        Self::from_vk(&VerifyingKey::new(program_id.0.to_vec()))
+    }
+
+    pub(crate) fn from_key(key: SchnorrVerifyingKey) -> Self {
+        Self(NativeVerifierKey::Key(key))
+    }
+
+    pub(crate) fn from_committee(committee: Committee) -> Self {
+        Self(NativeVerifierKey::Committee(committee))
+    }
+
+    /// Returns the verifying key this verifier checks receipts against.
+    pub fn vk(&self) -> VerifyingKey {
+        match &self.0 {
+            NativeVerifierKey::Key(key) => VerifyingKey::new(key.to_bytes().to_vec()),
+            NativeVerifierKey::Committee(committee) => committee.vk(),
+        }
+    }
+
+    /// Returns the program id of the receipts this verifier accepts.
+    pub fn program_id(&self) -> ProgramId {
+        match &self.0 {
+            NativeVerifierKey::Key(key) => ProgramId(key.to_bytes().into()),
+            NativeVerifierKey::Committee(committee) => committee.program_id(),
+        }
+    }
+
+    pub(crate) fn verify_receipt(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        match &self.0 {
+            NativeVerifierKey::Key(key) => verify_signature(key, receipt),
+            NativeVerifierKey::Committee(committee) => committee.verify_receipt(receipt),
+        }
+    }
+}
+
+impl ZkVmTypedVerifier for NativeVerifier {
+    type ZkVmProofReceipt = NativeProofReceipt;
+
+    fn verify_inner(&self, receipt: &NativeProofReceipt) -> ZkVmResult<()> {
+        let receipt: ProofReceiptWithMetadata = receipt
+            .clone()
+            .try_into()
+            .map_err(ZkVmError::InvalidProofReceipt)?;
+        self.verify_receipt(&receipt)
+    }
+}
@@ -1,6 +1,6 @@
 use risc0_zkvm::{guest::env, serde::from_slice};
 use serde::{Serialize, de::DeserializeOwned};
-use zkaleido::{ZkVmEnv, ZkVmEnvSerde};
+use zkaleido::{DataFormatError, ZkVmEnv, ZkVmEnvError, ZkVmEnvResult, ZkVmEnvSerde};
 
 /// An environment adapter for the RISC0 system implementing [`ZkVmEnv`].
 ///
@@ -23,25 +23,46 @@ impl ZkVmEnv for Risc0ZkVmEnv {
         env::commit_slice(output_raw);
     }
 
+    fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]) {
+        if let Err(error) = self.try_verify_native_proof(vk_digest, public_values) {
+            panic!("{error}")
+        }
+    }
+
+    /// Adds the proof as an assumption of the receipt, which is resolved when the guest is
+    /// proven, so a proof that does not verify makes the whole execution unprovable.
     // FIXME: This is not consistent with SP1 where the vk_digest is passed
-    fn verify_native_proof(&self, _vk_digest: &[u32; 8], public_values: &[u8]) {
+    fn try_verify_native_proof(
+        &self,
+        _vk_digest: &[u32; 8],
+        public_values: &[u8],
+    ) -> ZkVmEnvResult<()> {
         let vk: [u32; 8] = env::read();
-        env::verify(vk, public_values).expect("verification failed")
+        env::verify(vk, public_values).map_err(|e| ZkVmEnvError::ProofVerification(e.to_string()))
     }
-}
 
-/// Overrides the default bincode-based implementations with RISC0-specific I/O.
-impl ZkVmEnvSerde for Risc0ZkVmEnv {
-    fn read_serde<T: DeserializeOwned>(&self) -> T {
-        env::read()
+    /// Writes the message to stderr and exits the guest with the exit code.
+    fn abort(&self, exit_code: u8, message: &str) -> ! {
+        eprintln!("guest aborted with exit code {exit_code}: {message}");
+        env::exit(exit_code.max(1))
     }
+}
 
+/// Commits through RISC0-specific I/O.
+///
+/// Inputs are read with the default implementation, which decodes a `bincode` buffer as written
+/// by the host. Outputs committed with `CanonicalSerdeCodec` bypass `commit_serde`, so they match
+/// the other zkVMs byte for byte.
+impl ZkVmEnvSerde for Risc0ZkVmEnv {
     fn commit_serde<T: Serialize>(&self, output: &T) {
         env::commit(output);
     }
 
-    fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T {
-        let buf = self.read_verified_buf(vk_digest);
-        from_slice(&buf).expect("risc0 zkvm deserialization failed")
+    fn try_read_verified_serde<T: DeserializeOwned>(
+        &self,
+        vk_digest: &[u32; 8],
+    ) -> ZkVmEnvResult<T> {
+        let buf = self.try_read_verified_buf(vk_digest)?;
+        from_slice(&buf).map_err(|e| DataFormatError::Serde(e.to_string()).into())
     }
 }
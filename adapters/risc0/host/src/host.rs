@@ -1,8 +1,17 @@
 use std::fmt;
 
 use hex::encode;
-use risc0_zkvm::{compute_image_id, sha::Digest};
-use zkaleido::{ZkVm, ZkVmHost};
+use risc0_binfmt::ProgramBinary;
+use risc0_zkvm::sha::Digest;
+use zkaleido::{EM_RISCV, ElfClass, ElfTarget, ZkVm, ZkVmError, ZkVmHost, ZkVmResult};
+
+/// The guest ELFs RISC0 runs, built for `riscv32im-risc0-zkvm-elf`.
+const RISC0_ELF: ElfTarget = ElfTarget {
+    name: "riscv32im-risc0-zkvm-elf",
+    class: ElfClass::Elf32,
+    machine: EM_RISCV,
+    required_sections: &[".text"],
+};
 
 /// A host for the `Risc0` zkVM that stores the guest program in ELF format.
 ///
@@ -19,12 +28,35 @@ pub struct Risc0Host {
 
 impl Risc0Host {
     /// Initializes the Risc0Host with the given ELF.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ELF is invalid, see [`Risc0Host::try_init`].
     pub fn init(elf: &[u8]) -> Self {
-        let image_id = compute_image_id(elf).expect("invalid elf");
-        Risc0Host {
+        Self::try_init(elf).expect("invalid elf")
+    }
+
+    /// Initializes the Risc0Host with the given ELF, checking it first.
+    ///
+    /// `elf` is a RISC0 program binary, as embedded by `risc0-build`, which bundles the guest ELF
+    /// with the kernel ELF supplied by RISC0. The guest ELF must be a `riscv32im` executable with
+    /// a `.text` section whose entry point lies in an executable segment. Returns
+    /// [`ZkVmError::InvalidELF`] otherwise, or if the image id cannot be computed from the ELFs.
+    pub fn try_init(elf: &[u8]) -> ZkVmResult<Self> {
+        let binary = ProgramBinary::decode(elf).map_err(|e| match RISC0_ELF.validate(elf) {
+            // Explain why a bare ELF, such as one built for another zkVM, is not a RISC0 guest.
+            Err(invalid) => invalid,
+            Ok(()) => ZkVmError::InvalidELF(format!("not a RISC0 program binary: {e}")),
+        })?;
+        RISC0_ELF.validate(binary.user_elf)?;
+
+        let image_id = binary
+            .compute_image_id()
+            .map_err(|e| ZkVmError::InvalidELF(e.to_string()))?;
+        Ok(Risc0Host {
             elf: elf.to_vec(),
             image_id,
-        }
+        })
     }
 
     /// Returns a reference to the guest program in ELF format.
@@ -36,6 +68,16 @@ impl Risc0Host {
     pub fn image_id(&self) -> Digest {
         self.image_id
     }
+
+    /// Creates a host for the program with image id `image_id`, without its ELF, to test the
+    /// code paths that never run the guest.
+    #[cfg(test)]
+    pub(crate) fn without_elf(image_id: Digest) -> Self {
+        Risc0Host {
+            elf: Vec::new(),
+            image_id,
+        }
+    }
 }
 
 impl ZkVmHost for Risc0Host {
@@ -49,3 +91,32 @@ impl fmt::Debug for Risc0Host {
         write!(f, "risc0_{}", encode(self.image_id.as_bytes()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(result: ZkVmResult<Risc0Host>) -> String {
+        match result {
+            Err(ZkVmError::InvalidELF(reason)) => reason,
+            other => panic!("expected InvalidELF, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_elfs_that_are_not_risc0_guests() {
+        // The header of a 64-bit ELF, as built for SP1.
+        let mut elf64 = b"\x7fELF\x02\x01\x01".to_vec();
+        elf64.resize(64, 0);
+        assert!(reason(Risc0Host::try_init(&elf64)).contains("expected Elf32, got Elf64"));
+
+        assert!(reason(Risc0Host::try_init(b"not an elf")).contains("missing ELF magic"));
+
+        // The header of a 32-bit RISC-V ELF, which is not wrapped in a program binary.
+        let mut elf32 = b"\x7fELF\x01\x01\x01".to_vec();
+        elf32.resize(52, 0);
+        elf32[16..18].copy_from_slice(&2u16.to_le_bytes());
+        elf32[18..20].copy_from_slice(&EM_RISCV.to_le_bytes());
+        assert!(reason(Risc0Host::try_init(&elf32)).contains("entry point"));
+    }
+}
@@ -1,10 +1,11 @@
+use std::{mem, path::PathBuf};
+
 use risc0_zkvm::{ExecutorEnv, ExecutorEnvBuilder, sha::Digest};
 use zkaleido::{
     AggregationInput, DataFormatError, ZkVmInputBuilder, ZkVmInputError, ZkVmInputResult,
-    ZkVmVerifyingKeyError,
+    ZkVmResult, ZkVmVerifyingKeyError,
 };
-
-use crate::proof::Risc0ProofReceipt;
+use zkaleido_risc0_verifier::Risc0ProofReceipt;
 
 /// A proof input builder for the RISC0 host environment.
 ///
@@ -18,8 +19,33 @@ use crate::proof::Risc0ProofReceipt;
 )]
 pub struct Risc0ProofInputBuilder<'a>(ExecutorEnvBuilder<'a>);
 
+/// The input of the RISC0 host, as built by [`Risc0ProofInputBuilder`].
+///
+/// This holds the executor environment before it is built, so that the host can still apply
+/// the [`RunConfig`](zkaleido::RunConfig), such as enabling the profiler, when it runs the
+/// guest.
+#[expect(
+    missing_debug_implementations,
+    reason = "RISC0's ExecutorEnvBuilder does not implement Debug"
+)]
+pub struct Risc0Input<'a>(ExecutorEnvBuilder<'a>);
+
+impl<'a> Risc0Input<'a> {
+    /// Builds the executor environment, writing a profiling trace to `trace_path` if set.
+    pub(crate) fn into_env(mut self, trace_path: Option<PathBuf>) -> ZkVmResult<ExecutorEnv<'a>> {
+        if let Some(trace_path) = trace_path {
+            self.0.enable_profiler(trace_path);
+        }
+        let env = self
+            .0
+            .build()
+            .map_err(|e| ZkVmInputError::InputBuild(e.to_string()))?;
+        Ok(env)
+    }
+}
+
 impl<'a> ZkVmInputBuilder<'a> for Risc0ProofInputBuilder<'a> {
-    type Input = ExecutorEnv<'a>;
+    type Input = Risc0Input<'a>;
     type ZkVmProofReceipt = Risc0ProofReceipt;
 
     fn new() -> Self {
@@ -27,11 +53,11 @@ impl<'a> ZkVmInputBuilder<'a> for Risc0ProofInputBuilder<'a> {
         Self(env_builder)
     }
 
+    // Serde inputs are framed like every other input, so guests can also read them as buffers.
     fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
-        self.0
-            .write(item)
+        let slice = bincode::serialize(item)
             .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
-        Ok(self)
+        self.write_buf(&slice)
     }
 
     // TODO: replace this with `write_frame` once the API stabilizies
@@ -71,8 +97,6 @@ impl<'a> ZkVmInputBuilder<'a> for Risc0ProofInputBuilder<'a> {
     }
 
     fn build(&mut self) -> ZkVmInputResult<Self::Input> {
-        self.0
-            .build()
-            .map_err(|e| ZkVmInputError::InputBuild(e.to_string()))
+        Ok(Risc0Input(mem::take(&mut self.0)))
     }
 }
@@ -6,11 +6,11 @@
 
 mod host;
 mod input;
-mod proof;
 mod prover;
 mod verifier;
 
 pub use host::Risc0Host;
+pub use zkaleido_risc0_verifier::Risc0ReceiptVerifier;
 
 #[cfg(feature = "perf")]
 mod perf;
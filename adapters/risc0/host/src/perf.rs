@@ -1,11 +1,13 @@
-use std::{env, rc::Rc};
+use std::rc::Rc;
 
 use risc0_groth16::prove::shrink_wrap;
 use risc0_zkvm::{
     ExecutorImpl, ProverOpts, ProverServer, Receipt, Session, VerifierContext, get_prover_server,
     sha::Digest,
 };
-use zkaleido::{PerformanceReport, ProofMetrics, ZkVmExecutor, ZkVmHostPerf, time_operation};
+use zkaleido::{
+    PerformanceReport, ProofMetrics, RunConfig, ZkVmExecutor, ZkVmHostPerf, time_operation,
+};
 
 use crate::Risc0Host;
 
@@ -13,7 +15,11 @@ impl ZkVmHostPerf for Risc0Host {
     fn perf_report<'a>(
         &self,
         input: <Self::Input<'a> as zkaleido::ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
     ) -> zkaleido::PerformanceReport {
+        let env = input
+            .into_env(config.trace_path(&format!("{self:?}")))
+            .unwrap();
         let elf = self.get_elf();
         let image_id = self.program_id().0;
 
@@ -21,22 +27,18 @@ impl ZkVmHostPerf for Risc0Host {
         let prover = get_prover_server(&opts).unwrap();
 
         // Generate the session.
-        let mut exec = ExecutorImpl::from_elf(input, elf).unwrap();
+        let mut exec = ExecutorImpl::from_elf(env, elf).unwrap();
         let (session, execution_duration) = time_operation(|| exec.run().unwrap());
         let shards = session.segments.len();
         let cycles = session.user_cycles;
 
-        // If the environment variable "ZKVM_MOCK" is set to "1" or "true" (case-insensitive),
-        // then do not generate the proof metrics
-        let (core_proof_report, compressed_proof_report, groth16_proof_report) =
-            if env::var("ZKVM_MOCK")
-                .map(|v| v == "1" || v.to_lowercase() == "true")
-                .unwrap_or(false)
-            {
-                (None, None, None)
-            } else {
-                gen_proof_metrics(prover, session, image_id, cycles)
-            };
+        // In dev mode, do not generate the proof metrics
+        let (core_proof_report, compressed_proof_report, groth16_proof_report) = if config.dev_mode
+        {
+            (None, None, None)
+        } else {
+            gen_proof_metrics(prover, session, image_id, cycles)
+        };
 
         PerformanceReport::new(
             shards,
@@ -1,12 +1,11 @@
-use std::env::{set_var, var};
-
-use risc0_zkvm::{ProverOpts, default_executor, default_prover};
+use risc0_zkvm::{ExitCode, InnerReceipt, ProverOpts, default_executor, default_prover};
 use zkaleido::{
-    ExecutionSummary, ProgramId, ProofType, PublicValues, ZkVmError, ZkVmExecutor,
-    ZkVmInputBuilder, ZkVmProver, ZkVmResult,
+    ExecutionSummary, ProgramId, ProofMetadata, ProofReceiptWithMetadata, ProofType, PublicValues,
+    RunConfig, ZkVmError, ZkVmExecutor, ZkVmInputBuilder, ZkVmProver, ZkVmResult,
 };
+use zkaleido_risc0_verifier::Risc0ProofReceipt;
 
-use crate::{Risc0Host, input::Risc0ProofInputBuilder, proof::Risc0ProofReceipt};
+use crate::{Risc0Host, input::Risc0ProofInputBuilder};
 
 impl ZkVmExecutor for Risc0Host {
     type Input<'a> = Risc0ProofInputBuilder<'a>;
@@ -14,14 +13,18 @@ impl ZkVmExecutor for Risc0Host {
     fn execute<'a>(
         &self,
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
     ) -> ZkVmResult<ExecutionSummary> {
+        let env = prover_input.into_env(config.trace_path(&format!("{self:?}")))?;
         let executor = default_executor();
 
-        let session_info = executor
-            .execute(prover_input, self.get_elf())
-            .map_err(|e| ZkVmError::ExecutionError(e.to_string()))?;
+        let session_info = executor.execute(env, self.get_elf()).map_err(|e| {
+            let message = e.to_string();
+            guest_panic(&message).unwrap_or(ZkVmError::ExecutionError(message))
+        })?;
 
         let cycles = session_info.cycles();
+        ensure_clean_exit(session_info.exit_code, cycles)?;
         let public_values = PublicValues::new(session_info.journal.bytes);
 
         Ok(ExecutionSummary::new(public_values, cycles, None))
@@ -31,16 +34,6 @@ impl ZkVmExecutor for Risc0Host {
         self.elf()
     }
 
-    fn save_trace(&self, trace_name: &str) {
-        let profiling_file_name = format!("{}_{:?}.trace_profile", trace_name, self);
-        // SAFETY: RISC0 consumes this process-global trace setting from the
-        // environment. Callers must configure tracing before concurrent prover
-        // work starts.
-        unsafe {
-            set_var("RISC0_PPROF_OUT", profiling_file_name);
-        }
-    }
-
     fn program_id(&self) -> ProgramId {
         ProgramId(self.image_id().into())
     }
@@ -53,36 +46,209 @@ impl ZkVmProver for Risc0Host {
         &self,
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
+        config: &RunConfig,
     ) -> ZkVmResult<Risc0ProofReceipt> {
-        // If the environment variable "ZKVM_MOCK" is set to "1" or "true" (case-insensitive),
-        // then enable "RISC0_DEV_MODE" . This effectively enables the mock mode in the Risc0
-        // prover.
-        if var("ZKVM_MOCK")
-            .map(|v| v == "1" || v.to_lowercase() == "true")
-            .unwrap_or(false)
-        {
-            // SAFETY: RISC0's prover SDK reads mock mode from this
-            // process-global environment variable. This preserves the existing
-            // mock-mode API and must not race with other environment access.
-            unsafe {
-                set_var("RISC0_DEV_MODE", "true");
-            }
-        }
+        let env = prover_input.into_env(config.trace_path(&format!("{self:?}")))?;
 
-        // Setup the prover
+        // Setup the prover. Dev mode makes the RISC0 prover generate fake receipts.
         let opts = match proof_type {
             ProofType::Core => ProverOpts::default(),
             ProofType::Compressed => ProverOpts::succinct(),
             ProofType::Groth16 => ProverOpts::groth16(),
-        };
+            ProofType::Plonk => {
+                return Err(ZkVmError::ProofGenerationError(
+                    "RISC0 does not generate Plonk proofs".to_string(),
+                ));
+            }
+        }
+        .with_dev_mode(config.dev_mode);
 
         let prover = default_prover();
 
         // Generate the proof
         let proof_info = prover
-            .prove_with_opts(prover_input, self.get_elf(), &opts)
+            .prove_with_opts(env, self.get_elf(), &opts)
+            .map_err(|e| {
+                let message = e.to_string();
+                guest_panic(&message).unwrap_or(ZkVmError::ProofGenerationError(message))
+            })?;
+
+        // Like the executor, the prover proves guests that halt with a non-zero exit code.
+        let claim = proof_info
+            .receipt
+            .claim()
             .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
+        let exit_code = claim
+            .as_value()
+            .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?
+            .exit_code;
+        ensure_clean_exit(exit_code, proof_info.stats.user_cycles)?;
+
+        Ok(Risc0ProofReceipt::new(
+            proof_info.receipt,
+            self.program_id(),
+        ))
+    }
+
+    /// Lifts and joins the segment receipts of a core receipt into a succinct receipt.
+    fn compress(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.upgrade(receipt, ProofType::Compressed, ProverOpts::succinct())
+    }
+
+    /// Compresses `receipt` if needed, then runs `identity_p254` and `shrink_wrap` on the succinct
+    /// receipt to obtain a Groth16 receipt.
+    fn wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.upgrade(receipt, ProofType::Groth16, ProverOpts::groth16())
+    }
+}
+
+impl Risc0Host {
+    /// Upgrades a receipt of this program to `proof_type`, the receipt kind selected by `opts`,
+    /// using the recursion prover only.
+    ///
+    /// Fake receipts stay fake: the prover runs in dev mode when it is handed one and returns it
+    /// as is, so the upgraded receipt is labeled with `proof_type` here.
+    fn upgrade(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+        proof_type: ProofType,
+        opts: ProverOpts,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        let receipt = Risc0ProofReceipt::try_from(receipt)?;
+        if receipt.program_id() != &self.program_id() {
+            return Err(ZkVmError::ProofGenerationError(
+                "receipt is for another program".to_string(),
+            ));
+        }
+
+        let dev_mode = matches!(receipt.as_ref().inner, InnerReceipt::Fake(_));
+        let upgraded = default_prover()
+            .compress(&opts.with_dev_mode(dev_mode), receipt.as_ref())
+            .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
+
+        let upgraded: ProofReceiptWithMetadata =
+            Risc0ProofReceipt::new(upgraded, self.program_id())
+                .try_into()
+                .map_err(ZkVmError::InvalidProofReceipt)?;
+        if !dev_mode {
+            return Ok(upgraded);
+        }
+        let metadata = upgraded.metadata();
+        let metadata = ProofMetadata::new(
+            *metadata.zkvm(),
+            metadata.program_id().clone(),
+            metadata.version(),
+            proof_type,
+        )
+        .with_dev_mode(true);
+        Ok(ProofReceiptWithMetadata::new(
+            upgraded.receipt().clone(),
+            metadata,
+        ))
+    }
+}
+
+/// Fails with [`ZkVmError::GuestAborted`] if the guest halted with a non-zero exit code after
+/// running `cycles` user cycles.
+fn ensure_clean_exit(exit_code: ExitCode, cycles: u64) -> ZkVmResult<()> {
+    if let ExitCode::Halted(exit_code) = exit_code
+        && exit_code != 0
+    {
+        return Err(ZkVmError::GuestAborted {
+            exit_code,
+            message: format!(
+                "guest halted after {cycles} cycles; see the guest's stderr for details"
+            ),
+            cycles: Some(cycles),
+            public_values: None,
+        });
+    }
+    Ok(())
+}
+
+/// Recognizes a guest panic in a RISC0 executor or prover error message.
+///
+/// RISC0 does not halt a panicking guest with an exit code; instead the executor fails with an
+/// error reading `Guest panicked: <message>`. Such errors are reported as
+/// [`ZkVmError::GuestAborted`] with exit code `1`, like a panicking SP1 guest.
+fn guest_panic(error: &str) -> Option<ZkVmError> {
+    let (_, message) = error.split_once("Guest panicked: ")?;
+    Some(ZkVmError::GuestAborted {
+        exit_code: 1,
+        message: message.to_string(),
+        cycles: None,
+        public_values: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm::{FakeReceipt, Receipt, ReceiptClaim, sha::Digest};
+    use zkaleido::{ZkVm, ZkVmTypedVerifier};
+
+    use super::*;
+
+    /// Returns a fake core receipt of the program with image id `image_id`, as proven in dev mode.
+    fn fake_receipt(image_id: Digest) -> ProofReceiptWithMetadata {
+        let journal = vec![1, 2, 3];
+        let claim = ReceiptClaim::ok(image_id, journal.clone());
+        let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal);
+        Risc0ProofReceipt::new(receipt, ProgramId(image_id.into()))
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn ensure_clean_exit_rejects_non_zero_exit_codes() {
+        ensure_clean_exit(ExitCode::Halted(0), 10).unwrap();
+        ensure_clean_exit(ExitCode::Paused(1), 10).unwrap();
+        match ensure_clean_exit(ExitCode::Halted(3), 10) {
+            Err(ZkVmError::GuestAborted {
+                exit_code, cycles, ..
+            }) => {
+                assert_eq!(exit_code, 3);
+                assert_eq!(cycles, Some(10));
+            }
+            other => panic!("expected GuestAborted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fake_receipts_upgrade_to_fake_receipts() {
+        let host = Risc0Host::without_elf(Digest::from([7u8; 32]));
+        let core = fake_receipt(host.image_id());
+        assert_eq!(core.metadata().proof_type(), ProofType::Core);
+
+        let compressed = host.compress(&core).unwrap();
+        assert_eq!(compressed.metadata().proof_type(), ProofType::Compressed);
+        assert!(compressed.metadata().is_dev_mode());
+        assert_eq!(compressed.receipt(), core.receipt());
+
+        for receipt in [&core, &compressed] {
+            let groth16 = host.wrap_groth16(receipt).unwrap();
+            assert_eq!(groth16.metadata().proof_type(), ProofType::Groth16);
+            assert!(groth16.metadata().is_dev_mode());
+            assert!(matches!(
+                host.verify(&groth16),
+                Err(ZkVmError::DevModeReceipt { zkvm: ZkVm::Risc0 })
+            ));
+        }
+    }
 
-        Ok(proof_info.receipt.into())
+    #[test]
+    fn receipts_of_other_programs_are_not_upgraded() {
+        let host = Risc0Host::without_elf(Digest::from([7u8; 32]));
+        let other = fake_receipt(Digest::from([8u8; 32]));
+        assert!(matches!(
+            host.compress(&other),
+            Err(ZkVmError::ProofGenerationError(_))
+        ));
+        assert!(matches!(
+            host.wrap_groth16(&other),
+            Err(ZkVmError::ProofGenerationError(_))
+        ));
     }
 }
@@ -1,21 +1,18 @@
 use risc0_zkvm::Journal;
 use serde::{Serialize, de::DeserializeOwned};
 use zkaleido::{
-    PublicValues, VerifyingKey, ZkVmError, ZkVmOutputExtractor, ZkVmResult, ZkVmTypedVerifier,
-    ZkVmVkProvider,
+    PublicValues, VerifyingKey, ZkVmError, ZkVmExecutor, ZkVmOutputExtractor, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVkProvider,
 };
+use zkaleido_risc0_verifier::{Risc0ProofReceipt, Risc0ReceiptVerifier};
 
-use crate::{Risc0Host, proof::Risc0ProofReceipt};
+use crate::Risc0Host;
 
 impl ZkVmTypedVerifier for Risc0Host {
     type ZkVmProofReceipt = Risc0ProofReceipt;
 
     fn verify_inner(&self, proof: &Risc0ProofReceipt) -> ZkVmResult<()> {
-        proof
-            .as_ref()
-            .verify(self.image_id())
-            .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))?;
-        Ok(())
+        Risc0ReceiptVerifier::from_image_id(self.program_id()).verify_inner(proof)
     }
 }
 
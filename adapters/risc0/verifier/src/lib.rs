@@ -0,0 +1,11 @@
+//! # zkaleido-risc0-verifier
+//!
+//! This crate verifies [RISC Zero](https://www.risczero.com/) receipts saved as
+//! [`zkaleido::ProofReceiptWithMetadata`]. It uses `risc0-zkvm` without its client and prover, so
+//! that nodes which only verify do not pull them in.
+
+mod proof;
+mod verifier;
+
+pub use proof::Risc0ProofReceipt;
+pub use verifier::Risc0ReceiptVerifier;
@@ -5,23 +5,28 @@ use zkaleido::{
 };
 
 #[derive(Debug, Clone)]
-pub struct Risc0ProofReceipt(Receipt);
+pub struct Risc0ProofReceipt {
+    inner: Receipt,
+    program_id: ProgramId,
+}
 
 impl Risc0ProofReceipt {
+    pub fn new(inner: Receipt, program_id: ProgramId) -> Self {
+        Self { inner, program_id }
+    }
+
     pub fn inner(self) -> Receipt {
-        self.0
+        self.inner
     }
-}
 
-impl From<Receipt> for Risc0ProofReceipt {
-    fn from(receipt: Receipt) -> Self {
-        Risc0ProofReceipt(receipt)
+    pub fn program_id(&self) -> &ProgramId {
+        &self.program_id
     }
 }
 
 impl AsRef<Receipt> for Risc0ProofReceipt {
     fn as_ref(&self) -> &Receipt {
-        &self.0
+        &self.inner
     }
 }
 
@@ -56,26 +61,43 @@ impl TryFrom<&ProofReceiptWithMetadata> for Risc0ProofReceipt {
         let journal = value.receipt().public_values().as_bytes().to_vec();
         let inner: InnerReceipt = bincode::deserialize(value.receipt().proof().as_bytes())
             .map_err(|e| ZkVmProofError::DataFormat(DataFormatError::Serde(e.to_string())))?;
-        Ok(Receipt::new(inner, journal).into())
+        // A fake receipt proves nothing, so it must be flagged as such.
+        if matches!(inner, InnerReceipt::Fake(_)) && !value.metadata().is_dev_mode() {
+            return Err(ZkVmProofError::DataFormat(DataFormatError::Other(
+                "fake receipt is not flagged as dev mode".to_string(),
+            )));
+        }
+        let program_id = value.metadata().program_id().clone();
+        Ok(Risc0ProofReceipt::new(
+            Receipt::new(inner, journal),
+            program_id,
+        ))
     }
 }
 
 impl TryFrom<Risc0ProofReceipt> for ProofReceiptWithMetadata {
     type Error = ZkVmProofError;
     fn try_from(value: Risc0ProofReceipt) -> Result<Self, Self::Error> {
-        let proof_type = match &value.0.inner {
+        let proof_type = match &value.inner.inner {
             InnerReceipt::Succinct(_) => ProofType::Compressed,
             InnerReceipt::Groth16(_) => ProofType::Groth16,
             _ => ProofType::Core,
         };
-        let proof_bytes = bincode::serialize(&value.0.inner)
+        // Fake receipts are produced in dev mode and must not pass for real core proofs.
+        let dev_mode = matches!(value.inner.inner, InnerReceipt::Fake(_));
+        let proof_bytes = bincode::serialize(&value.inner.inner)
             .map_err(|e| ZkVmProofError::DataFormat(DataFormatError::Serde(e.to_string())))?;
         let proof = Proof::new(proof_bytes);
-        let public_values = PublicValues::new(value.0.journal.bytes.to_vec());
+        let public_values = PublicValues::new(value.inner.journal.bytes.to_vec());
         let receipt = ProofReceipt::new(proof, public_values);
 
-        let program_id = ProgramId(value.0.metadata.verifier_parameters.into());
-        let metadata = ProofMetadata::new(ZkVm::Risc0, program_id, risc0_zkvm::VERSION, proof_type);
+        let metadata = ProofMetadata::new(
+            ZkVm::Risc0,
+            value.program_id,
+            risc0_zkvm::VERSION,
+            proof_type,
+        )
+        .with_dev_mode(dev_mode);
         Ok(ProofReceiptWithMetadata::new(receipt, metadata))
     }
 }
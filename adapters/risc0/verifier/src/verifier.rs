@@ -0,0 +1,117 @@
+use risc0_zkvm::sha::Digest;
+use zkaleido::{ProgramId, ZkVmError, ZkVmResult, ZkVmTypedVerifier};
+
+use crate::Risc0ProofReceipt;
+
+/// Verifies Risc0 receipts of a single program, identified by its image id.
+///
+/// Unlike `Risc0Host`, this needs neither the guest ELF nor a prover, which makes it suitable for
+/// nodes that only verify. Core, Compressed and Groth16 receipts are all accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct Risc0ReceiptVerifier {
+    image_id: Digest,
+}
+
+impl Risc0ReceiptVerifier {
+    /// Creates a verifier for the program whose image id is `image_id`, as returned by
+    /// [`ZkVmExecutor::program_id`](zkaleido::ZkVmExecutor::program_id) on a Risc0 host.
+    pub fn from_image_id(image_id: ProgramId) -> Self {
+        Self {
+            image_id: Digest::from(image_id.0),
+        }
+    }
+
+    /// Returns the image id of the program this verifier accepts receipts for.
+    pub fn image_id(&self) -> Digest {
+        self.image_id
+    }
+}
+
+impl ZkVmTypedVerifier for Risc0ReceiptVerifier {
+    type ZkVmProofReceipt = Risc0ProofReceipt;
+
+    fn verify_inner(&self, proof: &Risc0ProofReceipt) -> ZkVmResult<()> {
+        proof
+            .as_ref()
+            .verify(self.image_id)
+            .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use risc0_zkvm::{
+        FakeReceipt, Groth16Receipt, Groth16ReceiptVerifierParameters, InnerReceipt, Receipt,
+        ReceiptClaim, sha::Digestible,
+    };
+    use zkaleido::{ProofReceiptWithMetadata, ZkVm};
+
+    use super::*;
+
+    /// Returns a Groth16 receipt of the fibonacci guest with the given journal, built from the
+    /// seal of the fixture of `zkaleido-risc0-groth16-verifier`.
+    fn groth16_receipt(journal: Option<Vec<u8>>) -> ProofReceiptWithMetadata {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../groth16-verifier/proofs/fibonacci_Risc0_3.0.5.proof.bin");
+        let fixture = ProofReceiptWithMetadata::load(path).unwrap();
+        let program_id = fixture.metadata().program_id().clone();
+        let journal =
+            journal.unwrap_or_else(|| fixture.receipt().public_values().as_bytes().to_vec());
+
+        let inner = InnerReceipt::Groth16(Groth16Receipt::new(
+            fixture.receipt().proof().as_bytes().to_vec(),
+            ReceiptClaim::ok(Digest::from(program_id.0), journal.clone()).into(),
+            Groth16ReceiptVerifierParameters::default().digest(),
+        ));
+        Risc0ProofReceipt::new(Receipt::new(inner, journal), program_id)
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn verifies_groth16_receipts() {
+        let receipt = groth16_receipt(None);
+        let verifier = Risc0ReceiptVerifier::from_image_id(receipt.metadata().program_id().clone());
+        verifier.verify(&receipt).unwrap();
+
+        let mut journal = receipt.receipt().public_values().as_bytes().to_vec();
+        journal[0] ^= 1;
+        assert!(matches!(
+            verifier.verify(&groth16_receipt(Some(journal))),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_receipts_of_other_programs() {
+        let receipt = groth16_receipt(None);
+        let mut other = receipt.metadata().program_id().clone();
+        other.0[0] ^= 1;
+        assert!(matches!(
+            Risc0ReceiptVerifier::from_image_id(other).verify(&receipt),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_fake_receipts() {
+        let image_id = ProgramId([7; 32]);
+        let journal = vec![1, 2, 3];
+        let claim = ReceiptClaim::ok(Digest::from(image_id.0), journal.clone());
+        let receipt: ProofReceiptWithMetadata = Risc0ProofReceipt::new(
+            Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal),
+            image_id.clone(),
+        )
+        .try_into()
+        .unwrap();
+        assert!(receipt.metadata().is_dev_mode());
+
+        assert!(matches!(
+            Risc0ReceiptVerifier::from_image_id(image_id).verify(&receipt),
+            Err(ZkVmError::DevModeReceipt { zkvm: ZkVm::Risc0 })
+        ));
+    }
+}
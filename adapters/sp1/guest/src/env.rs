@@ -1,11 +1,10 @@
-use bincode::deserialize;
-use serde::{Serialize, de::DeserializeOwned};
+use serde::Serialize;
 #[cfg(feature = "zkvm-verify")]
 use sha2::{Digest, Sha256};
-use sp1_zkvm::io;
 #[cfg(feature = "zkvm-verify")]
 use sp1_zkvm::lib::verify::verify_sp1_proof;
-use zkaleido::{ZkVmEnv, ZkVmEnvSerde};
+use sp1_zkvm::{io, syscalls::syscall_halt};
+use zkaleido::{ZkVmEnv, ZkVmEnvError, ZkVmEnvResult, ZkVmEnvSerde};
 
 /// An environment adapter for the SP1 proof system implementing [`ZkVmEnv`].
 ///
@@ -26,6 +25,20 @@ impl ZkVmEnv for Sp1ZkVmEnv {
     }
 
     fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]) {
+        if let Err(error) = self.try_verify_native_proof(vk_digest, public_values) {
+            panic!("{error}")
+        }
+    }
+
+    /// Registers the proof with SP1, which checks it when the guest is proven, so a proof that
+    /// does not verify makes the whole execution unprovable rather than failing here.
+    ///
+    /// Fails if neither the `zkvm-verify` nor the `mock-verify` feature is enabled.
+    fn try_verify_native_proof(
+        &self,
+        vk_digest: &[u32; 8],
+        public_values: &[u8],
+    ) -> ZkVmEnvResult<()> {
         #[cfg(not(feature = "zkvm-verify"))]
         let _ = (vk_digest, public_values);
 
@@ -33,29 +46,44 @@ impl ZkVmEnv for Sp1ZkVmEnv {
             if #[cfg(feature = "zkvm-verify")] {
                 let pv_digest = Sha256::digest(public_values);
                 verify_sp1_proof(vk_digest, &pv_digest.into());
-            } else if #[cfg(feature = "mock-verify")] {}
-            else {
-                panic!(
+                Ok(())
+            } else if #[cfg(feature = "mock-verify")] {
+                Ok(())
+            } else {
+                Err(ZkVmEnvError::ProofVerification(
                     "No verification feature enabled. \
                      Please enable either `zkvm-verify` or `mock-verify`."
-                );
+                        .to_string(),
+                ))
             }
         }
     }
-}
 
-/// Overrides the default bincode-based implementations with SP1-specific I/O.
-impl ZkVmEnvSerde for Sp1ZkVmEnv {
-    fn read_serde<T: DeserializeOwned>(&self) -> T {
-        io::read()
+    /// Emits SP1's `cycle-tracker-report-start` marker, so the cycles of the region are
+    /// reported by the executor.
+    fn cycle_tracker_start(&self, label: &str) {
+        println!("cycle-tracker-report-start: {label}");
     }
 
-    fn commit_serde<T: Serialize>(&self, output: &T) {
-        io::commit(&output);
+    /// Emits SP1's `cycle-tracker-report-end` marker.
+    fn cycle_tracker_end(&self, label: &str) {
+        println!("cycle-tracker-report-end: {label}");
     }
 
-    fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T {
-        let buf = self.read_verified_buf(vk_digest);
-        deserialize(&buf).expect("bincode deserialization failed")
+    /// Writes the message to stderr and halts the guest with the exit code.
+    fn abort(&self, exit_code: u8, message: &str) -> ! {
+        eprintln!("guest aborted with exit code {exit_code}: {message}");
+        syscall_halt(exit_code.max(1))
+    }
+}
+
+/// Commits through SP1-specific I/O.
+///
+/// Reads use the default implementations, which decode the buffers returned by
+/// [`io::read_vec`] with `bincode` exactly like [`io::read`], but abort the guest with a
+/// structured exit code instead of panicking when decoding fails.
+impl ZkVmEnvSerde for Sp1ZkVmEnv {
+    fn commit_serde<T: Serialize>(&self, output: &T) {
+        io::commit(&output);
     }
 }
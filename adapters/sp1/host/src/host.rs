@@ -5,21 +5,29 @@ use sp1_sdk::{
     env::{EnvProver, EnvProvingKey},
     network::{FulfillmentStrategy, NetworkMode},
 };
-use zkaleido::{ZkVm, ZkVmHost};
+use zkaleido::{EM_RISCV, ElfClass, ElfTarget, ZkVm, ZkVmError, ZkVmHost, ZkVmResult};
 
 use crate::SP1HostConfig;
 
+/// The guest ELFs SP1 runs, built for `riscv64im-succinct-zkvm-elf`.
+const SP1_ELF: ElfTarget = ElfTarget {
+    name: "riscv64im-succinct-zkvm-elf",
+    class: ElfClass::Elf64,
+    machine: EM_RISCV,
+    required_sections: &[".text"],
+};
+
 /// Host for the SP1 zkVM. Bundles a proving key (which embeds the guest ELF
 /// and verifying key), a long-lived prover client and a configuration.  Implements [`ZkVmHost`],
 /// [`zkaleido::ZkVmExecutor`], [`zkaleido::ZkVmProver`], and [`zkaleido::ZkVmRemoteProver`] across
 /// the sibling modules in this crate.
 #[derive(Clone)]
 pub struct SP1Host {
-    /// Proving key set up from the guest ELF in [`SP1Host::init_with_config`].
+    /// Proving key set up from the guest ELF in [`SP1Host::try_init_with_config`].
     /// Carries both the ELF (re-exposed via `elf()`) and the verifying key
     /// used for the program id and on-chain/local verification.
     pub(crate) proving_key: EnvProvingKey,
-    /// Prover client built once in [`SP1Host::init_with_config`] from
+    /// Prover client built once in [`SP1Host::try_init_with_config`] from
     /// `SP1_PROVER` (and [`SP1HostConfig::proof_strategy`] for the network
     /// mode). Reused across execute, prove, verify, and remote prove.
     pub(crate) client: EnvProver,
@@ -36,17 +44,39 @@ impl SP1Host {
     }
 
     /// Initializes a new [`SP1Host`] with an explicit [`SP1HostConfig`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ELF is invalid, see [`SP1Host::try_init_with_config`].
     pub async fn init_with_config(elf: &[u8], config: SP1HostConfig) -> Self {
+        Self::try_init_with_config(elf, config)
+            .await
+            .expect("failed to setup sp1 proving key")
+    }
+
+    /// Initializes a new [`SP1Host`] with [`SP1HostConfig::default`], checking the ELF first.
+    pub async fn try_init(elf: &[u8]) -> ZkVmResult<Self> {
+        Self::try_init_with_config(elf, SP1HostConfig::default()).await
+    }
+
+    /// Initializes a new [`SP1Host`] with an explicit [`SP1HostConfig`], checking the ELF first.
+    ///
+    /// The ELF must be a `riscv64im` executable with a `.text` section whose entry point lies in
+    /// an executable segment. Returns [`ZkVmError::InvalidELF`] otherwise, and [`ZkVmError::Other`]
+    /// if SP1 then fails to set up the proving key.
+    pub async fn try_init_with_config(elf: &[u8], config: SP1HostConfig) -> ZkVmResult<Self> {
+        SP1_ELF.validate(elf)?;
+
         let client = build_env_prover(&config).await;
         let proving_key = client
             .setup(elf.into())
             .await
-            .expect("failed to setup sp1 proving key");
-        Self {
+            .map_err(|e| ZkVmError::Other(format!("failed to setup proving key: {e}")))?;
+        Ok(Self {
             proving_key,
             client,
             config,
-        }
+        })
     }
 }
 
@@ -84,3 +114,26 @@ impl fmt::Debug for SP1Host {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::block_on_async;
+
+    #[test]
+    fn rejects_elfs_that_are_not_sp1_guests() {
+        // The header of a 32-bit ELF, as built for RISC0. It is rejected before any prover client
+        // is built.
+        let mut elf32 = b"\x7fELF\x01\x01\x01".to_vec();
+        elf32.resize(52, 0);
+        match block_on_async(SP1Host::try_init(&elf32)) {
+            Err(ZkVmError::InvalidELF(reason)) => {
+                assert!(
+                    reason.contains("expected Elf64, got Elf32"),
+                    "got: {reason}"
+                )
+            }
+            other => panic!("expected InvalidELF, got {other:?}"),
+        }
+    }
+}
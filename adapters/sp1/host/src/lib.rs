@@ -5,6 +5,13 @@
 //! It enables both the generation of SP1 proofs and the verification of SP1-based Groth16
 //! proofs within a zkVM context.
 //!
+//! ## Limitations
+//!
+//! The SP1 SDK only proves from a proving key and its stdin: its recursion and wrapping provers
+//! are not exposed, so [`SP1Host`] cannot upgrade an existing receipt and keeps the failing
+//! defaults of [`zkaleido::ZkVmProver::compress`] and [`zkaleido::ZkVmProver::wrap_groth16`].
+//! Prove again with the wanted [`zkaleido::ProofType`] instead.
+//!
 //! ## Configuration
 //!
 //! [`SP1HostConfig::from_env`] (also used by [`SP1HostConfig::default`] and
@@ -24,9 +31,12 @@
 //!   `report.exit_code` (panicked). Set to `false` to opt back into the SDK's permissive behavior
 //!   (e.g. testing a panic path end-to-end). Unparsable values default to `true`.
 //!
-//! Upstream SP1 envs such as `SP1_PROVER` (prover backend) and `ZKVM_MOCK`
-//! (mock mode) are read by the SP1 SDK itself and continue to apply; see
-//! the SP1 docs for their semantics.
+//! Upstream SP1 envs such as `SP1_PROVER` (prover backend), `ZKVM_MOCK`
+//! (mock mode) and `TRACE_FILE` (profiling) are read by the SP1 SDK itself
+//! and continue to apply; see the SP1 docs for their semantics. Because the
+//! SDK only reads them from the process environment, the host ignores the
+//! dev mode setting of [`zkaleido::RunConfig`] and fails to execute or prove
+//! when its profiling directory is set: use `TRACE_FILE` to profile instead.
 
 mod config;
 mod host;
@@ -38,3 +48,4 @@ mod verifier;
 
 pub use config::SP1HostConfig;
 pub use host::SP1Host;
+pub use zkaleido_sp1_verifier::SP1ReceiptVerifier;
@@ -1,13 +1,14 @@
 use num_bigint::BigUint;
 use sha2::{Digest, Sha256};
 use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
-use sp1_verifier::{GROTH16_VK_BYTES, Groth16Bn254Proof};
+use sp1_verifier::{GROTH16_VK_BYTES, Groth16Bn254Proof, PLONK_VK_BYTES, PlonkBn254Proof};
 use zkaleido::{
     DataFormatError, Mismatched, ProgramId, Proof, ProofMetadata, ProofReceipt,
     ProofReceiptWithMetadata, ProofType, PublicValues, ZkVm, ZkVmProofError,
 };
 
-/// Layout of the bytes stored in `ProofReceiptWithMetadata::receipt().proof()` for Groth16:
+/// Layout of the bytes stored in `ProofReceiptWithMetadata::receipt().proof()` for Groth16 and
+/// Plonk:
 ///   `[vkey_hash_tag(4) || exit_code(32) || vk_root(32) || proof_nonce(32) || gnark_proof_bytes]`
 const VKEY_HASH_TAG_LEN: usize = 4;
 const FR_BYTES: usize = 32;
@@ -20,11 +21,23 @@ const GNARK_PROOF_OFFSET: usize = PROOF_NONCE_OFFSET + FR_BYTES;
 pub struct SP1ProofReceipt {
     inner: SP1ProofWithPublicValues,
     program_id: ProgramId,
+    dev_mode: bool,
 }
 
 impl SP1ProofReceipt {
     pub fn new(inner: SP1ProofWithPublicValues, program_id: ProgramId) -> Self {
-        Self { inner, program_id }
+        Self {
+            inner,
+            program_id,
+            dev_mode: false,
+        }
+    }
+
+    /// Flags the proof as generated by the mock prover.
+    #[must_use]
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+        self
     }
 
     pub fn into_inner(self) -> SP1ProofWithPublicValues {
@@ -85,6 +98,11 @@ impl TryFrom<&ProofReceiptWithMetadata> for SP1ProofReceipt {
                 public_values_bytes,
                 &program_id,
             )?),
+            ProofType::Plonk => SP1Proof::Plonk(reconstruct_plonk_bn254_proof(
+                proof_bytes,
+                public_values_bytes,
+                &program_id,
+            )?),
         };
         let proof_receipt = SP1ProofWithPublicValues {
             proof,
@@ -92,7 +110,8 @@ impl TryFrom<&ProofReceiptWithMetadata> for SP1ProofReceipt {
             sp1_version,
             tee_proof: None,
         };
-        Ok(SP1ProofReceipt::new(proof_receipt, program_id))
+        Ok(SP1ProofReceipt::new(proof_receipt, program_id)
+            .with_dev_mode(value.metadata().is_dev_mode()))
     }
 }
 
@@ -101,18 +120,14 @@ impl TryFrom<SP1ProofReceipt> for ProofReceiptWithMetadata {
     fn try_from(value: SP1ProofReceipt) -> Result<Self, Self::Error> {
         let sp1_receipt = value.as_ref();
 
-        // For Groth16, store the on-chain bytes (selector + proof) so consumers can verify
-        // directly without reconstructing the SP1 proof type. For other variants, bincode
+        // For Groth16 and Plonk, store the on-chain bytes (selector + proof) so consumers can
+        // verify directly without reconstructing the SP1 proof type. For other variants, bincode
         // round-trips losslessly through `SP1Proof`.
         let (proof_bytes, proof_type) = match &sp1_receipt.proof {
             SP1Proof::Groth16(_) => (sp1_receipt.bytes(), ProofType::Groth16),
+            SP1Proof::Plonk(_) => (sp1_receipt.bytes(), ProofType::Plonk),
             SP1Proof::Core(_) => (sp1_proof_bincode(sp1_receipt)?, ProofType::Core),
             SP1Proof::Compressed(_) => (sp1_proof_bincode(sp1_receipt)?, ProofType::Compressed),
-            SP1Proof::Plonk(_) => {
-                return Err(ZkVmProofError::DataFormat(DataFormatError::Other(
-                    "SP1Proof::Plonk is not supported by zkaleido".into(),
-                )));
-            }
         };
 
         let proof = Proof::new(proof_bytes);
@@ -125,7 +140,8 @@ impl TryFrom<SP1ProofReceipt> for ProofReceiptWithMetadata {
             value.program_id().clone(),
             sp1_version,
             proof_type,
-        );
+        )
+        .with_dev_mode(value.dev_mode);
 
         Ok(ProofReceiptWithMetadata::new(receipt, metadata))
     }
@@ -155,17 +171,62 @@ fn sp1_proof_bincode(sp1_receipt: &SP1ProofWithPublicValues) -> Result<Vec<u8>,
 /// is left at its default — it is not consumed by any verification path we use.
 ///
 /// Verification of saved Groth16 receipts is expected to go through
-/// [`zkaleido_sp1_groth16_verifier::SP1Groth16Verifier::verify`], which derives every public
-/// input from the proof bytes plus `program_id` and `public_values` directly — see that function
-/// for the full verification flow and the exact structure it expects in the proof bytes.
+/// [`SP1ReceiptVerifier`](crate::SP1ReceiptVerifier), whose `SP1Groth16Verifier::verify` derives
+/// every public input from the proof bytes plus `program_id` and `public_values` directly — see
+/// that function for the full verification flow and the exact structure it expects in the proof
+/// bytes.
 fn reconstruct_groth16_bn254_proof(
     proof_bytes: &[u8],
     public_values_bytes: &[u8],
     program_id: &ProgramId,
 ) -> Result<Groth16Bn254Proof, ZkVmProofError> {
+    let public_inputs =
+        reconstruct_public_inputs("Groth16", proof_bytes, public_values_bytes, program_id)?;
+
+    Ok(Groth16Bn254Proof {
+        public_inputs,
+        encoded_proof: hex::encode(&proof_bytes[VKEY_HASH_TAG_LEN..]),
+        raw_proof: String::new(),
+        groth16_vkey_hash: Sha256::digest(*GROTH16_VK_BYTES).into(),
+    })
+}
+
+/// Rebuild an SP1 [`PlonkBn254Proof`] from the bytes stored in
+/// `ProofReceiptWithMetadata::receipt().proof()`.
+///
+/// The stored bytes and the reconstructed `public_inputs` follow the same layout as for Groth16,
+/// see [`reconstruct_groth16_bn254_proof`]; only the gnark proof after `proof_nonce` differs.
+/// `plonk_vkey_hash` is populated from [`PLONK_VK_BYTES`].
+///
+/// Verification of saved Plonk receipts is expected to go through
+/// [`SP1ReceiptVerifier`](crate::SP1ReceiptVerifier) as well.
+fn reconstruct_plonk_bn254_proof(
+    proof_bytes: &[u8],
+    public_values_bytes: &[u8],
+    program_id: &ProgramId,
+) -> Result<PlonkBn254Proof, ZkVmProofError> {
+    let public_inputs =
+        reconstruct_public_inputs("Plonk", proof_bytes, public_values_bytes, program_id)?;
+
+    Ok(PlonkBn254Proof {
+        public_inputs,
+        encoded_proof: hex::encode(&proof_bytes[VKEY_HASH_TAG_LEN..]),
+        raw_proof: String::new(),
+        plonk_vkey_hash: Sha256::digest(*PLONK_VK_BYTES).into(),
+    })
+}
+
+/// Reconstructs the five decimal-string public inputs of a gnark proof stored with the layout
+/// described on [`VKEY_HASH_TAG_LEN`].
+fn reconstruct_public_inputs(
+    kind: &str,
+    proof_bytes: &[u8],
+    public_values_bytes: &[u8],
+    program_id: &ProgramId,
+) -> Result<[String; 5], ZkVmProofError> {
     if proof_bytes.len() < GNARK_PROOF_OFFSET {
         return Err(ZkVmProofError::DataFormat(DataFormatError::Other(format!(
-            "Groth16 proof bytes too short: got {}, need at least {GNARK_PROOF_OFFSET}",
+            "{kind} proof bytes too short: got {}, need at least {GNARK_PROOF_OFFSET}",
             proof_bytes.len(),
         ))));
     }
@@ -176,26 +237,20 @@ fn reconstruct_groth16_bn254_proof(
 
     let pv_hash = SP1PublicValues::from(public_values_bytes).hash_bn254();
 
-    let public_inputs = [
+    Ok([
         BigUint::from_bytes_be(&program_id.0).to_string(),
         pv_hash.to_string(),
         BigUint::from_bytes_be(exit_code).to_string(),
         BigUint::from_bytes_be(vk_root).to_string(),
         BigUint::from_bytes_be(proof_nonce).to_string(),
-    ];
-
-    Ok(Groth16Bn254Proof {
-        public_inputs,
-        encoded_proof: hex::encode(&proof_bytes[VKEY_HASH_TAG_LEN..]),
-        raw_proof: String::new(),
-        groth16_vkey_hash: Sha256::digest(*GROTH16_VK_BYTES).into(),
-    })
+    ])
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs, path::PathBuf};
 
+    use sp1_verifier::VK_ROOT_BYTES;
     use zkaleido::ZkVmTypedVerifier;
 
     use super::*;
@@ -224,6 +279,66 @@ mod tests {
         assert_eq!(round_tripped.metadata(), original.metadata());
     }
 
+    /// No Plonk receipt of the current circuit version is checked in, so the receipt is
+    /// assembled by hand with the stored layout: the tag of [`PLONK_VK_BYTES`], the three SP1
+    /// public inputs and opaque gnark proof bytes.
+    #[test]
+    fn plonk_round_trips_through_sp1_proof_receipt() {
+        let proof_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../groth16-verifier/proofs/fibonacci_SP1_v6.1.0.proof.bin");
+        let groth16 = ProofReceiptWithMetadata::load(&proof_path).unwrap();
+
+        let mut proof_nonce = [0u8; FR_BYTES];
+        proof_nonce[FR_BYTES - 1] = 7;
+        let proof_bytes = [
+            &Sha256::digest(*PLONK_VK_BYTES)[..VKEY_HASH_TAG_LEN],
+            &[0u8; FR_BYTES][..],
+            &VK_ROOT_BYTES[..],
+            &proof_nonce[..],
+            &[0xab; 96][..],
+        ]
+        .concat();
+        let original = ProofReceiptWithMetadata::new(
+            ProofReceipt::new(
+                Proof::new(proof_bytes),
+                groth16.receipt().public_values().clone(),
+            ),
+            ProofMetadata::new(
+                ZkVm::SP1,
+                groth16.metadata().program_id().clone(),
+                sp1_sdk::SP1_CIRCUIT_VERSION,
+                ProofType::Plonk,
+            ),
+        );
+
+        let sp1_receipt: SP1ProofReceipt = (&original).try_into().unwrap();
+        let SP1Proof::Plonk(plonk) = &sp1_receipt.inner().proof else {
+            panic!("expected a Plonk proof");
+        };
+        assert_eq!(
+            plonk.public_inputs[0],
+            BigUint::from_bytes_be(&original.metadata().program_id().0).to_string()
+        );
+        assert_eq!(plonk.public_inputs[2], "0");
+        assert_eq!(
+            plonk.public_inputs[3],
+            BigUint::from_bytes_be(&VK_ROOT_BYTES[..]).to_string()
+        );
+        assert_eq!(plonk.public_inputs[4], "7");
+
+        let round_tripped: ProofReceiptWithMetadata = sp1_receipt.try_into().unwrap();
+
+        assert_eq!(
+            round_tripped.receipt().proof().as_bytes(),
+            original.receipt().proof().as_bytes()
+        );
+        assert_eq!(
+            round_tripped.receipt().public_values().as_bytes(),
+            original.receipt().public_values().as_bytes()
+        );
+        assert_eq!(round_tripped.metadata(), original.metadata());
+    }
+
     #[test]
     #[ignore = "requires the fibonacci guest ELF to be prebuilt under artifacts/sp1/fibonacci/target/; not produced by the standard test pipeline"]
     fn groth16_reconstructed_proof_verifies() {
@@ -1,7 +1,4 @@
-use std::{
-    env::set_var,
-    future::{Future, IntoFuture},
-};
+use std::future::{Future, IntoFuture};
 
 use sp1_core_executor::ExecutionReport;
 use sp1_sdk::{HashableKey, ProveRequest, Prover, ProvingKey, SP1ProofMode, env::EnvProver};
@@ -11,7 +8,7 @@ use tokio::{
     time::sleep,
 };
 use zkaleido::{
-    ExecutionSummary, ProgramId, ProofType, PublicValues, RemoteProofStatus, ZkVmError,
+    ExecutionSummary, ProgramId, ProofType, PublicValues, RemoteProofStatus, RunConfig, ZkVmError,
     ZkVmExecutor, ZkVmInputBuilder, ZkVmProver, ZkVmRemoteProver, ZkVmResult,
 };
 
@@ -19,10 +16,15 @@ use crate::{SP1Host, input::SP1ProofInputBuilder, proof::SP1ProofReceipt};
 
 impl ZkVmExecutor for SP1Host {
     type Input<'a> = SP1ProofInputBuilder;
+
+    /// Fails if `config` enables profiling: the SP1 executor only reads its trace destination from
+    /// the `TRACE_FILE` environment variable.
     fn execute<'a>(
         &self,
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
     ) -> ZkVmResult<ExecutionSummary> {
+        reject_profiling(config)?;
         let elf = self.proving_key.elf().clone();
         let (output, report) = block_on_async(self.client.execute(elf, prover_input).into_future())
             .map_err(|e| ZkVmError::ExecutionError(e.to_string()))?;
@@ -44,16 +46,6 @@ impl ZkVmExecutor for SP1Host {
         self.proving_key.elf()
     }
 
-    fn save_trace(&self, trace_name: &str) {
-        let profiling_file_name = format!("{}_{:?}.trace_profile", trace_name, self);
-        // SAFETY: SP1 consumes this process-global trace setting from the
-        // environment. Callers must configure tracing before concurrent prover
-        // work starts.
-        unsafe {
-            set_var("TRACE_FILE", profiling_file_name);
-        }
-    }
-
     fn program_id(&self) -> ProgramId {
         ProgramId(self.proving_key.verifying_key().bytes32_raw())
     }
@@ -61,11 +53,17 @@ impl ZkVmExecutor for SP1Host {
 
 impl ZkVmProver for SP1Host {
     type ZkVmProofReceipt = SP1ProofReceipt;
+
+    /// SP1 selects mock proving when the prover client is built, through `SP1_PROVER=mock`, so
+    /// the dev mode setting of `config` is ignored. Receipts of the mock prover are flagged as
+    /// dev mode. Fails if `config` enables profiling, like [`execute`](ZkVmExecutor::execute).
     fn prove_inner<'a>(
         &self,
         prover_input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
+        config: &RunConfig,
     ) -> ZkVmResult<SP1ProofReceipt> {
+        reject_profiling(config)?;
         if matches!(self.client, EnvProver::Network(_)) {
             return block_on_async(self.prove_via_network(prover_input, proof_type));
         }
@@ -74,7 +72,7 @@ impl ZkVmProver for SP1Host {
         // whose public values carry exit_code=1 (verifiable as panicked
         // by a downstream verifier). Fail fast with the same honest
         // ExecutionError the network path produces.
-        <Self as ZkVmExecutor>::execute(self, prover_input.clone())?;
+        <Self as ZkVmExecutor>::execute(self, prover_input.clone(), config)?;
 
         let mode = to_sp1_mode(proof_type);
         let proof_info = block_on_async(async {
@@ -85,7 +83,10 @@ impl ZkVmProver for SP1Host {
         })
         .map_err(|e| ZkVmError::ProofGenerationError(e.to_string()))?;
 
-        Ok(SP1ProofReceipt::new(proof_info, self.program_id()))
+        // Read from the client rather than `SP1_PROVER`, which may have changed since the client
+        // was built.
+        let mock = matches!(self.client, EnvProver::Mock(_));
+        Ok(SP1ProofReceipt::new(proof_info, self.program_id()).with_dev_mode(mock))
     }
 }
 
@@ -126,6 +127,21 @@ pub(crate) fn to_sp1_mode(proof_type: ProofType) -> SP1ProofMode {
         ProofType::Compressed => SP1ProofMode::Compressed,
         ProofType::Core => SP1ProofMode::Core,
         ProofType::Groth16 => SP1ProofMode::Groth16,
+        ProofType::Plonk => SP1ProofMode::Plonk,
+    }
+}
+
+/// Fails if `config` enables profiling.
+///
+/// The SP1 executor only reads its trace destination from the `TRACE_FILE` environment variable,
+/// so a trace cannot be written to the directory of `config`. Set `TRACE_FILE` instead.
+fn reject_profiling(config: &RunConfig) -> ZkVmResult<()> {
+    match &config.profiling_output_dir {
+        Some(dir) => Err(ZkVmError::Other(format!(
+            "SP1 cannot write traces to {}: set TRACE_FILE instead of a profiling directory",
+            dir.display()
+        ))),
+        None => Ok(()),
     }
 }
 
@@ -155,27 +171,31 @@ where
 }
 
 /// Converts an [`ExecutionReport`] with a non-zero `exit_code` into
-/// [`ZkVmError::ExecutionError`].
+/// [`ZkVmError::GuestAborted`].
 ///
 /// The SP1 executor returns `Ok((pv, report))` even when the guest halted
-/// with a non-zero exit code (panic). Without this check, a panicking
-/// guest looks like a successful simulation — and the SDK's network
-/// simulation path makes the same mistake, which is how a request the
-/// guest will panic on can reach the network. SP1's
+/// with a non-zero exit code (panic or [`abort`](zkaleido::ZkVmEnv::abort)).
+/// Without this check, a failing guest looks like a successful simulation —
+/// and the SDK's network simulation path makes the same mistake, which is how
+/// a request the guest will panic on can reach the network. SP1's
 /// `SP1Context::expected_exit_code` does not help: that field is only
 /// consulted by the verifier, never by the executor.
 ///
-/// The cycle count is included in the message so operators can correlate
-/// against the `panicked at ...` line the guest's panic handler prints
-/// to stderr — the panic string itself isn't carried in
-/// [`ExecutionReport`] (no panic-message field exists in SP1 6.2).
+/// The cycle count is included so operators can correlate against the
+/// `panicked at ...` or `guest aborted ...` line the guest prints to stderr —
+/// the message itself isn't carried in [`ExecutionReport`] (no panic-message
+/// field exists in SP1 6.2).
 pub(crate) fn ensure_clean_exit(report: &ExecutionReport) -> ZkVmResult<()> {
     if report.exit_code != 0 {
-        return Err(ZkVmError::ExecutionError(format!(
-            "guest exited with non-zero exit code {} after {} instructions",
-            report.exit_code,
-            report.total_instruction_count(),
-        )));
+        let cycles = report.total_instruction_count();
+        return Err(ZkVmError::GuestAborted {
+            exit_code: u32::try_from(report.exit_code).unwrap_or(u32::MAX),
+            message: format!(
+                "guest halted after {cycles} instructions; see the guest's stderr for details"
+            ),
+            cycles: Some(cycles),
+            public_values: None,
+        });
     }
     Ok(())
 }
@@ -184,17 +204,34 @@ pub(crate) fn ensure_clean_exit(report: &ExecutionReport) -> ZkVmResult<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn profiling_directories_are_rejected() {
+        reject_profiling(&RunConfig::new()).unwrap();
+        let config = RunConfig::new().with_profiling_output_dir("traces");
+        assert!(matches!(
+            reject_profiling(&config),
+            Err(ZkVmError::Other(message)) if message.contains("TRACE_FILE")
+        ));
+    }
+
     #[test]
     fn ensure_clean_exit_rejects_non_zero_exit_code() {
         let mut report = ExecutionReport::default();
         report.exit_code = 1;
         let err = ensure_clean_exit(&report).expect_err("non-zero exit must error");
         match err {
-            ZkVmError::ExecutionError(msg) => {
-                assert!(msg.contains("exit code 1"), "got: {msg}");
-                assert!(msg.contains("instructions"), "got: {msg}");
+            ZkVmError::GuestAborted {
+                exit_code,
+                message,
+                cycles,
+                public_values,
+            } => {
+                assert_eq!(exit_code, 1);
+                assert!(message.contains("instructions"), "got: {message}");
+                assert_eq!(cycles, Some(report.total_instruction_count()));
+                assert_eq!(public_values, None);
             }
-            other => panic!("expected ExecutionError, got {other:?}"),
+            other => panic!("expected GuestAborted, got {other:?}"),
         }
     }
 
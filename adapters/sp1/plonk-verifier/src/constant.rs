@@ -0,0 +1,86 @@
+use std::mem::size_of;
+
+/// Number of bytes used from the SHA-256 hash of the Plonk verifying key.
+/// SP1 prepends these bytes to each raw Plonk proof to ensure the proof
+/// was generated with the expected verifying key.
+pub(crate) const VK_HASH_PREFIX_LENGTH: usize = 4;
+
+/// SP1 program exit code committed to a successful execution (32 zero bytes).
+pub(crate) const SUCCESS_EXIT_CODE: [u8; 32] = [0u8; 32];
+
+/// Number of public inputs of SP1's Plonk circuit:
+/// `(program_vk_hash, hash(public_values), exit_code, vk_root, proof_nonce)`.
+pub(crate) const SP1_NUM_PUBLIC_INPUTS: usize = 5;
+
+/// Domain separator gnark uses to hash BSB22 commitments into the field.
+pub(crate) const BSB22_DST: &[u8] = b"BSB22-Plonk";
+
+/// Mask to clear out the two most significant bits when reconstructing an Fq (G1) or
+/// Fq2 (G2) element from a compressed representation.
+///
+/// These two bits encode the `CompressedPointFlag` for both G1 and G2 points (positive,
+/// negative, or infinity).
+/// https://github.com/Consensys/gnark-crypto/blob/a7d721497f2a98b1f292886bb685fd3c5a90f930/ecc/bn254/marshal.go#L32-L42
+pub(crate) const MASK: u8 = 0b11 << 6;
+
+/// Flag indicating the “positive” y‐coordinate branch of a compressed G1 or G2 point.
+pub(crate) const COMPRESSED_POSITIVE: u8 = 0b10 << 6;
+
+/// Flag indicating the “negative” y‐coordinate branch of a compressed G1 or G2 point.
+pub(crate) const COMPRESSED_NEGATIVE: u8 = 0b11 << 6;
+
+/// Size of a u32 in bytes
+pub(crate) const U32_SIZE: usize = size_of::<u32>();
+
+/// Size of a u64 in bytes
+pub(crate) const U64_SIZE: usize = size_of::<u64>();
+
+/// Size of a serialized Fq or Fr field element in bytes
+pub(crate) const FIELD_SIZE: usize = 32;
+
+/// Size of a GNARK-compressed G1 point in bytes (x-coordinate with flag bits)
+pub(crate) const G1_COMPRESSED_SIZE: usize = FIELD_SIZE;
+
+/// Size of an uncompressed G1 point in bytes (x + y coordinates)
+pub(crate) const G1_UNCOMPRESSED_SIZE: usize = FIELD_SIZE * 2;
+
+/// Size of a GNARK-compressed G2 point in bytes (Fq2 x-coordinate with flag bits)
+pub(crate) const G2_COMPRESSED_SIZE: usize = FIELD_SIZE * 2;
+
+// GNARK Plonk verifying key offsets
+/// Offset of the first permutation commitment `S[0]`, after `size` (8), `size_inv` (32),
+/// `generator` (32), `nb_public_variables` (8) and `coset_shift` (32)
+pub(crate) const GNARK_VK_S_OFFSET: usize = U64_SIZE + FIELD_SIZE * 2 + U64_SIZE + FIELD_SIZE;
+
+/// Offset of the selector commitments `Ql, Qr, Qm, Qo, Qk`, after the three `S` commitments
+pub(crate) const GNARK_VK_SELECTORS_OFFSET: usize = GNARK_VK_S_OFFSET + 3 * G1_COMPRESSED_SIZE;
+
+/// Offset of `num_qcp`, the number of custom-gate selector commitments
+pub(crate) const GNARK_VK_NUM_QCP_OFFSET: usize =
+    GNARK_VK_SELECTORS_OFFSET + 5 * G1_COMPRESSED_SIZE;
+
+/// Size of the fixed GNARK Plonk VK header, which is also where the `Qcp` commitments start:
+/// 372 bytes
+pub(crate) const GNARK_VK_HEADER_SIZE: usize = GNARK_VK_NUM_QCP_OFFSET + U32_SIZE;
+
+/// Size of the KZG part of the verifying key that is read: `G1` followed by `G2[0]` and `G2[1]`.
+/// GNARK follows it with precomputed pairing lines, which are not read.
+pub(crate) const GNARK_VK_KZG_SIZE: usize = G1_COMPRESSED_SIZE + 2 * G2_COMPRESSED_SIZE;
+
+// Plonk proof size constants
+/// Size of a Solidity-encoded Plonk proof without BSB22 commitments: `L, R, O, H0, H1, H2`
+/// (6 * 64), the five claimed values at ζ (5 * 32), `Z` (64), `Z(ωζ)` (32) and the two opening
+/// quotients (2 * 64): 768 bytes
+pub(crate) const PLONK_PROOF_BASE_SIZE: usize = 6 * G1_UNCOMPRESSED_SIZE
+    + 5 * FIELD_SIZE
+    + G1_UNCOMPRESSED_SIZE
+    + FIELD_SIZE
+    + 2 * G1_UNCOMPRESSED_SIZE;
+
+/// Size added to a Plonk proof by each BSB22 commitment: its claimed value at ζ (32) and the
+/// commitment itself (64)
+pub(crate) const PLONK_PROOF_COMMITMENT_SIZE: usize = FIELD_SIZE + G1_UNCOMPRESSED_SIZE;
+
+/// Size of the SP1 metadata preceding the raw Plonk proof: `exit_code`, `vk_root` and
+/// `proof_nonce`, 32 bytes each
+pub(crate) const SP1_METADATA_SIZE: usize = 3 * FIELD_SIZE;
@@ -0,0 +1,217 @@
+//! Byte encodings of BN254 field elements and curve points, as written by GNARK.
+
+use std::{cmp::Ordering, iter};
+
+use bn::{AffineG1, AffineG2, FieldError, Fq, Fq2, Fr, G1, G2, Group, arith::U256};
+
+use crate::{
+    constant::{
+        COMPRESSED_NEGATIVE, COMPRESSED_POSITIVE, FIELD_SIZE, G1_COMPRESSED_SIZE,
+        G1_UNCOMPRESSED_SIZE, G2_COMPRESSED_SIZE, MASK,
+    },
+    error::{BufferLengthError, InvalidDataFormatError, InvalidPointError, SerializationError},
+};
+
+/// Parses a canonical big-endian `Fr` element, rejecting values not below the modulus.
+pub(crate) fn fr_from_bytes(bytes: &[u8]) -> Result<Fr, SerializationError> {
+    // `Fr::from_slice` silently reduces its input, so go through `Fr::new`, which does not.
+    let value = U256::from_slice(bytes).map_err(|_| FieldError::InvalidSliceLength)?;
+    Fr::new(value).ok_or_else(|| FieldError::NotMember.into())
+}
+
+/// Serializes an `Fr` element as 32 big-endian bytes.
+pub(crate) fn fr_to_bytes(fr: Fr) -> [u8; FIELD_SIZE] {
+    let mut bytes = [0u8; FIELD_SIZE];
+    // `Fr::to_big_endian` writes the Montgomery form, so convert to an integer first.
+    // NOTE: It is safe to unwrap because the only error is if size of slice is not of length
+    // FIELD_SIZE.
+    fr.into_u256().to_big_endian(&mut bytes).unwrap();
+    bytes
+}
+
+/// Interprets big-endian `bytes` of any length as an integer and reduces it modulo the order of
+/// `Fr`, like GNARK's `fr.Element.SetBytes`.
+pub(crate) fn fr_from_bytes_reduced(bytes: &[u8]) -> Fr {
+    // 2^128 and every 16-byte chunk are below the modulus, so Horner's rule over 16-byte chunks
+    // only ever parses canonical elements.
+    let mut shift_bytes = [0u8; FIELD_SIZE];
+    shift_bytes[FIELD_SIZE / 2 - 1] = 1;
+    let shift = Fr::from_slice(&shift_bytes).expect("2^128 is below the modulus");
+
+    let head = bytes.len() % (FIELD_SIZE / 2);
+    let chunks = iter::once(&bytes[..head]).chain(bytes[head..].chunks(FIELD_SIZE / 2));
+    chunks.fold(Fr::zero(), |acc, chunk| {
+        let mut padded = [0u8; FIELD_SIZE];
+        padded[FIELD_SIZE - chunk.len()..].copy_from_slice(chunk);
+        acc * shift + Fr::from_slice(&padded).expect("a 128-bit value is below the modulus")
+    })
+}
+
+/// Deserializes a G1 point from GNARK-compressed bytes (32 bytes: x-coordinate with flag bits).
+///
+/// The first two bits of the first byte encode a flag:
+/// - `COMPRESSED_POSITIVE`: use the lexicographically smaller of (y, -y) as y.
+/// - `COMPRESSED_NEGATIVE`: use the lexicographically larger of (y, -y) as y.
+///
+/// Any other flag, including the point at infinity, is rejected.
+pub(crate) fn g1_from_compressed_bytes(bytes: &[u8]) -> Result<AffineG1, SerializationError> {
+    if bytes.len() != G1_COMPRESSED_SIZE {
+        return Err(BufferLengthError {
+            context: "Gnark-compressed G1 point",
+            expected: G1_COMPRESSED_SIZE,
+            actual: bytes.len(),
+        }
+        .into());
+    }
+
+    let flag = bytes[0] & MASK;
+    let mut x_bytes = [0u8; FIELD_SIZE];
+    x_bytes.copy_from_slice(bytes);
+    x_bytes[0] &= !MASK;
+    let x = Fq::from_slice(&x_bytes)?;
+
+    // Recover both possible y-coordinates from x: y^2 = x^3 + b for G1.
+    let y = ((x * x * x) + G1::b()).sqrt().ok_or(InvalidPointError)?;
+    let neg_y = -y;
+    let (smaller_y, larger_y) = if y.into_u256() < neg_y.into_u256() {
+        (y, neg_y)
+    } else {
+        (neg_y, y)
+    };
+
+    let y = match flag {
+        COMPRESSED_NEGATIVE => larger_y,
+        COMPRESSED_POSITIVE => smaller_y,
+        _ => return Err(InvalidDataFormatError.into()),
+    };
+    Ok(AffineG1::new(x, y)?)
+}
+
+/// Deserializes a G2 point from GNARK-compressed bytes (64 bytes: the imaginary then the real
+/// part of the x-coordinate, with flag bits), using the same flags as
+/// [`g1_from_compressed_bytes`].
+pub(crate) fn g2_from_compressed_bytes(bytes: &[u8]) -> Result<AffineG2, SerializationError> {
+    if bytes.len() != G2_COMPRESSED_SIZE {
+        return Err(BufferLengthError {
+            context: "Gnark-compressed G2 point",
+            expected: G2_COMPRESSED_SIZE,
+            actual: bytes.len(),
+        }
+        .into());
+    }
+
+    let flag = bytes[0] & MASK;
+    let mut x1_bytes = [0u8; FIELD_SIZE];
+    x1_bytes.copy_from_slice(&bytes[..FIELD_SIZE]);
+    x1_bytes[0] &= !MASK;
+    let x1 = Fq::from_slice(&x1_bytes)?;
+    let x0 = Fq::from_slice(&bytes[FIELD_SIZE..])?;
+    let x = Fq2::new(x0, x1);
+
+    // Recover both possible y-coordinates from x: y^2 = x^3 + b for G2.
+    let y = ((x * x * x) + G2::b()).sqrt().ok_or(InvalidPointError)?;
+    let neg_y = -y;
+
+    // Lexicographic ordering compares the imaginary parts, then the real parts.
+    let y_is_smaller = match y
+        .imaginary()
+        .into_u256()
+        .cmp(&neg_y.imaginary().into_u256())
+    {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => y.real().into_u256() < neg_y.real().into_u256(),
+    };
+    let (smaller_y, larger_y) = if y_is_smaller { (y, neg_y) } else { (neg_y, y) };
+
+    let y = match flag {
+        COMPRESSED_NEGATIVE => larger_y,
+        COMPRESSED_POSITIVE => smaller_y,
+        _ => return Err(InvalidDataFormatError.into()),
+    };
+    Ok(AffineG2::new(x, y)?)
+}
+
+/// Deserializes a G1 point from uncompressed bytes (64 bytes: big-endian x then y).
+///
+/// All-zero bytes decode to the point at infinity, as in the Solidity encoding of GNARK proofs.
+pub(crate) fn g1_from_uncompressed_bytes(bytes: &[u8]) -> Result<G1, SerializationError> {
+    if bytes.len() != G1_UNCOMPRESSED_SIZE {
+        return Err(BufferLengthError {
+            context: "Uncompressed G1 point",
+            expected: G1_UNCOMPRESSED_SIZE,
+            actual: bytes.len(),
+        }
+        .into());
+    }
+    if bytes.iter().all(|&b| b == 0) {
+        return Ok(G1::zero());
+    }
+
+    let (x_bytes, y_bytes) = bytes.split_at(FIELD_SIZE);
+    let x = Fq::from_slice(x_bytes)?;
+    let y = Fq::from_slice(y_bytes)?;
+    Ok(AffineG1::new(x, y)?.into())
+}
+
+/// Serializes a G1 point to uncompressed bytes (64 bytes: big-endian x then y), the encoding
+/// GNARK binds into its Fiat-Shamir transcripts. The point at infinity serializes to zeros.
+pub(crate) fn g1_to_uncompressed_bytes(point: G1) -> [u8; G1_UNCOMPRESSED_SIZE] {
+    let mut bytes = [0u8; G1_UNCOMPRESSED_SIZE];
+    if let Some(affine) = AffineG1::from_jacobian(point) {
+        // NOTE: It is safe to unwrap because the only error is if size of slice is not of length
+        // FIELD_SIZE.
+        affine.x().to_big_endian(&mut bytes[..FIELD_SIZE]).unwrap();
+        affine.y().to_big_endian(&mut bytes[FIELD_SIZE..]).unwrap();
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use bn::{Fr, G1, Group};
+
+    use super::{
+        fr_from_bytes, fr_from_bytes_reduced, fr_to_bytes, g1_from_uncompressed_bytes,
+        g1_to_uncompressed_bytes,
+    };
+
+    /// Order of the BN254 scalar field.
+    const FR_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00,
+        0x00, 0x01,
+    ];
+
+    #[test]
+    fn reduces_bytes_modulo_the_scalar_field() {
+        assert!(fr_from_bytes(&FR_MODULUS).is_err());
+        assert!(fr_from_bytes_reduced(&FR_MODULUS) == Fr::zero());
+
+        let mut modulus_plus_five = FR_MODULUS;
+        modulus_plus_five[31] += 5;
+        let five = fr_from_bytes_reduced(&[5]);
+        assert!(fr_from_bytes_reduced(&modulus_plus_five) == five);
+
+        // Leading zeros and lengths that are not a multiple of the chunk size do not matter.
+        let mut padded = [0u8; 47];
+        padded[15..].copy_from_slice(&modulus_plus_five);
+        assert!(fr_from_bytes_reduced(&padded) == five);
+        assert!(fr_from_bytes(&fr_to_bytes(five)).unwrap() == five);
+
+        let mut five_bytes = [0u8; 32];
+        five_bytes[31] = 5;
+        assert_eq!(fr_to_bytes(five), five_bytes);
+    }
+
+    #[test]
+    fn uncompressed_g1_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let point = G1::random(&mut rng);
+        let bytes = g1_to_uncompressed_bytes(point);
+        assert!(g1_from_uncompressed_bytes(&bytes).unwrap() == point);
+
+        assert_eq!(g1_to_uncompressed_bytes(G1::zero()), [0u8; 64]);
+        assert!(g1_from_uncompressed_bytes(&[0u8; 64]).unwrap().is_zero());
+    }
+}
@@ -0,0 +1,153 @@
+use bn::{CurveError, FieldError, GroupError};
+use thiserror::Error;
+
+use crate::constant::VK_HASH_PREFIX_LENGTH;
+
+/// Error for buffer length mismatches during deserialization.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid buffer length for {context}: expected {expected} bytes, got {actual} bytes")]
+pub struct BufferLengthError {
+    pub context: &'static str,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+/// Error for invalid data format during deserialization.
+///
+/// This occurs when:
+/// - Invalid flag bits in compressed point encoding
+/// - The commitment constraint indexes of a verifying key do not match its commitments
+/// - Other data format violations
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid data format")]
+pub struct InvalidDataFormatError;
+
+/// Error for unsupported or invalid proof format.
+///
+/// Raised when an SP1 Plonk proof's byte length matches neither the tagged nor the untagged wire
+/// format for the number of commitments of the loaded verifying key.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid SP1 Plonk proof length: expected {expected} bytes, got {actual} bytes")]
+pub struct InvalidProofFormatError {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+/// Error for Plonk public input count mismatches.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid public input count: expected {expected}, got {actual}")]
+pub struct PublicInputCountError {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+/// Error for invalid elliptic curve points.
+///
+/// This occurs when:
+/// - Point does not lie on the curve
+/// - Square root computation fails during decompression
+/// - Point is the point at infinity where an affine point is required
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid elliptic curve point")]
+pub struct InvalidPointError;
+
+/// Unified serialization and deserialization error type.
+#[derive(Error, Debug)]
+pub enum SerializationError {
+    /// Buffer length does not match expected size.
+    #[error(transparent)]
+    BufferLength(#[from] BufferLengthError),
+
+    /// Data format is invalid or malformed.
+    #[error(transparent)]
+    InvalidFormat(#[from] InvalidDataFormatError),
+
+    /// Proof format is invalid or unsupported.
+    #[error(transparent)]
+    InvalidProofFormat(#[from] InvalidProofFormatError),
+
+    /// Elliptic curve point is invalid.
+    #[error(transparent)]
+    InvalidPoint(#[from] InvalidPointError),
+
+    /// BN254 field element error.
+    #[error("BN254 field error")]
+    Field(FieldError),
+
+    /// BN254 group element error.
+    #[error("BN254 group error")]
+    Group(GroupError),
+
+    /// BN254 curve error.
+    #[error("BN254 curve error")]
+    Curve(CurveError),
+}
+
+// Manual From implementations for BN254 errors (they don't implement std::error::Error)
+impl From<FieldError> for SerializationError {
+    fn from(err: FieldError) -> Self {
+        SerializationError::Field(err)
+    }
+}
+
+impl From<GroupError> for SerializationError {
+    fn from(err: GroupError) -> Self {
+        SerializationError::Group(err)
+    }
+}
+
+impl From<CurveError> for SerializationError {
+    fn from(err: CurveError) -> Self {
+        SerializationError::Curve(err)
+    }
+}
+
+/// Errors specific to Plonk proof verification.
+#[derive(Debug, Error)]
+pub enum Sp1PlonkError {
+    /// Proof verification failed.
+    ///
+    /// This occurs when the KZG pairing check fails, indicating that the proof is invalid or does
+    /// not correspond to the provided public inputs and verifying key.
+    #[error("Proof verification failed")]
+    VerificationFailed,
+
+    /// Verifying key hash mismatch.
+    ///
+    /// This occurs when the hash of the verifying key embedded in the proof does not match the
+    /// hash of the provided verifying key, indicating that the proof was generated with a
+    /// different verifying key.
+    #[error("Verifying key hash mismatch: expected {expected:02x?}, got {actual:02x?}")]
+    VkeyHashMismatch {
+        expected: [u8; VK_HASH_PREFIX_LENGTH],
+        actual: [u8; VK_HASH_PREFIX_LENGTH],
+    },
+
+    /// SP1 recursion verifying key root mismatch.
+    ///
+    /// This occurs when the proof is tied to a recursion verifying key set that does not match
+    /// the current SP1 verifier.
+    #[error("SP1 verifying key root mismatch: expected {expected:02x?}, got {actual:02x?}")]
+    VkeyRootMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+
+    /// Exit code mismatch.
+    ///
+    /// SP1 Plonk proofs expose the program exit code as a public input. A verifier loaded with
+    /// `require_success` expects successful execution, encoded as 32 zero bytes.
+    #[error("SP1 exit code mismatch: expected {expected:02x?}, got {actual:02x?}")]
+    ExitCodeMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+
+    /// Public input count mismatch.
+    #[error(transparent)]
+    PublicInputCount(#[from] PublicInputCountError),
+
+    /// Serialization or deserialization error.
+    #[error(transparent)]
+    Serialization(#[from] SerializationError),
+}
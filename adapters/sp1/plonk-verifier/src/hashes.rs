@@ -0,0 +1,121 @@
+//! Hash functions for SP1 Plonk proof verification.
+//!
+//! This module provides the hash functions SP1 uses to commit to the public values of a program
+//! in the public inputs of its Plonk circuit, and the `hash_to_field` GNARK uses to bind BSB22
+//! commitments into the public inputs polynomial.
+
+use bn::Fr;
+use sha2::{Digest, Sha256};
+
+use crate::{encoding::fr_from_bytes_reduced, error::SerializationError};
+
+/// Output size of SHA-256 in bytes.
+const SHA256_OUTPUT_SIZE: usize = 32;
+
+/// Input block size of SHA-256 in bytes.
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// Number of uniform bytes hashed into one `Fr` element: 16 bytes more than the element itself,
+/// so the reduction modulo the field order is statistically unbiased.
+const HASH_TO_FIELD_SIZE: usize = 48;
+
+/// Hashes the input using SHA-256.
+fn sha256(inputs: &[u8]) -> [u8; 32] {
+    Sha256::digest(inputs).into()
+}
+
+/// Hashes the input using Blake3.
+fn blake3(inputs: &[u8]) -> [u8; 32] {
+    *blake3::hash(inputs).as_bytes()
+}
+
+/// Hashes public inputs and converts to an Fr element for Plonk verifiers.
+///
+/// The top 3 bits of the digest are zeroed (masked with 0x1F) to ensure the 256-bit hash fits
+/// within the 254-bit BN254 scalar field, matching the behavior in SP1's Ethereum verifier
+/// contract.
+fn hash_public_inputs<F>(public_inputs: &[u8], hasher: F) -> Result<Fr, SerializationError>
+where
+    F: Fn(&[u8]) -> [u8; 32],
+{
+    let mut result = hasher(public_inputs);
+    result[0] &= 0x1F;
+    Fr::from_slice(&result).map_err(Into::into)
+}
+
+/// Hashes public inputs using SHA-256 and converts the result to an Fr element.
+pub fn sha256_to_fr(public_inputs: &[u8]) -> Result<Fr, SerializationError> {
+    hash_public_inputs(public_inputs, sha256)
+}
+
+/// Hashes public inputs using Blake3 and converts the result to an Fr element.
+pub fn blake3_to_fr(public_inputs: &[u8]) -> Result<Fr, SerializationError> {
+    hash_public_inputs(public_inputs, blake3)
+}
+
+/// Hashes `msg` into an `Fr` element under the domain separator `dst`, like GNARK's
+/// `hash_to_field` for BN254: `expand_message_xmd` with SHA-256 (RFC 9380, section 5.3.1) to 48
+/// bytes, reduced modulo the field order.
+pub(crate) fn hash_to_fr(msg: &[u8], dst: &[u8]) -> Fr {
+    fr_from_bytes_reduced(&expand_message_xmd(msg, dst, HASH_TO_FIELD_SIZE))
+}
+
+/// `expand_message_xmd` with SHA-256, as specified in RFC 9380, section 5.3.1.
+///
+/// `dst` must be at most 255 bytes and `len` at most `255 * 32`, which holds for every caller.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let ell = len.div_ceil(SHA256_OUTPUT_SIZE);
+    let dst_len = [u8::try_from(dst.len()).expect("domain separator fits in 255 bytes")];
+    let len_bytes = u16::try_from(len)
+        .expect("output length fits in two bytes")
+        .to_be_bytes();
+
+    let b0: [u8; SHA256_OUTPUT_SIZE] = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK_SIZE])
+        .chain_update(msg)
+        .chain_update(len_bytes)
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize()
+        .into();
+
+    let mut uniform = Vec::with_capacity(ell * SHA256_OUTPUT_SIZE);
+    let mut bi = [0u8; SHA256_OUTPUT_SIZE];
+    for i in 1..=ell {
+        // b_1 hashes b_0 itself, every later block hashes b_0 xor the previous block.
+        let mut input = b0;
+        if i > 1 {
+            input.iter_mut().zip(bi).for_each(|(a, b)| *a ^= b);
+        }
+        bi = Sha256::new()
+            .chain_update(input)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize()
+            .into();
+        uniform.extend_from_slice(&bi);
+    }
+    uniform.truncate(len);
+    uniform
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_message_xmd;
+
+    /// Test vectors of RFC 9380, appendix K.1.
+    #[test]
+    fn expand_message_xmd_matches_rfc_vectors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use bn::{Fr, G1, G2, Group, Gt, pairing_batch};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    encoding::{fr_from_bytes_reduced, fr_to_bytes, g1_to_uncompressed_bytes},
+    error::Sp1PlonkError,
+    transcript::Transcript,
+    vk::PlonkVerifyingKey,
+};
+
+/// Domain separator of the challenge combining the openings of a batch verification.
+const BATCH_VERIFY_DST: &[u8] = b"zkaleido-plonk-kzg-batch";
+
+/// A KZG opening of a committed polynomial at some point.
+#[derive(Clone, Copy)]
+pub(crate) struct OpeningProof {
+    /// Commitment to the quotient polynomial.
+    pub(crate) h: G1,
+    /// Claimed value of the polynomial at the point.
+    pub(crate) claimed_value: Fr,
+}
+
+/// Folds openings of several polynomials at the same `point` into a single opening, like GNARK's
+/// `kzg.FoldProof`.
+///
+/// The digests and claimed values are combined with powers of a challenge `γ`, derived from
+/// `point`, the digests, the claimed values and `data`. Returns the folded digest and opening.
+pub(crate) fn fold_proof(
+    digests: &[G1],
+    claimed_values: &[Fr],
+    h: G1,
+    point: Fr,
+    data: &[u8],
+) -> (G1, OpeningProof) {
+    debug_assert_eq!(digests.len(), claimed_values.len());
+
+    let mut bindings = fr_to_bytes(point).to_vec();
+    for digest in digests {
+        bindings.extend_from_slice(&g1_to_uncompressed_bytes(*digest));
+    }
+    for value in claimed_values {
+        bindings.extend_from_slice(&fr_to_bytes(*value));
+    }
+    bindings.extend_from_slice(data);
+    let gamma = Transcript::default().challenge("gamma", &bindings);
+
+    let mut gamma_i = Fr::one();
+    let mut folded_digest = G1::zero();
+    let mut folded_value = Fr::zero();
+    for (digest, value) in digests.iter().zip(claimed_values) {
+        folded_digest = folded_digest + *digest * gamma_i;
+        folded_value = folded_value + *value * gamma_i;
+        gamma_i = gamma_i * gamma;
+    }
+
+    (
+        folded_digest,
+        OpeningProof {
+            h,
+            claimed_value: folded_value,
+        },
+    )
+}
+
+/// Verifies the openings of `digests` at `points` with a single pairing check, like GNARK's
+/// `kzg.BatchVerifyMultiPoints`.
+///
+/// GNARK combines the openings with random coefficients `λᵢ`. Here `λ₀ = 1` and the other
+/// coefficients are hashed from every input of the check, which binds them to the openings just
+/// as well and keeps verification deterministic inside guests. The check is
+/// `e(∑ᵢλᵢ(Dᵢ - vᵢ[1] + pᵢHᵢ), [1]) · e(-∑ᵢλᵢHᵢ, [τ]) = 1`.
+pub(crate) fn batch_verify_multi_points(
+    digests: &[G1],
+    proofs: &[OpeningProof],
+    points: &[Fr],
+    vk: &PlonkVerifyingKey,
+) -> Result<(), Sp1PlonkError> {
+    debug_assert!(digests.len() == proofs.len() && digests.len() == points.len());
+
+    let mut hasher = Sha256::new();
+    hasher.update(BATCH_VERIFY_DST);
+    for ((digest, proof), point) in digests.iter().zip(proofs).zip(points) {
+        hasher.update(g1_to_uncompressed_bytes(*digest));
+        hasher.update(g1_to_uncompressed_bytes(proof.h));
+        hasher.update(fr_to_bytes(proof.claimed_value));
+        hasher.update(fr_to_bytes(*point));
+    }
+    let seed: [u8; 32] = hasher.finalize().into();
+
+    let mut folded_digests = G1::zero();
+    let mut folded_quotients = G1::zero();
+    let mut folded_values = Fr::zero();
+    for (i, ((digest, proof), point)) in digests.iter().zip(proofs).zip(points).enumerate() {
+        let lambda = if i == 0 {
+            Fr::one()
+        } else {
+            let challenge = Sha256::new()
+                .chain_update(seed)
+                .chain_update((i as u64).to_be_bytes())
+                .finalize();
+            fr_from_bytes_reduced(&challenge)
+        };
+        folded_digests = folded_digests + (*digest + proof.h * *point) * lambda;
+        folded_quotients = folded_quotients + proof.h * lambda;
+        folded_values = folded_values + proof.claimed_value * lambda;
+    }
+    folded_digests = folded_digests - G1::from(vk.kzg_g1) * folded_values;
+
+    if pairing_batch(&[
+        (folded_digests, G2::from(vk.kzg_g2[0])),
+        (-folded_quotients, G2::from(vk.kzg_g2[1])),
+    ]) == Gt::one()
+    {
+        Ok(())
+    } else {
+        Err(Sp1PlonkError::VerificationFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bn::{AffineG1, AffineG2, Fr, G1, G2, Group};
+    use sp1_verifier::PLONK_VK_BYTES;
+
+    use super::{OpeningProof, batch_verify_multi_points, fold_proof};
+    use crate::{error::Sp1PlonkError, vk::PlonkVerifyingKey};
+
+    /// SP1's verifying key with a KZG setup whose secret `τ` is known.
+    fn setup(tau: Fr) -> PlonkVerifyingKey {
+        let mut vk = PlonkVerifyingKey::from_gnark_bytes(&PLONK_VK_BYTES).unwrap();
+        vk.kzg_g1 = AffineG1::from_jacobian(G1::one()).unwrap();
+        vk.kzg_g2 = [
+            AffineG2::from_jacobian(G2::one()).unwrap(),
+            AffineG2::from_jacobian(G2::one() * tau).unwrap(),
+        ];
+        vk
+    }
+
+    /// Commits to a polynomial `f` with `f(τ) = at_tau` and opens it at `point` to `value`.
+    ///
+    /// The quotient `(f(X) - f(p)) / (X - p)` evaluates to `(f(τ) - f(p)) / (τ - p)` at `τ`.
+    fn open(tau: Fr, at_tau: Fr, point: Fr, value: Fr) -> (G1, OpeningProof) {
+        let h = (at_tau - value) * (tau - point).inverse().unwrap();
+        (
+            G1::one() * at_tau,
+            OpeningProof {
+                h: G1::one() * h,
+                claimed_value: value,
+            },
+        )
+    }
+
+    #[test]
+    fn batch_verifies_openings_at_several_points() {
+        let mut rng = rand::thread_rng();
+        let tau = Fr::random(&mut rng);
+        let vk = setup(tau);
+        let points = [Fr::random(&mut rng), Fr::random(&mut rng)];
+        let (digests, proofs): (Vec<_>, Vec<_>) = points
+            .iter()
+            .map(|&point| open(tau, Fr::random(&mut rng), point, Fr::random(&mut rng)))
+            .unzip();
+        batch_verify_multi_points(&digests, &proofs, &points, &vk).unwrap();
+
+        let mut wrong_value = proofs.clone();
+        wrong_value[1].claimed_value = wrong_value[1].claimed_value + Fr::one();
+        assert!(matches!(
+            batch_verify_multi_points(&digests, &wrong_value, &points, &vk),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+
+        // Shift the quotients of both openings so that their errors cancel out when the openings
+        // are summed. Only coefficients that differ between the openings catch this.
+        let error = Fr::random(&mut rng);
+        let mut cancelling = proofs.clone();
+        cancelling[0].h = cancelling[0].h + G1::one() * error;
+        cancelling[1].h = cancelling[1].h
+            - G1::one() * (error * (tau - points[0]) * (tau - points[1]).inverse().unwrap());
+        assert!(matches!(
+            batch_verify_multi_points(&digests, &cancelling, &points, &vk),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn folds_openings_at_the_same_point() {
+        let mut rng = rand::thread_rng();
+        let tau = Fr::random(&mut rng);
+        let vk = setup(tau);
+        let point = Fr::random(&mut rng);
+
+        // One opening of a random polynomial and two of constant polynomials, whose quotients
+        // are zero, so the quotient of the folded opening is the quotient of the first one.
+        let (digest, proof) = open(tau, Fr::random(&mut rng), point, Fr::random(&mut rng));
+        let constants = [Fr::random(&mut rng), Fr::random(&mut rng)];
+        let digests: Vec<_> = [digest]
+            .into_iter()
+            .chain(constants.iter().map(|&c| G1::one() * c))
+            .collect();
+        let claimed_values: Vec<_> = [proof.claimed_value].into_iter().chain(constants).collect();
+
+        let (folded_digest, folded_proof) =
+            fold_proof(&digests, &claimed_values, proof.h, point, b"data");
+        batch_verify_multi_points(&[folded_digest], &[folded_proof], &[point], &vk).unwrap();
+
+        let mut wrong_values = claimed_values.clone();
+        wrong_values[2] = wrong_values[2] + Fr::one();
+        let (folded_digest, folded_proof) =
+            fold_proof(&digests, &wrong_values, proof.h, point, b"data");
+        assert!(matches!(
+            batch_verify_multi_points(&[folded_digest], &[folded_proof], &[point], &vk),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+    }
+}
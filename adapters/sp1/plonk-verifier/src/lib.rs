@@ -0,0 +1,49 @@
+//! # zkaleido-sp1-plonk-verifier
+//!
+//! SP1 Plonk proof verification for zkaleido.
+//!
+//! Unlike Groth16, Plonk needs no circuit-specific trusted setup: SP1's Plonk circuit only relies
+//! on a universal KZG setup. This crate verifies its proofs in pure Rust, so it runs inside
+//! guests as well as on the host.
+//!
+//! [`SP1PlonkVerifier`] is the crate's entry point. Its operational model is
+//! **load once, verify many**: [`SP1PlonkVerifier::load`] parses the GNARK-serialized verifying
+//! key and pins the constants the verifier is willing to accept, so each subsequent
+//! [`SP1PlonkVerifier::verify`] call only handles the statement-specific public inputs.
+//!
+//! # Public surface
+//!
+//! - [`SP1PlonkVerifier`] — the verifier itself, with `load` / `verify`.
+//! - [`Sp1PlonkError`] — error type returned by its inherent methods.
+//! - [`hashes`] — the hashes SP1 uses to commit to the public values of a program.
+//!
+//! Everything else (the Plonk and KZG verification routines, the Fiat-Shamir transcript, size
+//! constants) is an implementation detail and not part of the stable surface.
+//!
+//! # Wire format
+//!
+//! [`SP1PlonkVerifier::verify`] accepts the bytes of `SP1ProofWithPublicValues::bytes` for a
+//! Plonk proof, `vk_hash_tag(4) || exit_code(32) || vk_root(32) || proof_nonce(32) ||
+//! plonk_proof`, with or without the leading tag. These are also the proof bytes of a zkaleido
+//! receipt of type `ProofType::Plonk`. The public inputs of SP1's circuit are
+//! `(program_vk_hash, hash(public_values), exit_code, vk_root, proof_nonce)`.
+//!
+//! # Differences with GNARK
+//!
+//! The verification follows GNARK's `plonk.Verify` for BN254, except that the coefficients
+//! combining the final KZG openings are hashed from the openings instead of sampled at random,
+//! which keeps verification deterministic inside guests.
+
+mod constant;
+mod encoding;
+mod error;
+pub mod hashes;
+mod kzg;
+mod proof;
+mod transcript;
+mod verification;
+mod verifier;
+mod vk;
+
+pub use error::Sp1PlonkError;
+pub use verifier::SP1PlonkVerifier;
@@ -0,0 +1,115 @@
+use bn::{Fr, G1};
+
+use crate::{
+    constant::{
+        FIELD_SIZE, G1_UNCOMPRESSED_SIZE, PLONK_PROOF_BASE_SIZE, PLONK_PROOF_COMMITMENT_SIZE,
+    },
+    encoding::{fr_from_bytes, g1_from_uncompressed_bytes},
+    error::{InvalidProofFormatError, SerializationError},
+};
+
+/// A Plonk proof over BN254, as produced by GNARK.
+#[derive(Clone)]
+pub(crate) struct PlonkProof {
+    /// Commitments to the left, right and output wire polynomials.
+    pub(crate) lro: [G1; 3],
+    /// Commitments to the three parts of the quotient polynomial.
+    pub(crate) h: [G1; 3],
+    /// Claimed values at ζ of `L`, `R`, `O`, `S1` and `S2`, in that order.
+    pub(crate) claimed_values: [Fr; 5],
+    /// Commitment to the grand product polynomial `Z`.
+    pub(crate) z: G1,
+    /// Claimed value of `Z` at `ωζ`.
+    pub(crate) z_shifted_value: Fr,
+    /// Quotient of the batched opening at ζ.
+    pub(crate) batched_h: G1,
+    /// Quotient of the opening of `Z` at `ωζ`.
+    pub(crate) z_shifted_h: G1,
+    /// Claimed values at ζ of the BSB22 commitment selectors `Qcp`.
+    pub(crate) qcp_values: Vec<Fr>,
+    /// BSB22 commitments to the committed wires.
+    pub(crate) bsb22_commitments: Vec<G1>,
+}
+
+impl PlonkProof {
+    /// Size of the encoding of a proof with `num_commitments` BSB22 commitments.
+    pub(crate) fn encoded_len(num_commitments: usize) -> usize {
+        PLONK_PROOF_BASE_SIZE + num_commitments * PLONK_PROOF_COMMITMENT_SIZE
+    }
+
+    /// Deserialize from GNARK's `MarshalSolidity` encoding, for a circuit with `num_commitments`
+    /// BSB22 commitments.
+    ///
+    /// Layout, with G1 points uncompressed and all values big-endian:
+    /// `L, R, O, H0, H1, H2 (G1) || l, r, o, s1, s2 at ζ (Fr) || Z (G1) || Z(ωζ) (Fr) ||
+    /// batched opening quotient (G1) || Z(ωζ) opening quotient (G1) || Qcp values at ζ (Fr) ||
+    /// BSB22 commitments (G1)`.
+    pub(crate) fn from_gnark_bytes(
+        bytes: &[u8],
+        num_commitments: usize,
+    ) -> Result<Self, SerializationError> {
+        let expected = Self::encoded_len(num_commitments);
+        if bytes.len() != expected {
+            return Err(InvalidProofFormatError {
+                expected,
+                actual: bytes.len(),
+            }
+            .into());
+        }
+
+        let mut reader = Reader { bytes, offset: 0 };
+        let lro = [reader.g1()?, reader.g1()?, reader.g1()?];
+        let h = [reader.g1()?, reader.g1()?, reader.g1()?];
+        let claimed_values = [
+            reader.fr()?,
+            reader.fr()?,
+            reader.fr()?,
+            reader.fr()?,
+            reader.fr()?,
+        ];
+        let z = reader.g1()?;
+        let z_shifted_value = reader.fr()?;
+        let batched_h = reader.g1()?;
+        let z_shifted_h = reader.g1()?;
+        let qcp_values = (0..num_commitments)
+            .map(|_| reader.fr())
+            .collect::<Result<_, _>>()?;
+        let bsb22_commitments = (0..num_commitments)
+            .map(|_| reader.g1())
+            .collect::<Result<_, _>>()?;
+
+        Ok(PlonkProof {
+            lro,
+            h,
+            claimed_values,
+            z,
+            z_shifted_value,
+            batched_h,
+            z_shifted_h,
+            qcp_values,
+            bsb22_commitments,
+        })
+    }
+}
+
+/// Reads consecutive values from a buffer whose length was checked upfront.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> &[u8] {
+        let slice = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        slice
+    }
+
+    fn fr(&mut self) -> Result<Fr, SerializationError> {
+        fr_from_bytes(self.take(FIELD_SIZE))
+    }
+
+    fn g1(&mut self) -> Result<G1, SerializationError> {
+        g1_from_uncompressed_bytes(self.take(G1_UNCOMPRESSED_SIZE))
+    }
+}
@@ -0,0 +1,29 @@
+use bn::Fr;
+use sha2::{Digest, Sha256};
+
+use crate::encoding::fr_from_bytes_reduced;
+
+/// A SHA-256 Fiat-Shamir transcript, derived like GNARK's `fiatshamir.Transcript`.
+///
+/// Each challenge hashes its name, then the previous challenge unless it is the first one, then
+/// the values bound to it. Challenges are derived in the order they are requested.
+#[derive(Debug, Default)]
+pub(crate) struct Transcript {
+    previous: Option<[u8; 32]>,
+}
+
+impl Transcript {
+    /// Derives the challenge `id` from `bindings`, the concatenation of the values bound to it,
+    /// and reduces it into an `Fr` element.
+    pub(crate) fn challenge(&mut self, id: &str, bindings: &[u8]) -> Fr {
+        let mut hasher = Sha256::new();
+        hasher.update(id.as_bytes());
+        if let Some(previous) = &self.previous {
+            hasher.update(previous);
+        }
+        hasher.update(bindings);
+        let challenge: [u8; 32] = hasher.finalize().into();
+        self.previous = Some(challenge);
+        fr_from_bytes_reduced(&challenge)
+    }
+}
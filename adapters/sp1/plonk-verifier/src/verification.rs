@@ -0,0 +1,302 @@
+use bn::{Fr, G1, Group};
+
+use crate::{
+    constant::BSB22_DST,
+    encoding::{fr_to_bytes, g1_to_uncompressed_bytes},
+    error::{PublicInputCountError, Sp1PlonkError},
+    hashes::hash_to_fr,
+    kzg::{OpeningProof, batch_verify_multi_points, fold_proof},
+    proof::PlonkProof,
+    transcript::Transcript,
+    vk::PlonkVerifyingKey,
+};
+
+/// Verify a GNARK Plonk proof against its public inputs, following GNARK's `plonk.Verify` for
+/// BN254 with SHA-256 challenges.
+pub(crate) fn verify_plonk_algebraic(
+    vk: &PlonkVerifyingKey,
+    proof: &PlonkProof,
+    public_inputs: &[Fr],
+) -> Result<(), Sp1PlonkError> {
+    if public_inputs.len() as u64 != vk.nb_public_variables {
+        return Err(PublicInputCountError {
+            expected: vk.nb_public_variables as usize,
+            actual: public_inputs.len(),
+        }
+        .into());
+    }
+    if proof.bsb22_commitments.len() != vk.qcp.len() {
+        return Err(Sp1PlonkError::VerificationFailed);
+    }
+
+    // Derive the challenges γ, β, α and ζ. γ binds the verifying key, the public inputs and the
+    // wire commitments, α the BSB22 commitments and Z, and ζ the quotient commitments.
+    let mut transcript = Transcript::default();
+    let mut bindings = Vec::new();
+    let vk_points =
+        vk.s.iter()
+            .chain([&vk.ql, &vk.qr, &vk.qm, &vk.qo, &vk.qk])
+            .chain(&vk.qcp);
+    for point in vk_points {
+        bindings.extend_from_slice(&g1_to_uncompressed_bytes((*point).into()));
+    }
+    for input in public_inputs {
+        bindings.extend_from_slice(&fr_to_bytes(*input));
+    }
+    bind_points(&mut bindings, &proof.lro);
+    let gamma = transcript.challenge("gamma", &bindings);
+
+    let beta = transcript.challenge("beta", &[]);
+
+    bindings.clear();
+    bind_points(&mut bindings, &proof.bsb22_commitments);
+    bind_points(&mut bindings, &[proof.z]);
+    let alpha = transcript.challenge("alpha", &bindings);
+
+    bindings.clear();
+    bind_points(&mut bindings, &proof.h);
+    let zeta = transcript.challenge("zeta", &bindings);
+
+    // ζⁿ - 1 and L₁(ζ) = (ζⁿ - 1) / (n(ζ - 1)).
+    let one = Fr::one();
+    let zeta_power_n = pow(zeta, vk.size);
+    let zh_zeta = zeta_power_n - one;
+    let lagrange_one = zh_zeta * vk.size_inv * inverse(zeta - one)?;
+
+    // PI(ζ) = ∑ᵢ Lᵢ(ζ)·wᵢ, with Lᵢ(ζ) = ωⁱ(ζⁿ - 1) / (n(ζ - ωⁱ)).
+    let mut pi = Fr::zero();
+    let mut omega_i = one;
+    for input in public_inputs {
+        pi = pi + zh_zeta * vk.size_inv * omega_i * inverse(zeta - omega_i)? * *input;
+        omega_i = omega_i * vk.generator;
+    }
+    // Each BSB22 commitment is hashed into the public input at its constraint index.
+    for (commitment, index) in proof
+        .bsb22_commitments
+        .iter()
+        .zip(&vk.commitment_constraint_indexes)
+    {
+        let hashed = hash_to_fr(&g1_to_uncompressed_bytes(*commitment), BSB22_DST);
+        let omega_index = pow(vk.generator, vk.nb_public_variables + index);
+        let lagrange = (zeta - one) * omega_index * inverse(zeta - omega_index)? * lagrange_one;
+        pi = pi + lagrange * hashed;
+    }
+
+    let [l, r, o, s1, s2] = proof.claimed_values;
+    let zu = proof.z_shifted_value;
+    let alpha_square_lagrange_one = lagrange_one * alpha * alpha;
+
+    // Opening of the linearised polynomial at ζ:
+    // -[PI(ζ) - α²L₁(ζ) + α(l(ζ)+βs1(ζ)+γ)(r(ζ)+βs2(ζ)+γ)(o(ζ)+γ)Z(ωζ)]
+    let permutation = (l + beta * s1 + gamma) * (r + beta * s2 + gamma);
+    let const_lin = -(pi - alpha_square_lagrange_one + alpha * permutation * (o + gamma) * zu);
+
+    // Coefficients of the linearised polynomial digest:
+    // ∑ᵢQcpᵢ(ζ)[Bsb22ᵢ] + l(ζ)[Ql] + r(ζ)[Qr] + l(ζ)r(ζ)[Qm] + o(ζ)[Qo] + [Qk] + _s1[S3]
+    // + (α²L₁(ζ) + _s2)[Z] - (ζⁿ - 1)([H0] + ζⁿ⁺²[H1] + ζ²⁽ⁿ⁺²⁾[H2])
+    let u = vk.coset_shift;
+    let s1_coeff = alpha * beta * zu * permutation;
+    let s2_coeff = -(alpha
+        * (l + beta * zeta + gamma)
+        * (r + beta * u * zeta + gamma)
+        * (o + beta * u * u * zeta + gamma));
+    let zeta_n_plus_two = zeta_power_n * zeta * zeta;
+    let zh = -zh_zeta;
+    let zeta_n_plus_two_zh = zh * zeta_n_plus_two;
+    let zeta_n_plus_two_square_zh = zeta_n_plus_two_zh * zeta_n_plus_two;
+
+    let points = proof.bsb22_commitments.iter().copied().chain([
+        vk.ql.into(),
+        vk.qr.into(),
+        vk.qm.into(),
+        vk.qo.into(),
+        vk.qk.into(),
+        vk.s[2].into(),
+        proof.z,
+        proof.h[0],
+        proof.h[1],
+        proof.h[2],
+    ]);
+    let scalars = proof.qcp_values.iter().copied().chain([
+        l,
+        r,
+        l * r,
+        o,
+        one,
+        s1_coeff,
+        alpha_square_lagrange_one + s2_coeff,
+        zh,
+        zeta_n_plus_two_zh,
+        zeta_n_plus_two_square_zh,
+    ]);
+    let linearised_digest = points
+        .zip(scalars)
+        .fold(G1::zero(), |acc, (point, scalar)| acc + point * scalar);
+
+    // Fold the openings at ζ, then check them together with the opening of Z at ωζ.
+    let mut digests = vec![
+        linearised_digest,
+        proof.lro[0],
+        proof.lro[1],
+        proof.lro[2],
+        vk.s[0].into(),
+        vk.s[1].into(),
+    ];
+    digests.extend(vk.qcp.iter().map(|&point| G1::from(point)));
+    let mut claimed_values = vec![const_lin, l, r, o, s1, s2];
+    claimed_values.extend_from_slice(&proof.qcp_values);
+
+    let (folded_digest, folded_proof) = fold_proof(
+        &digests,
+        &claimed_values,
+        proof.batched_h,
+        zeta,
+        &fr_to_bytes(zu),
+    );
+    let z_shifted_proof = OpeningProof {
+        h: proof.z_shifted_h,
+        claimed_value: zu,
+    };
+
+    batch_verify_multi_points(
+        &[folded_digest, proof.z],
+        &[folded_proof, z_shifted_proof],
+        &[zeta, zeta * vk.generator],
+        vk,
+    )
+}
+
+fn bind_points(bindings: &mut Vec<u8>, points: &[G1]) {
+    for point in points {
+        bindings.extend_from_slice(&g1_to_uncompressed_bytes(*point));
+    }
+}
+
+/// Inverts `x`, failing verification if it is zero. This only happens if a challenge hits a
+/// root of unity, which a valid proof avoids with overwhelming probability.
+fn inverse(x: Fr) -> Result<Fr, Sp1PlonkError> {
+    x.inverse().ok_or(Sp1PlonkError::VerificationFailed)
+}
+
+/// Computes `base^exp` by square-and-multiply.
+fn pow(base: Fr, mut exp: u64) -> Fr {
+    let mut result = Fr::one();
+    let mut square = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * square;
+        }
+        square = square * square;
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use bn::Fr;
+
+    use super::verify_plonk_algebraic;
+    use crate::{
+        constant::{FIELD_SIZE, G1_UNCOMPRESSED_SIZE, VK_HASH_PREFIX_LENGTH},
+        error::{PublicInputCountError, Sp1PlonkError},
+        hashes::sha256_to_fr,
+        proof::PlonkProof,
+        vk::PlonkVerifyingKey,
+    };
+
+    // SP1 v6 does not publish a Plonk proof, so these tests use the fixture of the SP1 v3.4.0
+    // verifier. Its circuit only takes `(program_vk_hash, sha256(public_values))` as public
+    // inputs, but the Plonk algebra is the same.
+    const VK_BYTES: &[u8] = include_bytes!("../proofs/plonk_vk_SP1_v3.4.0.bin");
+    const PROOF_BYTES: &[u8] = include_bytes!("../proofs/fibonacci_SP1_v3.4.0.plonk.bin");
+    const PROGRAM_VK_HASH: &str =
+        "00e60860c07bfc6e4c480286c0ddbb879674eb47f84b4ef041cf858b17aa0ed1";
+    const PUBLIC_VALUES: &str = "f4010000f404000086070000";
+
+    fn load_vk() -> PlonkVerifyingKey {
+        PlonkVerifyingKey::from_gnark_bytes(VK_BYTES).unwrap()
+    }
+
+    fn load_proof(vk: &PlonkVerifyingKey, bytes: &[u8]) -> PlonkProof {
+        PlonkProof::from_gnark_bytes(&bytes[VK_HASH_PREFIX_LENGTH..], vk.qcp.len()).unwrap()
+    }
+
+    fn public_inputs(public_values: &[u8]) -> Vec<Fr> {
+        vec![
+            Fr::from_slice(&hex::decode(PROGRAM_VK_HASH).unwrap()).unwrap(),
+            sha256_to_fr(public_values).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn verifies_sp1_plonk_proof() {
+        let vk = load_vk();
+        let proof = load_proof(&vk, PROOF_BYTES);
+        verify_plonk_algebraic(
+            &vk,
+            &proof,
+            &public_inputs(&hex::decode(PUBLIC_VALUES).unwrap()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_tampered_public_inputs() {
+        let vk = load_vk();
+        let proof = load_proof(&vk, PROOF_BYTES);
+        let public_values = hex::decode(PUBLIC_VALUES).unwrap();
+
+        let mut tampered_values = public_values.clone();
+        tampered_values[0] ^= 1;
+        assert!(matches!(
+            verify_plonk_algebraic(&vk, &proof, &public_inputs(&tampered_values)),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+
+        let mut wrong_program = public_inputs(&public_values);
+        wrong_program[0] = wrong_program[0] + Fr::one();
+        assert!(matches!(
+            verify_plonk_algebraic(&vk, &proof, &wrong_program),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+
+        let mut extra_input = public_inputs(&public_values);
+        extra_input.push(Fr::one());
+        assert!(matches!(
+            verify_plonk_algebraic(&vk, &proof, &extra_input),
+            Err(Sp1PlonkError::PublicInputCount(PublicInputCountError {
+                expected: 2,
+                actual: 3
+            }))
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_proof() {
+        let vk = load_vk();
+        let public_inputs = public_inputs(&hex::decode(PUBLIC_VALUES).unwrap());
+
+        // Bump the claimed value of `L` at ζ, the first field element after the six commitments
+        // to the wires and the quotient, and the claimed value of `Z` at ωζ.
+        let claimed_l = VK_HASH_PREFIX_LENGTH + 6 * G1_UNCOMPRESSED_SIZE + FIELD_SIZE - 1;
+        let z_shifted = claimed_l + 5 * FIELD_SIZE + G1_UNCOMPRESSED_SIZE;
+        for offset in [claimed_l, z_shifted] {
+            let mut tampered = PROOF_BYTES.to_vec();
+            tampered[offset] ^= 1;
+            let proof = load_proof(&vk, &tampered);
+            assert!(matches!(
+                verify_plonk_algebraic(&vk, &proof, &public_inputs),
+                Err(Sp1PlonkError::VerificationFailed)
+            ));
+        }
+
+        // Swapping two commitments keeps every point on the curve.
+        let mut swapped = load_proof(&vk, PROOF_BYTES);
+        swapped.lro.swap(0, 1);
+        assert!(matches!(
+            verify_plonk_algebraic(&vk, &swapped, &public_inputs),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+    }
+}
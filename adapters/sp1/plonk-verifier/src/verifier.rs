@@ -0,0 +1,286 @@
+//! Hosts [`SP1PlonkVerifier`], the crate's stateful SP1 Plonk proof verifier.
+//!
+//! See the [crate-level docs](crate) for the operational model and the wire format accepted by
+//! `verify`. This module splits the SP1 metadata off the proof, cross-checks it against the
+//! pinned values, and hands the GNARK proof to the algebraic check. The [`ZkVmVerifier`] impl at
+//! the bottom of the file is a thin adapter that stringifies `Sp1PlonkError` into `ZkVmError`.
+
+use std::fmt;
+
+use bn::Fr;
+use sha2::{Digest, Sha256};
+use zkaleido::{ProofReceipt, ZkVmError, ZkVmResult, ZkVmVerifier};
+
+use crate::{
+    constant::{
+        FIELD_SIZE, SP1_METADATA_SIZE, SP1_NUM_PUBLIC_INPUTS, SUCCESS_EXIT_CODE,
+        VK_HASH_PREFIX_LENGTH,
+    },
+    error::{InvalidProofFormatError, PublicInputCountError, SerializationError, Sp1PlonkError},
+    hashes::{blake3_to_fr, sha256_to_fr},
+    proof::PlonkProof,
+    verification::verify_plonk_algebraic,
+    vk::PlonkVerifyingKey,
+};
+
+/// A stateful verifier for SP1 Plonk proofs.
+///
+/// Construction (see [`SP1PlonkVerifier::load`]) parses the Plonk verifying key and pins the
+/// `program_vk_hash` and `vk_root` public inputs, so callers of [`SP1PlonkVerifier::verify`]
+/// only supply the proof and the public values of the program.
+#[derive(Clone)]
+pub struct SP1PlonkVerifier {
+    /// First `VK_HASH_PREFIX_LENGTH` bytes of `Sha256(plonk_vk)`. SP1 prepends this as an
+    /// advisory tag on emitted proofs; when the proof carries the tag, it must match.
+    pub vk_hash_tag: [u8; VK_HASH_PREFIX_LENGTH],
+    /// SP1 recursion verifier-key root pinned by this verifier. The proof must carry the same
+    /// root, which is also bound to the proof as a public input.
+    pub vk_root: [u8; 32],
+    /// When `true`, the verifier requires the proof to commit to a successful exit code
+    /// (`SUCCESS_EXIT_CODE`); when `false`, it accepts whatever exit code the proof commits to.
+    pub require_success: bool,
+    /// Hash of the verifying key of the SP1 program whose proofs are accepted.
+    program_vk_hash: Fr,
+    /// The Plonk verifying key of the SP1 circuit.
+    vk: PlonkVerifyingKey,
+}
+
+impl SP1PlonkVerifier {
+    /// Build a `SP1PlonkVerifier` from a GNARK-serialized Plonk verifying key and a program
+    /// identifier.
+    ///
+    /// Besides parsing the verifying key, `load` computes `vk_hash_tag = Sha256(vk_bytes)[..4]`,
+    /// the advisory prefix SP1 prepends to emitted proofs, and checks the circuit takes SP1's
+    /// five public inputs.
+    pub fn load(
+        vk_bytes: &[u8],
+        program_vk_hash: [u8; 32],
+        vk_root: [u8; 32],
+        require_success: bool,
+    ) -> Result<Self, Sp1PlonkError> {
+        let digest = Sha256::digest(vk_bytes);
+        let mut vk_hash_tag = [0u8; VK_HASH_PREFIX_LENGTH];
+        vk_hash_tag.copy_from_slice(&digest[..VK_HASH_PREFIX_LENGTH]);
+
+        let vk = PlonkVerifyingKey::from_gnark_bytes(vk_bytes)?;
+        if vk.nb_public_variables != SP1_NUM_PUBLIC_INPUTS as u64 {
+            return Err(PublicInputCountError {
+                expected: SP1_NUM_PUBLIC_INPUTS,
+                actual: vk.nb_public_variables as usize,
+            }
+            .into());
+        }
+
+        let program_vk_hash = Fr::from_slice(&program_vk_hash).map_err(SerializationError::from)?;
+
+        Ok(SP1PlonkVerifier {
+            vk_hash_tag,
+            vk_root,
+            require_success,
+            program_vk_hash,
+            vk,
+        })
+    }
+
+    /// Verify an SP1 Plonk proof against the given public values.
+    ///
+    /// `proof` is `[vk_hash_tag(4)] || exit_code(32) || vk_root(32) || proof_nonce(32) ||
+    /// plonk_proof`, as returned by SP1's `SP1ProofWithPublicValues::bytes`, with or without the
+    /// leading tag. `plonk_proof` is GNARK's Solidity encoding of the proof.
+    ///
+    /// 1. **Cross-checks.** The `vk_hash_tag`, when present, and the `vk_root` must equal the
+    ///    verifier's pinned values. The `exit_code` must be `SUCCESS_EXIT_CODE` when
+    ///    `require_success` is set.
+    /// 2. **Algebraic verification** of the Plonk proof against the public inputs
+    ///    `(program_vk_hash, hash(public_values), exit_code, vk_root, proof_nonce)`.
+    ///
+    /// # HACK: SHA-256 / Blake3 retry
+    /// SP1 accepts either SHA-256 or Blake3 for `hash(public_values)`, and the on-wire format
+    /// does not record which was used. We try SHA-256 first, then retry with Blake3 if that
+    /// fails.
+    pub fn verify(&self, proof: &[u8], public_values: &[u8]) -> Result<(), Sp1PlonkError> {
+        let plonk_len = PlonkProof::encoded_len(self.vk.qcp.len());
+        let untagged_len = SP1_METADATA_SIZE + plonk_len;
+        let untagged = if proof.len() == VK_HASH_PREFIX_LENGTH + untagged_len {
+            let (tag, rest) = proof.split_at(VK_HASH_PREFIX_LENGTH);
+            if tag != self.vk_hash_tag {
+                let mut actual = [0u8; VK_HASH_PREFIX_LENGTH];
+                actual.copy_from_slice(tag);
+                return Err(Sp1PlonkError::VkeyHashMismatch {
+                    expected: self.vk_hash_tag,
+                    actual,
+                });
+            }
+            rest
+        } else if proof.len() == untagged_len {
+            proof
+        } else {
+            return Err(SerializationError::from(InvalidProofFormatError {
+                expected: VK_HASH_PREFIX_LENGTH + untagged_len,
+                actual: proof.len(),
+            })
+            .into());
+        };
+
+        let field = |i: usize| {
+            let mut bytes = [0u8; FIELD_SIZE];
+            bytes.copy_from_slice(&untagged[i * FIELD_SIZE..(i + 1) * FIELD_SIZE]);
+            bytes
+        };
+        let (exit_code, vk_root, proof_nonce) = (field(0), field(1), field(2));
+
+        if vk_root != self.vk_root {
+            return Err(Sp1PlonkError::VkeyRootMismatch {
+                expected: self.vk_root,
+                actual: vk_root,
+            });
+        }
+        if self.require_success && exit_code != SUCCESS_EXIT_CODE {
+            return Err(Sp1PlonkError::ExitCodeMismatch {
+                expected: SUCCESS_EXIT_CODE,
+                actual: exit_code,
+            });
+        }
+
+        let plonk_proof =
+            PlonkProof::from_gnark_bytes(&untagged[SP1_METADATA_SIZE..], self.vk.qcp.len())?;
+
+        let mut public_inputs = [
+            self.program_vk_hash,
+            sha256_to_fr(public_values)?,
+            Fr::from_slice(&exit_code).map_err(SerializationError::from)?,
+            Fr::from_slice(&vk_root).map_err(SerializationError::from)?,
+            Fr::from_slice(&proof_nonce).map_err(SerializationError::from)?,
+        ];
+
+        if verify_plonk_algebraic(&self.vk, &plonk_proof, &public_inputs).is_ok() {
+            return Ok(());
+        }
+
+        // If SHA-256 verification fails, retry with the Blake3 hash of `public_values`.
+        public_inputs[1] = blake3_to_fr(public_values)?;
+        verify_plonk_algebraic(&self.vk, &plonk_proof, &public_inputs)
+    }
+}
+
+impl fmt::Debug for SP1PlonkVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SP1PlonkVerifier")
+            .field("vk_hash_tag", &self.vk_hash_tag)
+            .field("vk_root", &self.vk_root)
+            .field("require_success", &self.require_success)
+            .field("vk", &self.vk)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ZkVmVerifier for SP1PlonkVerifier {
+    fn verify(&self, receipt: &ProofReceipt) -> ZkVmResult<()> {
+        SP1PlonkVerifier::verify(
+            self,
+            receipt.proof().as_bytes(),
+            receipt.public_values().as_bytes(),
+        )
+        .map_err(|e| ZkVmError::ProofVerificationError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_verifier::{PLONK_VK_BYTES, VK_ROOT_BYTES};
+
+    use super::SP1PlonkVerifier;
+    use crate::{
+        constant::{SP1_METADATA_SIZE, VK_HASH_PREFIX_LENGTH},
+        error::{SerializationError, Sp1PlonkError},
+        proof::PlonkProof,
+    };
+
+    fn load(require_success: bool) -> SP1PlonkVerifier {
+        SP1PlonkVerifier::load(&PLONK_VK_BYTES, [1u8; 32], *VK_ROOT_BYTES, require_success).unwrap()
+    }
+
+    /// A proof carrying the verifier's tag and `vk_root`, with an all-zero Plonk proof.
+    fn proof_bytes(verifier: &SP1PlonkVerifier, exit_code: u8) -> Vec<u8> {
+        let mut proof = verifier.vk_hash_tag.to_vec();
+        let mut exit_code_bytes = [0u8; 32];
+        exit_code_bytes[31] = exit_code;
+        proof.extend_from_slice(&exit_code_bytes);
+        proof.extend_from_slice(&verifier.vk_root);
+        proof.extend_from_slice(&[0u8; 32]);
+        proof.resize(
+            proof.len() + PlonkProof::encoded_len(verifier.vk.qcp.len()),
+            0,
+        );
+        proof
+    }
+
+    #[test]
+    fn loads_sp1_plonk_vk() {
+        let verifier = load(true);
+        assert_eq!(verifier.vk.nb_public_variables, 5);
+        assert!(verifier.vk.size.is_power_of_two());
+        assert_eq!(
+            verifier.vk.commitment_constraint_indexes.len(),
+            verifier.vk.qcp.len()
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_vk() {
+        let result = SP1PlonkVerifier::load(&PLONK_VK_BYTES[..100], [1u8; 32], [0u8; 32], true);
+        assert!(matches!(
+            result,
+            Err(Sp1PlonkError::Serialization(
+                SerializationError::BufferLength(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn checks_metadata_before_the_proof() {
+        let verifier = load(true);
+        let proof = proof_bytes(&verifier, 0);
+
+        // The all-zero proof passes the cross-checks and fails the algebraic check.
+        assert!(matches!(
+            verifier.verify(&proof, b""),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verifier.verify(&proof[VK_HASH_PREFIX_LENGTH..], b""),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+
+        let mut wrong_tag = proof.clone();
+        wrong_tag[0] ^= 1;
+        assert!(matches!(
+            verifier.verify(&wrong_tag, b""),
+            Err(Sp1PlonkError::VkeyHashMismatch { .. })
+        ));
+
+        let mut wrong_root = proof.clone();
+        wrong_root[VK_HASH_PREFIX_LENGTH + 32] ^= 1;
+        assert!(matches!(
+            verifier.verify(&wrong_root, b""),
+            Err(Sp1PlonkError::VkeyRootMismatch { .. })
+        ));
+
+        let failed = proof_bytes(&verifier, 1);
+        assert!(matches!(
+            verifier.verify(&failed, b""),
+            Err(Sp1PlonkError::ExitCodeMismatch { .. })
+        ));
+        assert!(matches!(
+            load(false).verify(&failed, b""),
+            Err(Sp1PlonkError::VerificationFailed)
+        ));
+
+        assert!(matches!(
+            verifier.verify(&proof[..VK_HASH_PREFIX_LENGTH + SP1_METADATA_SIZE], b""),
+            Err(Sp1PlonkError::Serialization(
+                SerializationError::InvalidProofFormat(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+use bn::{AffineG1, AffineG2, Fr};
+
+use crate::{
+    constant::{
+        FIELD_SIZE, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE, GNARK_VK_HEADER_SIZE,
+        GNARK_VK_KZG_SIZE, GNARK_VK_NUM_QCP_OFFSET, GNARK_VK_S_OFFSET, GNARK_VK_SELECTORS_OFFSET,
+        U32_SIZE, U64_SIZE,
+    },
+    encoding::{fr_from_bytes, g1_from_compressed_bytes, g2_from_compressed_bytes},
+    error::{BufferLengthError, InvalidDataFormatError, SerializationError},
+};
+
+/// A Plonk verifying key over BN254, as serialized by GNARK.
+#[derive(Clone)]
+pub(crate) struct PlonkVerifyingKey {
+    /// Size of the evaluation domain.
+    pub(crate) size: u64,
+    /// Inverse of `size` in `Fr`.
+    pub(crate) size_inv: Fr,
+    /// Generator `ω` of the evaluation domain.
+    pub(crate) generator: Fr,
+    /// Number of public inputs of the circuit.
+    pub(crate) nb_public_variables: u64,
+    /// Shift `u` of the cosets of the permutation argument.
+    pub(crate) coset_shift: Fr,
+    /// Commitments to the permutation polynomials `S1, S2, S3`.
+    pub(crate) s: [AffineG1; 3],
+    /// Commitment to the left selector.
+    pub(crate) ql: AffineG1,
+    /// Commitment to the right selector.
+    pub(crate) qr: AffineG1,
+    /// Commitment to the multiplication selector.
+    pub(crate) qm: AffineG1,
+    /// Commitment to the output selector.
+    pub(crate) qo: AffineG1,
+    /// Commitment to the constant selector.
+    pub(crate) qk: AffineG1,
+    /// Commitments to the selectors of the BSB22 commitment custom gates, one per commitment.
+    pub(crate) qcp: Vec<AffineG1>,
+    /// Generator of G1 of the KZG setup.
+    pub(crate) kzg_g1: AffineG1,
+    /// `[1]G2` and `[τ]G2` of the KZG setup.
+    pub(crate) kzg_g2: [AffineG2; 2],
+    /// Constraint index of each BSB22 commitment, relative to the public inputs.
+    pub(crate) commitment_constraint_indexes: Vec<u64>,
+}
+
+impl PlonkVerifyingKey {
+    /// Deserialize from the binary encoding of GNARK's `VerifyingKey.WriteTo`.
+    ///
+    /// Layout:
+    /// - `size` (u64), `size_inv`, `generator` (Fr), `nb_public_variables` (u64), `coset_shift`
+    ///   (Fr), all big-endian
+    /// - `S[0..3]`, `Ql`, `Qr`, `Qm`, `Qo`, `Qk` as compressed G1 points
+    /// - `num_qcp` (u32) followed by `num_qcp` compressed G1 points
+    /// - the KZG key: compressed `G1`, `G2[0]` and `G2[1]`, followed by precomputed pairing lines
+    /// - `commitment_constraint_indexes`: a length followed by one u64 per commitment
+    ///
+    /// The size of the precomputed lines depends on the GNARK version and they are not needed
+    /// for verification, so the commitment constraint indexes are read from the end of the
+    /// buffer. There is one index per `Qcp` commitment.
+    pub(crate) fn from_gnark_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        if bytes.len() < GNARK_VK_HEADER_SIZE {
+            return Err(BufferLengthError {
+                context: "Plonk VK header",
+                expected: GNARK_VK_HEADER_SIZE,
+                actual: bytes.len(),
+            }
+            .into());
+        }
+
+        let num_qcp = read_u32(bytes, GNARK_VK_NUM_QCP_OFFSET) as usize;
+        // Checked, since `num_qcp` is untrusted and guests run on 32-bit targets.
+        let min_len = num_qcp
+            .checked_mul(G1_COMPRESSED_SIZE + U64_SIZE)
+            .and_then(|len| len.checked_add(GNARK_VK_HEADER_SIZE + GNARK_VK_KZG_SIZE + U32_SIZE));
+        if min_len.is_none_or(|min_len| bytes.len() < min_len) {
+            return Err(BufferLengthError {
+                context: "Plonk VK",
+                expected: min_len.unwrap_or(usize::MAX),
+                actual: bytes.len(),
+            }
+            .into());
+        }
+        let kzg_offset = GNARK_VK_HEADER_SIZE + num_qcp * G1_COMPRESSED_SIZE;
+        let indexes_len = num_qcp * U64_SIZE;
+
+        let fr_at = |offset: usize| fr_from_bytes(&bytes[offset..offset + FIELD_SIZE]);
+        let g1_at =
+            |offset: usize| g1_from_compressed_bytes(&bytes[offset..offset + G1_COMPRESSED_SIZE]);
+        let g1_points = |offset: usize, count: usize| {
+            (0..count)
+                .map(|i| g1_at(offset + i * G1_COMPRESSED_SIZE))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let s = g1_points(GNARK_VK_S_OFFSET, 3)?;
+        let selectors = g1_points(GNARK_VK_SELECTORS_OFFSET, 5)?;
+
+        let g2_offset = kzg_offset + G1_COMPRESSED_SIZE;
+        let kzg_g2 = [
+            g2_from_compressed_bytes(&bytes[g2_offset..g2_offset + G2_COMPRESSED_SIZE])?,
+            g2_from_compressed_bytes(
+                &bytes[g2_offset + G2_COMPRESSED_SIZE..g2_offset + 2 * G2_COMPRESSED_SIZE],
+            )?,
+        ];
+
+        // The low four bytes of the length are the same whether GNARK writes it as a u32 or a
+        // u64, so only those are checked.
+        let indexes_offset = bytes.len() - indexes_len;
+        if read_u32(bytes, indexes_offset - U32_SIZE) as usize != num_qcp {
+            return Err(InvalidDataFormatError.into());
+        }
+        let commitment_constraint_indexes = (0..num_qcp)
+            .map(|i| read_u64(bytes, indexes_offset + i * U64_SIZE))
+            .collect();
+
+        Ok(PlonkVerifyingKey {
+            size: read_u64(bytes, 0),
+            size_inv: fr_at(U64_SIZE)?,
+            generator: fr_at(U64_SIZE + FIELD_SIZE)?,
+            nb_public_variables: read_u64(bytes, U64_SIZE + 2 * FIELD_SIZE),
+            coset_shift: fr_at(2 * U64_SIZE + 2 * FIELD_SIZE)?,
+            s: [s[0], s[1], s[2]],
+            ql: selectors[0],
+            qr: selectors[1],
+            qm: selectors[2],
+            qo: selectors[3],
+            qk: selectors[4],
+            qcp: g1_points(GNARK_VK_HEADER_SIZE, num_qcp)?,
+            kzg_g1: g1_at(kzg_offset)?,
+            kzg_g2,
+            commitment_constraint_indexes,
+        })
+    }
+}
+
+/// Reads a big-endian u32 at `offset`; the caller checks the buffer is long enough.
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buf = [0u8; U32_SIZE];
+    buf.copy_from_slice(&bytes[offset..offset + U32_SIZE]);
+    u32::from_be_bytes(buf)
+}
+
+/// Reads a big-endian u64 at `offset`; the caller checks the buffer is long enough.
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; U64_SIZE];
+    buf.copy_from_slice(&bytes[offset..offset + U64_SIZE]);
+    u64::from_be_bytes(buf)
+}
+
+impl fmt::Debug for PlonkVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlonkVerifyingKey")
+            .field("size", &self.size)
+            .field("nb_public_variables", &self.nb_public_variables)
+            .field("num_qcp", &self.qcp.len())
+            .finish_non_exhaustive()
+    }
+}
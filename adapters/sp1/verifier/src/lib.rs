@@ -0,0 +1,16 @@
+//! # zkaleido-sp1-verifier
+//!
+//! This crate verifies [SP1](https://docs.succinct.xyz/docs/sp1/introduction) receipts saved as
+//! [`zkaleido::ProofReceiptWithMetadata`] without depending on the SP1 SDK, so that nodes which
+//! only verify do not pull in a prover client.
+//!
+//! Groth16 and Plonk receipts are checked with the pure-Rust verifiers of
+//! `zkaleido-sp1-groth16-verifier` and `zkaleido-sp1-plonk-verifier`, and Compressed receipts with
+//! the STARK verifier of `sp1-verifier`. Core receipts can only be verified by SP1's prover
+//! client, through `zkaleido-sp1-host`.
+
+mod receipt;
+mod verifier;
+
+pub use receipt::SP1RawReceipt;
+pub use verifier::SP1ReceiptVerifier;
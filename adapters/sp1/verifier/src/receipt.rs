@@ -0,0 +1,53 @@
+use zkaleido::{
+    Mismatched, ProofReceipt, ProofReceiptWithMetadata, ProofType, ZkVm, ZkVmProofError,
+};
+
+/// An SP1 receipt kept in the encoding of [`ProofReceiptWithMetadata`].
+///
+/// The proof bytes are not decoded into SP1 SDK types: Groth16 and Plonk proofs are stored in the
+/// layout their verifiers expect, and Compressed proofs are decoded by the verifier itself.
+#[derive(Debug, Clone)]
+pub struct SP1RawReceipt {
+    receipt: ProofReceipt,
+    proof_type: ProofType,
+}
+
+impl SP1RawReceipt {
+    /// Returns the proof and public values of the receipt.
+    pub fn receipt(&self) -> &ProofReceipt {
+        &self.receipt
+    }
+
+    /// Returns the type of the proof.
+    pub fn proof_type(&self) -> ProofType {
+        self.proof_type
+    }
+}
+
+impl TryFrom<ProofReceiptWithMetadata> for SP1RawReceipt {
+    type Error = ZkVmProofError;
+    fn try_from(value: ProofReceiptWithMetadata) -> Result<Self, Self::Error> {
+        SP1RawReceipt::try_from(&value)
+    }
+}
+
+impl TryFrom<&ProofReceiptWithMetadata> for SP1RawReceipt {
+    type Error = ZkVmProofError;
+    fn try_from(value: &ProofReceiptWithMetadata) -> Result<Self, Self::Error> {
+        let zkvm_in_proof = value.metadata().zkvm();
+        if zkvm_in_proof != &ZkVm::SP1 {
+            Err(Mismatched {
+                expected: ZkVm::SP1,
+                actual: *zkvm_in_proof,
+            })?
+        }
+
+        // The circuit version is not checked here: it is only known to the SDK. Proofs of other
+        // versions fail verification against the verifying keys and the recursion vk root of
+        // `sp1-verifier` instead.
+        Ok(SP1RawReceipt {
+            receipt: value.receipt().clone(),
+            proof_type: value.metadata().proof_type(),
+        })
+    }
+}
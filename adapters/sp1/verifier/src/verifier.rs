@@ -0,0 +1,259 @@
+use std::fmt;
+
+use num_bigint::BigUint;
+use sp1_hypercube::{HashableKey, SP1VerifyingKey};
+use sp1_verifier::{
+    GROTH16_VK_BYTES, PLONK_VK_BYTES, VK_ROOT_BYTES, compressed::SP1CompressedVerifierRaw,
+};
+use zkaleido::{
+    DataFormatError, ProgramId, ProofType, VerifyingKey, ZkVmError, ZkVmProofError, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVerifyingKeyError,
+};
+use zkaleido_sp1_groth16_verifier::SP1Groth16Verifier;
+use zkaleido_sp1_plonk_verifier::SP1PlonkVerifier;
+
+use crate::SP1RawReceipt;
+
+/// Modulus of the KoalaBear field, in which SP1 hashes verifying keys.
+const KOALA_BEAR_MODULUS: u32 = 0x7f00_0001;
+
+/// Number of bits each KoalaBear word of a verifying key hash takes in its BN254 packing.
+const PACKED_WORD_BITS: usize = 31;
+
+/// Verifies SP1 receipts of a single program from its program id alone.
+///
+/// Unlike `SP1Host`, this needs neither the guest ELF nor a prover client, which makes it suitable
+/// for nodes that only verify. Groth16 and Plonk receipts are checked with the pure-Rust
+/// [`SP1Groth16Verifier`] and [`SP1PlonkVerifier`], and Compressed receipts with the STARK
+/// verifier of `sp1-verifier`. Core receipts are rejected: only the SDK verifies them.
+#[derive(Clone)]
+pub struct SP1ReceiptVerifier {
+    program_id: ProgramId,
+    /// Bincode encoding of the KoalaBear hash of the program's verifying key, as expected by
+    /// [`SP1CompressedVerifierRaw`].
+    vkey_hash: Vec<u8>,
+    groth16: SP1Groth16Verifier,
+    plonk: SP1PlonkVerifier,
+}
+
+impl SP1ReceiptVerifier {
+    /// Creates a verifier for the program whose id is `program_id`, as returned by
+    /// [`ZkVmExecutor::program_id`](zkaleido::ZkVmExecutor::program_id) on an SP1 host.
+    ///
+    /// Fails with [`ZkVmError::InvalidVerifyingKey`] if `program_id` is not the hash of an SP1
+    /// verifying key.
+    pub fn from_program_id(program_id: ProgramId) -> ZkVmResult<Self> {
+        let words = vkey_hash_words(&program_id).ok_or_else(|| {
+            ZkVmVerifyingKeyError::DataFormat(DataFormatError::Other(format!(
+                "{program_id} is not an SP1 verifying key hash"
+            )))
+        })?;
+        let vkey_hash = bincode::serialize(&words).map_err(|e| {
+            ZkVmVerifyingKeyError::DataFormat(DataFormatError::Serde(e.to_string()))
+        })?;
+        let groth16 =
+            SP1Groth16Verifier::load(&GROTH16_VK_BYTES, program_id.0, *VK_ROOT_BYTES, true)
+                .map_err(|e| {
+                    ZkVmError::Other(format!("failed to load SP1 Groth16 verifier: {e}"))
+                })?;
+        let plonk = SP1PlonkVerifier::load(&PLONK_VK_BYTES, program_id.0, *VK_ROOT_BYTES, true)
+            .map_err(|e| ZkVmError::Other(format!("failed to load SP1 Plonk verifier: {e}")))?;
+        Ok(Self {
+            program_id,
+            vkey_hash,
+            groth16,
+            plonk,
+        })
+    }
+
+    /// Creates a verifier from the verifying key returned by
+    /// [`ZkVmVkProvider::vk`](zkaleido::ZkVmVkProvider::vk) on an SP1 host.
+    pub fn from_vk(vk: &VerifyingKey) -> ZkVmResult<Self> {
+        let vk: SP1VerifyingKey = bincode::deserialize(vk.as_bytes()).map_err(|e| {
+            ZkVmVerifyingKeyError::DataFormat(DataFormatError::Serde(e.to_string()))
+        })?;
+        Self::from_program_id(ProgramId(vk.bytes32_raw()))
+    }
+
+    /// Returns the program id of the program this verifier accepts receipts for.
+    pub fn program_id(&self) -> ProgramId {
+        self.program_id.clone()
+    }
+}
+
+/// Unpacks the eight KoalaBear words of a verifying key hash from `program_id`.
+///
+/// SP1 packs the words into a BN254 element 31 bits at a time, the last word in the least
+/// significant bits. Returns `None` if `program_id` is not such a packing.
+fn vkey_hash_words(program_id: &ProgramId) -> Option<[u32; 8]> {
+    let mut packed = BigUint::from_bytes_be(&program_id.0);
+    let mask = BigUint::from(u32::MAX >> 1);
+    let mut words = [0u32; 8];
+    for word in words.iter_mut().rev() {
+        *word = (&packed & &mask).iter_u32_digits().next().unwrap_or(0);
+        packed >>= PACKED_WORD_BITS;
+    }
+    let canonical = words.iter().all(|&word| word < KOALA_BEAR_MODULUS);
+    (packed.bits() == 0 && canonical).then_some(words)
+}
+
+impl ZkVmTypedVerifier for SP1ReceiptVerifier {
+    type ZkVmProofReceipt = SP1RawReceipt;
+
+    fn verify_inner(&self, receipt: &SP1RawReceipt) -> ZkVmResult<()> {
+        let proof = receipt.receipt().proof().as_bytes();
+        let public_values = receipt.receipt().public_values().as_bytes();
+        match receipt.proof_type() {
+            ProofType::Groth16 => self
+                .groth16
+                .verify(proof, public_values)
+                .map_err(|e| ZkVmError::ProofVerificationError(e.to_string())),
+            ProofType::Plonk => self
+                .plonk
+                .verify(proof, public_values)
+                .map_err(|e| ZkVmError::ProofVerificationError(e.to_string())),
+            ProofType::Compressed => SP1CompressedVerifierRaw::verify_with_public_values(
+                proof,
+                public_values,
+                &self.vkey_hash,
+            )
+            .map_err(|e| ZkVmError::ProofVerificationError(e.to_string())),
+            ProofType::Core => Err(ZkVmProofError::InvalidProofType(ProofType::Core).into()),
+        }
+    }
+}
+
+impl fmt::Debug for SP1ReceiptVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sp1_verifier_0x{}", self.program_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use zkaleido::{Proof, ProofMetadata, ProofReceipt, ProofReceiptWithMetadata, PublicValues};
+
+    use super::*;
+
+    fn groth16_receipt() -> ProofReceiptWithMetadata {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../groth16-verifier/proofs/fibonacci_SP1_v6.1.0.proof.bin");
+        ProofReceiptWithMetadata::load(path).unwrap()
+    }
+
+    /// Returns `receipt` with its proof or public values replaced.
+    fn with_parts(
+        receipt: &ProofReceiptWithMetadata,
+        proof: Option<Vec<u8>>,
+        public_values: Option<Vec<u8>>,
+        proof_type: ProofType,
+    ) -> ProofReceiptWithMetadata {
+        let inner = receipt.receipt();
+        let proof = proof.unwrap_or_else(|| inner.proof().as_bytes().to_vec());
+        let public_values =
+            public_values.unwrap_or_else(|| inner.public_values().as_bytes().to_vec());
+        let metadata = receipt.metadata();
+        ProofReceiptWithMetadata::new(
+            ProofReceipt::new(Proof::new(proof), PublicValues::new(public_values)),
+            ProofMetadata::new(
+                *metadata.zkvm(),
+                metadata.program_id().clone(),
+                metadata.version(),
+                proof_type,
+            ),
+        )
+    }
+
+    #[test]
+    fn verifies_groth16_receipts() {
+        let receipt = groth16_receipt();
+        let verifier =
+            SP1ReceiptVerifier::from_program_id(receipt.metadata().program_id().clone()).unwrap();
+        verifier.verify(&receipt).unwrap();
+
+        let mut public_values = receipt.receipt().public_values().as_bytes().to_vec();
+        public_values[0] ^= 1;
+        let tampered = with_parts(&receipt, None, Some(public_values), ProofType::Groth16);
+        assert!(matches!(
+            verifier.verify(&tampered),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_receipts_of_other_programs() {
+        let receipt = groth16_receipt();
+        let mut other = receipt.metadata().program_id().clone();
+        other.0[31] ^= 1;
+        let verifier = SP1ReceiptVerifier::from_program_id(other).unwrap();
+        assert!(matches!(
+            verifier.verify(&receipt),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+    }
+
+    /// No Plonk receipt of the current circuit version is checked in, so this only checks that
+    /// Plonk receipts reach the Plonk verifier, which rejects the Groth16 proof by its length.
+    #[test]
+    fn routes_plonk_receipts_to_the_plonk_verifier() {
+        let receipt = groth16_receipt();
+        let verifier =
+            SP1ReceiptVerifier::from_program_id(receipt.metadata().program_id().clone()).unwrap();
+
+        let relabeled = with_parts(&receipt, None, None, ProofType::Plonk);
+        match verifier.verify(&relabeled) {
+            Err(ZkVmError::ProofVerificationError(e)) => {
+                assert!(e.starts_with("Invalid SP1 Plonk proof length"), "{e}")
+            }
+            other => panic!("expected a verification error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_core_and_malformed_compressed_receipts() {
+        let receipt = groth16_receipt();
+        let verifier =
+            SP1ReceiptVerifier::from_program_id(receipt.metadata().program_id().clone()).unwrap();
+
+        let core = with_parts(&receipt, None, None, ProofType::Core);
+        assert!(matches!(
+            verifier.verify(&core),
+            Err(ZkVmError::InvalidProofReceipt(
+                ZkVmProofError::InvalidProofType(ProofType::Core)
+            ))
+        ));
+
+        let compressed = with_parts(&receipt, None, None, ProofType::Compressed);
+        assert!(matches!(
+            verifier.verify(&compressed),
+            Err(ZkVmError::ProofVerificationError(_))
+        ));
+    }
+
+    #[test]
+    fn unpacks_verifying_key_hashes() {
+        let words = [1, 2, 3, 4, 5, 6, 7, KOALA_BEAR_MODULUS - 1];
+        let packed = words.iter().fold(BigUint::default(), |packed, &word| {
+            (packed << PACKED_WORD_BITS) + word
+        });
+        let mut program_id = ProgramId([0; 32]);
+        let bytes = packed.to_bytes_be();
+        program_id.0[32 - bytes.len()..].copy_from_slice(&bytes);
+        assert_eq!(vkey_hash_words(&program_id), Some(words));
+
+        // Above the 248 bits of a packing.
+        program_id.0[0] = 1;
+        assert_eq!(vkey_hash_words(&program_id), None);
+        assert!(matches!(
+            SP1ReceiptVerifier::from_program_id(program_id),
+            Err(ZkVmError::InvalidVerifyingKey(_))
+        ));
+
+        // A word outside the field.
+        let mut program_id = ProgramId([0; 32]);
+        program_id.0[28..].copy_from_slice(&KOALA_BEAR_MODULUS.to_be_bytes());
+        assert_eq!(vkey_hash_words(&program_id), None);
+    }
+}
@@ -6,3 +6,4 @@ pub const FIBONACCI_COMPOSITION_ELF: Elf = include_elf!("guest-sp1-fibonacci-com
 pub const SHA2_CHAIN_ELF: Elf = include_elf!("guest-sp1-sha2-chain");
 pub const SCHNORR_SIG_VERIFY_ELF: Elf = include_elf!("guest-sp1-schnorr-sig-verify");
 pub const GROTH16_VERIFY_SP1_ELF: Elf = include_elf!("guest-sp1-groth16-verify-sp1");
+pub const WIRE_CONFORMANCE_ELF: Elf = include_elf!("guest-sp1-wire-conformance");
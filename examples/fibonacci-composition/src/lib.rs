@@ -1,10 +1,12 @@
-use zkaleido::ZkVmEnvSerde;
+use zkaleido::ZkVmEnvCodec;
+
+use crate::program::FibCompositionProgram;
 
 pub mod program;
 
-pub fn process_fibonacci_composition(zkvm: &impl ZkVmEnvSerde) {
-    // Read the verification key of sha2-chain program
-    let fib_vk: [u32; 8] = zkvm.read_serde();
-    let valid_fib_no: u32 = zkvm.read_verified_serde(&fib_vk);
-    zkvm.commit_serde(&valid_fib_no);
+pub fn process_fibonacci_composition(zkvm: &impl ZkVmEnvCodec) {
+    let input = FibCompositionProgram::reader(zkvm);
+    let fib_vk = input.fib_program_id().to_vk_digest();
+    let valid_fib_no: u32 = input.fib_proof_with_vk(&fib_vk);
+    FibCompositionProgram::commit_output(zkvm, &valid_fib_no);
 }
@@ -1,40 +1,36 @@
-use zkaleido::{AggregationInput, ProgramId, ProofType, ZkVmInputResult, ZkVmProgram};
+use zkaleido::{AggregationInput, ProgramId, RawCodec, SerdeCodec, ZkVmProgram};
 
-pub struct FibCompositionInput {
-    pub fib_proof_with_vk: AggregationInput,
+#[derive(ZkVmProgram)]
+#[zkvm(name = "fibonacci composition", proof_type = Compressed, codec = SerdeCodec, output = u32)]
+pub struct FibCompositionProgram {
     pub fib_program_id: ProgramId,
+    /// Proof of [`FibProgram`](fibonacci::program::FibProgram), whose output is raw-encoded.
+    #[zkvm(proof, codec = RawCodec)]
+    pub fib_proof_with_vk: AggregationInput,
 }
 
-pub struct FibCompositionProgram;
+#[cfg(test)]
+mod tests {
+    use fibonacci::program::FibProgram;
+    use zkaleido::{AggregationInput, RunConfig, ZkVmExecutor, ZkVmProgram, ZkVmVkProvider};
+    use zkaleido_native_adapter::NativeHost;
 
-impl ZkVmProgram for FibCompositionProgram {
-    type Input = FibCompositionInput;
-    type Output = u32;
-
-    fn name() -> String {
-        "fibonacci composition".to_owned()
-    }
+    use crate::{process_fibonacci_composition, program::FibCompositionProgram};
 
-    fn proof_type() -> zkaleido::ProofType {
-        ProofType::Compressed
-    }
-
-    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
-    where
-        B: zkaleido::ZkVmInputBuilder<'a>,
-    {
-        B::new()
-            .write_serde(&input.fib_program_id)?
-            .write_proof(&input.fib_proof_with_vk)?
-            .build()
-    }
+    #[test]
+    fn test_native() {
+        let fib_host = NativeHost::new_with_random_key(fibonacci::process_fibonacci);
+        let receipt = FibProgram::prove(&5, &fib_host, &RunConfig::default()).unwrap();
 
-    fn process_output<H>(
-        public_values: &zkaleido::PublicValues,
-    ) -> zkaleido::ZkVmResult<Self::Output>
-    where
-        H: zkaleido::ZkVmHost,
-    {
-        H::extract_serde_public_output(public_values)
+        let input = FibCompositionProgram {
+            fib_program_id: fib_host.program_id(),
+            fib_proof_with_vk: AggregationInput::new(receipt, fib_host.vk()),
+        };
+        let host = NativeHost::new_with_random_key(process_fibonacci_composition);
+        let receipt = FibCompositionProgram::prove(&input, &host, &RunConfig::default()).unwrap();
+        let output =
+            FibCompositionProgram::process_output::<NativeHost>(receipt.receipt().public_values())
+                .unwrap();
+        assert_eq!(output, 5);
     }
 }
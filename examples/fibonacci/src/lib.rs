@@ -1,11 +1,12 @@
-use zkaleido::ZkVmEnv;
+use zkaleido::{ZkVmCodecProgram, ZkVmEnvCodec};
 use zkaleido_logging::{debug, info, trace};
 
+use crate::program::FibProgram;
+
 pub mod program;
 
-pub fn process_fibonacci(zkvm: &impl ZkVmEnv) {
-    let buf = zkvm.read_buf();
-    let n = u32::from_le_bytes(buf.try_into().expect("invalid input length"));
+pub fn process_fibonacci(zkvm: &impl ZkVmEnvCodec) {
+    let n = FibProgram::read_input(zkvm);
     debug!(?n, "calculating fibonacci number");
 
     // Compute the n'th fibonacci number, using normal Rust code.
@@ -22,5 +23,5 @@ pub fn process_fibonacci(zkvm: &impl ZkVmEnv) {
     info!(%a, "fibonacci value output");
 
     // Write the output of the program.
-    zkvm.commit_buf(&a.to_le_bytes());
+    FibProgram::commit_output(zkvm, &a);
 }
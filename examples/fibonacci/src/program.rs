@@ -1,44 +1,14 @@
-use zkaleido::{DataFormatError, ProofType, ZkVmInputResult, ZkVmProgram};
+use zkaleido::{RawCodec, ZkVmProgram};
 
+#[derive(ZkVmProgram)]
+#[zkvm(name = "fibonacci", proof_type = Groth16, codec = RawCodec, input = u32, output = u32)]
 pub struct FibProgram;
 
-impl ZkVmProgram for FibProgram {
-    type Input = u32;
-    type Output = u32;
-
-    fn name() -> String {
-        "fibonacci".to_owned()
-    }
-
-    fn proof_type() -> zkaleido::ProofType {
-        ProofType::Groth16
-    }
-
-    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
-    where
-        B: zkaleido::ZkVmInputBuilder<'a>,
-    {
-        B::new().write_buf(&input.to_le_bytes())?.build()
-    }
-
-    fn process_output<H>(
-        public_values: &zkaleido::PublicValues,
-    ) -> zkaleido::ZkVmResult<Self::Output>
-    where
-        H: zkaleido::ZkVmHost,
-    {
-        let bytes: [u8; 4] = public_values.as_bytes().try_into().map_err(|_| {
-            zkaleido::ZkVmError::OutputExtractionError {
-                source: DataFormatError::Other("invalid output length".to_string()),
-            }
-        })?;
-        Ok(u32::from_le_bytes(bytes))
-    }
-}
-
 #[cfg(test)]
 pub mod tests {
-    use zkaleido::ZkVmProgram;
+    use zkaleido::{
+        CachingHost, InMemoryProofStore, LruEviction, ProofStore, RunConfig, ZkVmProgram,
+    };
     use zkaleido_native_adapter::NativeHost;
 
     use crate::{process_fibonacci, program::FibProgram};
@@ -51,9 +21,39 @@ pub mod tests {
     fn test_native() {
         let input = 5;
         let host = get_native_host();
-        let receipt = FibProgram::prove(&input, &host).unwrap();
+        let receipt = FibProgram::prove(&input, &host, &RunConfig::default()).unwrap();
         let output =
             FibProgram::process_output::<NativeHost>(receipt.receipt().public_values()).unwrap();
         assert_eq!(output, 5);
     }
+
+    #[test]
+    fn test_native_prove_to_store() {
+        let input = 5;
+        let host = get_native_host();
+        let store = InMemoryProofStore::new();
+        let receipt =
+            FibProgram::prove_to_store(&input, &host, &store, &RunConfig::default()).unwrap();
+
+        let key = FibProgram::proof_key(&input, &host).unwrap();
+        assert_eq!(store.get(&key).unwrap(), Some(receipt));
+        assert_ne!(key, FibProgram::proof_key(&6, &host).unwrap());
+    }
+
+    #[test]
+    fn test_native_caching_host() {
+        let host = CachingHost::new(get_native_host(), InMemoryProofStore::new())
+            .with_eviction(LruEviction::new(1));
+
+        let first = FibProgram::prove(&5, &host, &RunConfig::default()).unwrap();
+        let second = FibProgram::prove(&5, &host, &RunConfig::default()).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(host.store().list().unwrap().len(), 1);
+
+        // A different input misses the cache and evicts the previous proof.
+        let other = FibProgram::prove(&6, &host, &RunConfig::default()).unwrap();
+        let keys = host.store().list().unwrap();
+        assert_eq!(keys, vec![FibProgram::proof_key(&6, host.inner()).unwrap()]);
+        assert_eq!(host.store().get(&keys[0]).unwrap(), Some(other));
+    }
 }
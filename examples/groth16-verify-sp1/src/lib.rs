@@ -1,15 +1,15 @@
-use zkaleido::ZkVmEnvSerde;
+use zkaleido::{ZkVmCodecProgram, ZkVmEnvCodec};
 
-use crate::input::SP1Groth16VerifyInput;
+use crate::{input::SP1Groth16VerifyInput, program::SP1Groth16VerifyProgram};
 
 pub mod input;
 pub mod program;
 
-pub fn process_groth16_verify_sp1(zkvm: &impl ZkVmEnvSerde) {
+pub fn process_groth16_verify_sp1(zkvm: &impl ZkVmEnvCodec) {
     let SP1Groth16VerifyInput {
         sp1_receipt,
         sp1_verifier,
-    } = zkvm.read_serde();
+    } = SP1Groth16VerifyProgram::read_input(zkvm);
 
     let sp1_verified = sp1_verifier
         .verify(
@@ -18,5 +18,5 @@ pub fn process_groth16_verify_sp1(zkvm: &impl ZkVmEnvSerde) {
         )
         .is_ok();
 
-    zkvm.commit_serde(&sp1_verified);
+    SP1Groth16VerifyProgram::commit_output(zkvm, &sp1_verified);
 }
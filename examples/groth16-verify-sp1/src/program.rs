@@ -1,41 +1,20 @@
-use zkaleido::{ProofType, ZkVmInputResult, ZkVmProgram};
+use zkaleido::{SerdeCodec, ZkVmProgram};
 
 use crate::input::SP1Groth16VerifyInput;
 
+#[derive(ZkVmProgram)]
+#[zkvm(
+    name = "groth16_verify_sp1",
+    proof_type = Core,
+    codec = SerdeCodec,
+    input = SP1Groth16VerifyInput,
+    output = bool
+)]
 pub struct SP1Groth16VerifyProgram;
 
-impl ZkVmProgram for SP1Groth16VerifyProgram {
-    type Input = SP1Groth16VerifyInput;
-    type Output = bool;
-
-    fn name() -> String {
-        "groth16_verify_sp1".to_string()
-    }
-
-    fn proof_type() -> zkaleido::ProofType {
-        ProofType::Core
-    }
-
-    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
-    where
-        B: zkaleido::ZkVmInputBuilder<'a>,
-    {
-        B::new().write_serde(input)?.build()
-    }
-
-    fn process_output<H>(
-        public_values: &zkaleido::PublicValues,
-    ) -> zkaleido::ZkVmResult<Self::Output>
-    where
-        H: zkaleido::ZkVmHost,
-    {
-        H::extract_serde_public_output(public_values)
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use zkaleido::ZkVmProgram;
+    use zkaleido::{RunConfig, ZkVmProgram};
     use zkaleido_native_adapter::NativeHost;
 
     use crate::{
@@ -50,7 +29,7 @@ mod tests {
     fn test_native() {
         let input = SP1Groth16VerifyInput::load();
         let host = get_native_host();
-        let receipt = SP1Groth16VerifyProgram::prove(&input, &host).unwrap();
+        let receipt = SP1Groth16VerifyProgram::prove(&input, &host, &RunConfig::default()).unwrap();
         let is_verified = SP1Groth16VerifyProgram::process_output::<NativeHost>(
             receipt.receipt().public_values(),
         )
@@ -1,28 +1,34 @@
 use rand::{Rng, rngs::OsRng};
 use secp256k1::{SECP256K1, SecretKey};
+use zkaleido::{SszCodec, ZkVmProgram};
 
 use crate::logic::sign_schnorr_sig;
 
-#[derive(Debug, Clone)]
+/// Input of the Schnorr signature verification program, which is the program itself.
+#[derive(Debug, Clone, ZkVmProgram)]
+#[zkvm(name = "schnorr_sig_verify", proof_type = Core, codec = SszCodec, output = bool)]
 pub struct SchnorrSigInput {
     pub sig: [u8; 64],
     pub msg: [u8; 32],
     pub pk: [u8; 32],
-    pub sk: [u8; 32],
 }
 
 impl SchnorrSigInput {
     pub fn new_random() -> Self {
-        let msg: [u8; 32] = [(); 32].map(|_| OsRng.r#gen());
-
         let sk = SecretKey::new(&mut OsRng);
-        let (pk, _) = sk.x_only_public_key(SECP256K1);
+        Self::sign_random(sk.as_ref())
+    }
+
+    /// Signs a random message with the secret key `sk`.
+    pub fn sign_random(sk: &[u8; 32]) -> Self {
+        let msg: [u8; 32] = [(); 32].map(|_| OsRng.r#gen());
 
-        let sk = *sk.as_ref();
+        let secret_key = SecretKey::from_slice(sk).expect("Invalid private key");
+        let (pk, _) = secret_key.x_only_public_key(SECP256K1);
         let pk = pk.serialize();
 
-        let sig = sign_schnorr_sig(&msg, &sk);
+        let sig = sign_schnorr_sig(&msg, sk);
 
-        SchnorrSigInput { sig, msg, pk, sk }
+        SchnorrSigInput { sig, msg, pk }
     }
 }
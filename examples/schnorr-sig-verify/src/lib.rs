@@ -2,16 +2,17 @@ pub mod input;
 pub mod logic;
 pub mod program;
 
-use zkaleido::{ZkVmEnvSerde, ZkVmEnvSsz};
+use zkaleido::ZkVmEnvCodec;
 
-use crate::logic::verify_schnorr_sig_k256;
+use crate::{input::SchnorrSigInput, logic::verify_schnorr_sig_k256};
 
-pub fn process_schnorr_sig_verify(zkvm: &impl ZkVmEnvSerde) {
-    let sig: Vec<u8> = zkvm.read_ssz();
-    let msg: [u8; 32] = zkvm.read_ssz();
-    let pk: [u8; 32] = zkvm.read_ssz();
+pub fn process_schnorr_sig_verify(zkvm: &impl ZkVmEnvCodec) {
+    let input = SchnorrSigInput::reader(zkvm);
+    let sig = input.sig();
+    let msg = input.msg();
+    let pk = input.pk();
 
-    let result = verify_schnorr_sig_k256(&sig.try_into().unwrap(), &msg, &pk);
+    let result = verify_schnorr_sig_k256(&sig, &msg, &pk);
 
-    zkvm.commit_ssz(&result);
+    SchnorrSigInput::commit_output(zkvm, &result);
 }
@@ -46,12 +46,19 @@ pub fn verify_schnorr_sig_k256(sig: &[u8; 64], msg: &[u8; 32], pk: &[u8; 32]) ->
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::OsRng;
+
     use super::*;
     use crate::input::SchnorrSigInput;
 
+    fn random_sk() -> [u8; 32] {
+        *SecretKey::new(&mut OsRng).as_ref()
+    }
+
     #[test]
     fn test_schnorr_signature_pass() {
-        let SchnorrSigInput { sk, pk, msg, sig } = SchnorrSigInput::new_random();
+        let sk = random_sk();
+        let SchnorrSigInput { sig, msg, pk } = SchnorrSigInput::sign_random(&sk);
 
         let mut mod_msg = msg;
         mod_msg.swap(1, 2);
@@ -66,7 +73,8 @@ mod tests {
 
     #[test]
     fn test_schnorr_k256_signature_pass() {
-        let SchnorrSigInput { sk, pk, msg, sig } = SchnorrSigInput::new_random();
+        let sk = random_sk();
+        let SchnorrSigInput { sig, msg, pk } = SchnorrSigInput::sign_random(&sk);
 
         let mut mod_msg = msg;
         mod_msg.swap(1, 2);
@@ -1,45 +1,12 @@
-use zkaleido::{ProofType, ZkVmInputResult, ZkVmProgram};
-
 use crate::input::SchnorrSigInput;
 
-pub struct SchnorrSigProgram;
-
-impl ZkVmProgram for SchnorrSigProgram {
-    type Input = SchnorrSigInput;
-    type Output = bool;
-
-    fn name() -> String {
-        "schnorr_sig_verify".to_string()
-    }
-
-    fn proof_type() -> ProofType {
-        ProofType::Core
-    }
-
-    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
-    where
-        B: zkaleido::ZkVmInputBuilder<'a>,
-    {
-        B::new()
-            .write_ssz(&input.sig)?
-            .write_ssz(&input.msg)?
-            .write_ssz(&input.pk)?
-            .build()
-    }
-
-    fn process_output<H>(
-        public_values: &zkaleido::PublicValues,
-    ) -> zkaleido::ZkVmResult<Self::Output>
-    where
-        H: zkaleido::ZkVmHost,
-    {
-        H::extract_ssz_public_output(public_values)
-    }
-}
+/// The Schnorr signature verification program, whose `ZkVmProgram` implementation is derived on
+/// its input.
+pub type SchnorrSigProgram = SchnorrSigInput;
 
 #[cfg(test)]
 mod tests {
-    use zkaleido::ZkVmProgram;
+    use zkaleido::{RunConfig, ZkVmProgram};
     use zkaleido_native_adapter::NativeHost;
 
     use super::*;
@@ -53,10 +20,31 @@ mod tests {
     fn test_native() {
         let input = SchnorrSigInput::new_random();
         let host = get_native_host();
-        let receipt = SchnorrSigProgram::prove(&input, &host).unwrap();
+        let receipt = SchnorrSigProgram::prove(&input, &host, &RunConfig::default()).unwrap();
         let output =
             SchnorrSigProgram::process_output::<NativeHost>(receipt.receipt().public_values())
                 .unwrap();
         assert!(output);
     }
+
+    /// The signature, message and key all travel through `SszCodec`, so a flipped bit in any of
+    /// them must reach the guest intact and fail verification.
+    #[test]
+    fn test_native_rejects_tampered_inputs() {
+        let host = get_native_host();
+        let tamperings: [fn(&mut SchnorrSigInput); 3] = [
+            |input| input.sig[0] ^= 1,
+            |input| input.msg[0] ^= 1,
+            |input| input.pk[31] ^= 1,
+        ];
+        for tamper in tamperings {
+            let mut input = SchnorrSigInput::new_random();
+            tamper(&mut input);
+            let receipt = SchnorrSigProgram::prove(&input, &host, &RunConfig::default()).unwrap();
+            let output =
+                SchnorrSigProgram::process_output::<NativeHost>(receipt.receipt().public_values())
+                    .unwrap();
+            assert!(!output);
+        }
+    }
 }
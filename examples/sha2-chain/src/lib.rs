@@ -1,14 +1,16 @@
 use sha2::{Digest, Sha256};
-use zkaleido::ZkVmEnvBorsh;
+use zkaleido::{ZkVmCodecProgram, ZkVmEnvCodec};
+
+use crate::program::ShaChainProgram;
 
 const MESSAGE_TO_HASH: &str = "Hello, world!";
 pub mod program;
 
-pub fn process_sha2_chain(zkvm: &impl ZkVmEnvBorsh) {
-    let rounds: u32 = zkvm.read_borsh();
+pub fn process_sha2_chain(zkvm: &impl ZkVmEnvCodec) {
+    let rounds = ShaChainProgram::read_input(zkvm);
     let final_hash = hash_n_rounds(MESSAGE_TO_HASH, rounds);
 
-    zkvm.commit_borsh(&final_hash);
+    ShaChainProgram::commit_output(zkvm, &final_hash);
 }
 
 fn hash_n_rounds(message: &str, rounds: u32) -> [u8; 32] {
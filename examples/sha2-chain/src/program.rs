@@ -1,39 +1,12 @@
-use zkaleido::{ProofType, ZkVmInputResult, ZkVmProgram};
+use zkaleido::{BorshCodec, ZkVmProgram};
 
+#[derive(ZkVmProgram)]
+#[zkvm(name = "sha2_chain", proof_type = Core, codec = BorshCodec, input = u32, output = [u8; 32])]
 pub struct ShaChainProgram;
 
-impl ZkVmProgram for ShaChainProgram {
-    type Input = u32;
-    type Output = [u8; 32];
-
-    fn name() -> String {
-        "sha2_chain".to_string()
-    }
-
-    fn proof_type() -> zkaleido::ProofType {
-        ProofType::Core
-    }
-
-    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
-    where
-        B: zkaleido::ZkVmInputBuilder<'a>,
-    {
-        B::new().write_borsh(input)?.build()
-    }
-
-    fn process_output<H>(
-        public_values: &zkaleido::PublicValues,
-    ) -> zkaleido::ZkVmResult<Self::Output>
-    where
-        H: zkaleido::ZkVmHost,
-    {
-        H::extract_borsh_public_output(public_values)
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use zkaleido::{ZkVmProgram, ZkVmTypedVerifier};
+    use zkaleido::{RunConfig, ZkVmProgram, ZkVmTypedVerifier};
     use zkaleido_native_adapter::NativeHost;
 
     use crate::{process_sha2_chain, program::ShaChainProgram};
@@ -46,7 +19,7 @@ mod tests {
     fn test_native() {
         let input = 5;
         let host = get_native_host();
-        let receipt = ShaChainProgram::prove(&input, &host).unwrap();
+        let receipt = ShaChainProgram::prove(&input, &host, &RunConfig::default()).unwrap();
         let public_params =
             ShaChainProgram::process_output::<NativeHost>(receipt.receipt().public_values())
                 .unwrap();
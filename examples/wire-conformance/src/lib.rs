@@ -0,0 +1,34 @@
+use zkaleido::{ZkVmEnvCodec, conformance_guest};
+
+/// Runs the guest of the wire-format [`ConformanceSuite`](zkaleido::ConformanceSuite).
+pub fn process_wire_conformance(zkvm: &impl ZkVmEnvCodec) {
+    conformance_guest(zkvm)
+}
+
+#[cfg(test)]
+mod tests {
+    use zkaleido::{
+        AggregationInput, ConformanceSuite, ProofType, RunConfig, ZkVmEnv, ZkVmExecutor,
+        ZkVmProver, ZkVmVkProvider,
+    };
+    use zkaleido_native_adapter::{NativeHost, NativeMachine};
+
+    use crate::process_wire_conformance;
+
+    #[test]
+    fn test_native() {
+        let inner = NativeHost::new_with_random_key(|zkvm: &NativeMachine| {
+            zkvm.commit_buf(&[4, 5, 6]);
+        });
+        let receipt = inner
+            .prove(NativeMachine::new(), ProofType::Core, &RunConfig::default())
+            .unwrap();
+        let vk_digest = inner.program_id().to_vk_digest();
+        let suite = ConformanceSuite::new()
+            .with_proof(AggregationInput::new(receipt, inner.vk()), vk_digest);
+
+        let host = NativeHost::new_with_random_key(process_wire_conformance);
+        let report = suite.run(&host).unwrap();
+        assert!(report.is_conformant(), "{report}");
+    }
+}
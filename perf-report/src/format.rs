@@ -1,4 +1,5 @@
 use num_format::{Locale, ToFormattedString};
+use zkaleido::ZkVm;
 
 use crate::{
     diff::format_delta,
@@ -22,11 +23,23 @@ pub fn render_report(results: &[ZkVmResults], baseline: Option<&ReportPayload>)
 
 /// Returns formatted results for one zkVM as a table, with delta columns
 /// against `baseline` when one is given.
+///
+/// Programs that report [`ExecutionStats`](zkaleido::ExecutionStats) get a
+/// second table with their I/O and wall-clock time. The native zkVM has no
+/// cycles, so it only gets that second table.
 pub fn format_results(results: &ZkVmResults, baseline: Option<&ZkVmPayload>) -> String {
-    let table_text = match baseline {
-        Some(baseline) => format_table_with_deltas(results, baseline),
-        None => format_table(results),
+    let mut table_text = match (results.zkvm, baseline) {
+        (ZkVm::Native, _) => String::new(),
+        (_, Some(baseline)) => format_table_with_deltas(results, baseline),
+        (_, None) => format_table(results),
     };
+    if results
+        .results
+        .iter()
+        .any(|program| program.summary.stats().is_some())
+    {
+        table_text.push_str(&format_stats_table(results));
+    }
     format!("**{} Execution Results**\n {table_text}", results.zkvm)
 }
 
@@ -79,6 +92,36 @@ fn format_table_with_deltas(results: &ZkVmResults, baseline: &ZkVmPayload) -> St
     table_text
 }
 
+/// Builds the I/O and wall-clock time table of the programs that report
+/// [`ExecutionStats`](zkaleido::ExecutionStats).
+fn format_stats_table(results: &ZkVmResults) -> String {
+    let mut table_text = String::new();
+    table_text.push('\n');
+    table_text.push_str(
+        "| program                | reads | input bytes | commits | output bytes | wall time   |\n",
+    );
+    table_text.push_str(
+        "|------------------------|-------|-------------|---------|--------------|-------------|",
+    );
+
+    for program in &results.results {
+        let Some(stats) = program.summary.stats() else {
+            continue;
+        };
+        table_text.push_str(&format!(
+            "\n| {:<22} | {:>5} | {:>11} | {:>7} | {:>12} | {:>11} |",
+            program.name,
+            stats.reads.to_formatted_string(&Locale::en),
+            stats.input_bytes.to_formatted_string(&Locale::en),
+            stats.commits.to_formatted_string(&Locale::en),
+            stats.output_bytes.to_formatted_string(&Locale::en),
+            format!("{:.3} ms", stats.wall_time_nanos as f64 / 1_000_000.0),
+        ));
+    }
+    table_text.push('\n');
+    table_text
+}
+
 /// Formats an optional gas amount, `-` when the program reports none.
 fn format_gas(gas: Option<u64>) -> String {
     gas.map(|gas| gas.to_formatted_string(&Locale::en))
@@ -106,7 +149,7 @@ fn format_gas_delta(gas: Option<u64>, baseline: Option<&ProgramPayload>) -> Stri
 
 #[cfg(test)]
 mod tests {
-    use zkaleido::{ExecutionSummary, PublicValues, ZkVm};
+    use zkaleido::{ExecutionStats, ExecutionSummary, PublicValues};
 
     use super::*;
 
@@ -176,6 +219,33 @@ mod tests {
         assert!(report.contains("new"));
     }
 
+    #[test]
+    fn renders_native_stats() {
+        let stats = ExecutionStats {
+            reads: 2,
+            input_bytes: 1_024,
+            commits: 1,
+            output_bytes: 4,
+            wall_time_nanos: 1_500_000,
+            regions: Vec::new(),
+        };
+        let results = ZkVmResults::new(
+            ZkVm::Native,
+            vec![(
+                "fibonacci".to_string(),
+                ExecutionSummary::new(PublicValues::default(), 0, None).with_stats(stats),
+            )],
+        );
+        let report = render_report(&[results], Some(&sample_baseline()));
+
+        assert!(report.contains("**Native Execution Results**"));
+        assert!(!report.contains("| cycles"));
+        assert!(report.contains("| input bytes |"));
+        assert!(report.contains(
+            "| fibonacci              |     2 |       1,024 |       1 |            4 |    1.500 ms |"
+        ));
+    }
+
     #[test]
     fn ignores_baseline_of_other_zkvm() {
         let mut baseline = sample_baseline();
@@ -3,7 +3,7 @@ pub mod programs;
 
 use args::EvalArgs;
 use clap::Parser;
-use zkaleido::ZkVm;
+use zkaleido::{RunConfig, ZkVm};
 use zkaleido_perf_report::{ZkVmResults, render_report};
 
 const COMMENT_MARKER: &str = "zkaleido-perf-report";
@@ -38,20 +38,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         None => None,
     };
 
+    // Keep honoring the ZKVM_PROOF_DUMP, ZKVM_PROFILING_DUMP and ZKVM_MOCK variables.
+    let config = RunConfig::from_env();
+
     let mut results: Vec<ZkVmResults> = Vec::new();
 
     #[cfg(feature = "sp1")]
     {
-        let sp1_reports = programs::run_sp1_programs(&args.programs).await;
+        // SP1 only profiles through `TRACE_FILE` and rejects a profiling directory.
+        let sp1_config = RunConfig {
+            profiling_output_dir: None,
+            ..config.clone()
+        };
+        let sp1_reports = programs::run_sp1_programs(&args.programs, &sp1_config).await;
         results.push(ZkVmResults::new(ZkVm::SP1, sp1_reports));
     }
 
     #[cfg(feature = "risc0")]
     {
-        let risc0_reports = programs::run_risc0_programs(&args.programs).await;
+        let risc0_reports = programs::run_risc0_programs(&args.programs, &config).await;
         results.push(ZkVmResults::new(ZkVm::Risc0, risc0_reports));
     }
 
+    #[cfg(feature = "native")]
+    {
+        let native_reports = programs::run_native_programs(&args.programs, &config).await;
+        results.push(ZkVmResults::new(ZkVm::Native, native_reports));
+    }
+
     // A missing baseline only degrades the report to absolute numbers, so
     // fetch failures must not block posting it, but the reported header
     // should still say the lookup failed rather than implying a clean miss.
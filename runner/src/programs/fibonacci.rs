@@ -1,24 +1,70 @@
 use fibonacci::program::FibProgram;
-use zkaleido::{ExecutionSummary, ZkVmHost, ZkVmProgram};
+use zkaleido::{ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram};
 
-fn fib_execution_report(host: &impl ZkVmHost) -> (String, ExecutionSummary) {
+fn fib_execution_report(host: &impl ZkVmHost, config: &RunConfig) -> (String, ExecutionSummary) {
     let input = 5;
-    let summary = FibProgram::execute(&input, host).unwrap();
+    let summary = FibProgram::execute(&input, host, config).unwrap();
     (FibProgram::name(), summary)
 }
 
 #[cfg(feature = "sp1")]
-pub async fn sp1_fib_report() -> (String, ExecutionSummary) {
+pub async fn sp1_fib_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_sp1_artifacts::FIBONACCI_ELF;
     use zkaleido_sp1_host::SP1Host;
     let host = SP1Host::init(&FIBONACCI_ELF).await;
-    fib_execution_report(&host)
+    fib_execution_report(&host, config)
 }
 
 #[cfg(feature = "risc0")]
-pub async fn risc0_fib_report() -> (String, ExecutionSummary) {
+pub async fn risc0_fib_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
     use zkaleido_risc0_host::Risc0Host;
     let host = Risc0Host::init(GUEST_RISC0_FIBONACCI_ELF);
-    fib_execution_report(&host)
+    fib_execution_report(&host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_fib_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use fibonacci::process_fibonacci;
+    use zkaleido_native_adapter::NativeHost;
+    let host = NativeHost::new_with_random_key(process_fibonacci);
+    fib_execution_report(&host, config)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sp1")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sp1_guest_elf_passes_validation() {
+        use zkaleido_sp1_artifacts::FIBONACCI_ELF;
+        use zkaleido_sp1_host::SP1Host;
+        SP1Host::try_init(&FIBONACCI_ELF).await.unwrap();
+    }
+
+    #[cfg(feature = "risc0")]
+    #[test]
+    fn risc0_guest_elf_passes_validation() {
+        use zkaleido_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
+        use zkaleido_risc0_host::Risc0Host;
+        Risc0Host::try_init(GUEST_RISC0_FIBONACCI_ELF).unwrap();
+    }
+
+    #[cfg(all(feature = "sp1", feature = "risc0"))]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn guest_elfs_of_other_zkvms_are_rejected() {
+        use zkaleido::ZkVmError;
+        use zkaleido_risc0_artifacts::GUEST_RISC0_FIBONACCI_ELF;
+        use zkaleido_risc0_host::Risc0Host;
+        use zkaleido_sp1_artifacts::FIBONACCI_ELF;
+        use zkaleido_sp1_host::SP1Host;
+
+        assert!(matches!(
+            SP1Host::try_init(GUEST_RISC0_FIBONACCI_ELF).await,
+            Err(ZkVmError::InvalidELF(_))
+        ));
+        assert!(matches!(
+            Risc0Host::try_init(&FIBONACCI_ELF),
+            Err(ZkVmError::InvalidELF(_))
+        ));
+    }
 }
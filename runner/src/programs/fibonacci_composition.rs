@@ -1,40 +1,51 @@
 use fibonacci::program::FibProgram;
-use fibonacci_composition::program::{FibCompositionInput, FibCompositionProgram};
-use zkaleido::{AggregationInput, ExecutionSummary, ZkVmHost, ZkVmProgram};
+use fibonacci_composition::program::FibCompositionProgram;
+use zkaleido::{AggregationInput, ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram};
 
 fn fib_composition_execution_report(
     fib_host: &impl ZkVmHost,
     fib_composition_host: &impl ZkVmHost,
+    config: &RunConfig,
 ) -> (String, ExecutionSummary) {
     let input = 5;
-    let receipt = FibProgram::prove(&input, fib_host).unwrap();
+    let receipt = FibProgram::prove(&input, fib_host, config).unwrap();
     let vk = fib_host.vk();
     let fib_proof_with_vk = AggregationInput::new(receipt, vk);
     let fib_program_id = fib_host.program_id();
-    let input = FibCompositionInput {
+    let input = FibCompositionProgram {
         fib_proof_with_vk,
         fib_program_id,
     };
-    let summary = FibCompositionProgram::execute(&input, fib_composition_host).unwrap();
+    let summary = FibCompositionProgram::execute(&input, fib_composition_host, config).unwrap();
     (FibCompositionProgram::name(), summary)
 }
 
 #[cfg(feature = "sp1")]
-pub async fn sp1_fib_composition_report() -> (String, ExecutionSummary) {
+pub async fn sp1_fib_composition_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_sp1_artifacts::{FIBONACCI_COMPOSITION_ELF, FIBONACCI_ELF};
     use zkaleido_sp1_host::SP1Host;
     let fib_host = SP1Host::init(&FIBONACCI_ELF).await;
     let fib_composition_host = SP1Host::init(&FIBONACCI_COMPOSITION_ELF).await;
-    fib_composition_execution_report(&fib_host, &fib_composition_host)
+    fib_composition_execution_report(&fib_host, &fib_composition_host, config)
 }
 
 #[cfg(feature = "risc0")]
-pub async fn risc0_fib_composition_report() -> (String, ExecutionSummary) {
+pub async fn risc0_fib_composition_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_risc0_artifacts::{
         GUEST_RISC0_FIBONACCI_COMPOSITION_ELF, GUEST_RISC0_FIBONACCI_ELF,
     };
     use zkaleido_risc0_host::Risc0Host;
     let fib_host = Risc0Host::init(GUEST_RISC0_FIBONACCI_ELF);
     let fib_composition_host = Risc0Host::init(GUEST_RISC0_FIBONACCI_COMPOSITION_ELF);
-    fib_composition_execution_report(&fib_host, &fib_composition_host)
+    fib_composition_execution_report(&fib_host, &fib_composition_host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_fib_composition_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use fibonacci::process_fibonacci;
+    use fibonacci_composition::process_fibonacci_composition;
+    use zkaleido_native_adapter::NativeHost;
+    let fib_host = NativeHost::new_with_random_key(process_fibonacci);
+    let fib_composition_host = NativeHost::new_with_random_key(process_fibonacci_composition);
+    fib_composition_execution_report(&fib_host, &fib_composition_host, config)
 }
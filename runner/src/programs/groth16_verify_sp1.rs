@@ -1,24 +1,32 @@
 use groth16_verify_sp1::{input::SP1Groth16VerifyInput, program::SP1Groth16VerifyProgram};
-use zkaleido::{ExecutionSummary, ZkVmHost, ZkVmProgram};
+use zkaleido::{ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram};
 
-fn execution_report(host: &impl ZkVmHost) -> (String, ExecutionSummary) {
+fn execution_report(host: &impl ZkVmHost, config: &RunConfig) -> (String, ExecutionSummary) {
     let input = SP1Groth16VerifyInput::load();
-    let summary = SP1Groth16VerifyProgram::execute(&input, host).unwrap();
+    let summary = SP1Groth16VerifyProgram::execute(&input, host, config).unwrap();
     (SP1Groth16VerifyProgram::name(), summary)
 }
 
 #[cfg(feature = "sp1")]
-pub async fn sp1_groth16_verify() -> (String, ExecutionSummary) {
+pub async fn sp1_groth16_verify(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_sp1_artifacts::GROTH16_VERIFY_SP1_ELF;
     use zkaleido_sp1_host::SP1Host;
     let host = SP1Host::init(&GROTH16_VERIFY_SP1_ELF).await;
-    execution_report(&host)
+    execution_report(&host, config)
 }
 
 #[cfg(feature = "risc0")]
-pub async fn risc0_groth16_verify() -> (String, ExecutionSummary) {
+pub async fn risc0_groth16_verify(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_risc0_artifacts::GUEST_RISC0_GROTH16_VERIFY_SP1_ELF;
     use zkaleido_risc0_host::Risc0Host;
     let host = Risc0Host::init(GUEST_RISC0_GROTH16_VERIFY_SP1_ELF);
-    execution_report(&host)
+    execution_report(&host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_groth16_verify(config: &RunConfig) -> (String, ExecutionSummary) {
+    use groth16_verify_sp1::process_groth16_verify_sp1;
+    use zkaleido_native_adapter::NativeHost;
+    let host = NativeHost::new_with_random_key(process_groth16_verify_sp1);
+    execution_report(&host, config)
 }
@@ -1,13 +1,14 @@
 use std::str::FromStr;
 
 use clap::ValueEnum;
-use zkaleido::ExecutionSummary;
+use zkaleido::{ExecutionSummary, RunConfig};
 
 mod fibonacci;
 mod fibonacci_composition;
 mod groth16_verify_sp1;
 mod schnorr;
 mod sha2;
+mod wire_conformance;
 
 #[derive(Debug, Clone, ValueEnum)]
 #[non_exhaustive]
@@ -17,6 +18,7 @@ pub enum GuestProgram {
     Sha2Chain,
     SchnorrSigVerify,
     Groth16VerifySP1,
+    WireConformance,
 }
 
 impl FromStr for GuestProgram {
@@ -29,6 +31,7 @@ impl FromStr for GuestProgram {
             "sha2-chain" => Ok(GuestProgram::Sha2Chain),
             "schnorr-sig-verify" => Ok(GuestProgram::SchnorrSigVerify),
             "groth16-verify-sp1" => Ok(GuestProgram::Groth16VerifySP1),
+            "wire-conformance" => Ok(GuestProgram::WireConformance),
             // Add more matches
             _ => Err(format!("unknown program: {}", s)),
         }
@@ -39,17 +42,23 @@ impl FromStr for GuestProgram {
 ///
 /// Pairs each program's [`ZkVmProgram::name`] with its [`ExecutionSummary`].
 #[cfg(feature = "sp1")]
-pub async fn run_sp1_programs(programs: &[GuestProgram]) -> Vec<(String, ExecutionSummary)> {
+pub async fn run_sp1_programs(
+    programs: &[GuestProgram],
+    config: &RunConfig,
+) -> Vec<(String, ExecutionSummary)> {
     let mut reports = Vec::with_capacity(programs.len());
     for program in programs {
         let report = match program {
-            GuestProgram::Fibonacci => fibonacci::sp1_fib_report().await,
+            GuestProgram::Fibonacci => fibonacci::sp1_fib_report(config).await,
             GuestProgram::FibonacciComposition => {
-                fibonacci_composition::sp1_fib_composition_report().await
+                fibonacci_composition::sp1_fib_composition_report(config).await
+            }
+            GuestProgram::Sha2Chain => sha2::sp1_sha_report(config).await,
+            GuestProgram::SchnorrSigVerify => schnorr::sp1_schnorr_sig_verify_report(config).await,
+            GuestProgram::Groth16VerifySP1 => groth16_verify_sp1::sp1_groth16_verify(config).await,
+            GuestProgram::WireConformance => {
+                wire_conformance::sp1_wire_conformance_report(config).await
             }
-            GuestProgram::Sha2Chain => sha2::sp1_sha_report().await,
-            GuestProgram::SchnorrSigVerify => schnorr::sp1_schnorr_sig_verify_report().await,
-            GuestProgram::Groth16VerifySP1 => groth16_verify_sp1::sp1_groth16_verify().await,
         };
         reports.push(report);
     }
@@ -60,17 +69,59 @@ pub async fn run_sp1_programs(programs: &[GuestProgram]) -> Vec<(String, Executi
 ///
 /// Pairs each program's [`ZkVmProgram::name`] with its [`ExecutionSummary`].
 #[cfg(feature = "risc0")]
-pub async fn run_risc0_programs(programs: &[GuestProgram]) -> Vec<(String, ExecutionSummary)> {
+pub async fn run_risc0_programs(
+    programs: &[GuestProgram],
+    config: &RunConfig,
+) -> Vec<(String, ExecutionSummary)> {
     let mut reports = Vec::with_capacity(programs.len());
     for program in programs {
         let report = match program {
-            GuestProgram::Fibonacci => fibonacci::risc0_fib_report().await,
+            GuestProgram::Fibonacci => fibonacci::risc0_fib_report(config).await,
             GuestProgram::FibonacciComposition => {
-                fibonacci_composition::risc0_fib_composition_report().await
+                fibonacci_composition::risc0_fib_composition_report(config).await
+            }
+            GuestProgram::Sha2Chain => sha2::risc0_sha_report(config).await,
+            GuestProgram::SchnorrSigVerify => {
+                schnorr::risc0_schnorr_sig_verify_report(config).await
+            }
+            GuestProgram::Groth16VerifySP1 => {
+                groth16_verify_sp1::risc0_groth16_verify(config).await
+            }
+            GuestProgram::WireConformance => {
+                wire_conformance::risc0_wire_conformance_report(config).await
+            }
+        };
+        reports.push(report);
+    }
+    reports
+}
+
+/// Runs the programs natively to generate reports.
+///
+/// Pairs each program's [`ZkVmProgram::name`] with its [`ExecutionSummary`], whose
+/// [`ExecutionStats`](zkaleido::ExecutionStats) stand in for cycles.
+#[cfg(feature = "native")]
+pub async fn run_native_programs(
+    programs: &[GuestProgram],
+    config: &RunConfig,
+) -> Vec<(String, ExecutionSummary)> {
+    let mut reports = Vec::with_capacity(programs.len());
+    for program in programs {
+        let report = match program {
+            GuestProgram::Fibonacci => fibonacci::native_fib_report(config).await,
+            GuestProgram::FibonacciComposition => {
+                fibonacci_composition::native_fib_composition_report(config).await
+            }
+            GuestProgram::Sha2Chain => sha2::native_sha_report(config).await,
+            GuestProgram::SchnorrSigVerify => {
+                schnorr::native_schnorr_sig_verify_report(config).await
+            }
+            GuestProgram::Groth16VerifySP1 => {
+                groth16_verify_sp1::native_groth16_verify(config).await
+            }
+            GuestProgram::WireConformance => {
+                wire_conformance::native_wire_conformance_report(config).await
             }
-            GuestProgram::Sha2Chain => sha2::risc0_sha_report().await,
-            GuestProgram::SchnorrSigVerify => schnorr::risc0_schnorr_sig_verify_report().await,
-            GuestProgram::Groth16VerifySP1 => groth16_verify_sp1::risc0_groth16_verify().await,
         };
         reports.push(report);
     }
@@ -1,24 +1,32 @@
 use schnorr_sig_verify::{input::SchnorrSigInput, program::SchnorrSigProgram};
-use zkaleido::{ExecutionSummary, ZkVmHost, ZkVmProgram};
+use zkaleido::{ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram};
 
-fn execution_report(host: &impl ZkVmHost) -> (String, ExecutionSummary) {
+fn execution_report(host: &impl ZkVmHost, config: &RunConfig) -> (String, ExecutionSummary) {
     let input = SchnorrSigInput::new_random();
-    let summary = SchnorrSigProgram::execute(&input, host).unwrap();
+    let summary = SchnorrSigProgram::execute(&input, host, config).unwrap();
     (SchnorrSigProgram::name(), summary)
 }
 
 #[cfg(feature = "sp1")]
-pub async fn sp1_schnorr_sig_verify_report() -> (String, ExecutionSummary) {
+pub async fn sp1_schnorr_sig_verify_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_sp1_artifacts::SCHNORR_SIG_VERIFY_ELF;
     use zkaleido_sp1_host::SP1Host;
     let host = SP1Host::init(&SCHNORR_SIG_VERIFY_ELF).await;
-    execution_report(&host)
+    execution_report(&host, config)
 }
 
 #[cfg(feature = "risc0")]
-pub async fn risc0_schnorr_sig_verify_report() -> (String, ExecutionSummary) {
+pub async fn risc0_schnorr_sig_verify_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_risc0_artifacts::GUEST_RISC0_SCHNORR_SIG_VERIFY_ELF;
     use zkaleido_risc0_host::Risc0Host;
     let host = Risc0Host::init(GUEST_RISC0_SCHNORR_SIG_VERIFY_ELF);
-    execution_report(&host)
+    execution_report(&host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_schnorr_sig_verify_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use schnorr_sig_verify::process_schnorr_sig_verify;
+    use zkaleido_native_adapter::NativeHost;
+    let host = NativeHost::new_with_random_key(process_schnorr_sig_verify);
+    execution_report(&host, config)
 }
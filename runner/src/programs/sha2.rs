@@ -1,24 +1,32 @@
 use sha2_chain::program::ShaChainProgram;
-use zkaleido::{ExecutionSummary, ZkVmHost, ZkVmProgram};
+use zkaleido::{ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram};
 
-fn sha2_execution_report(host: &impl ZkVmHost) -> (String, ExecutionSummary) {
+fn sha2_execution_report(host: &impl ZkVmHost, config: &RunConfig) -> (String, ExecutionSummary) {
     let input = 5;
-    let summary = ShaChainProgram::execute(&input, host).unwrap();
+    let summary = ShaChainProgram::execute(&input, host, config).unwrap();
     (ShaChainProgram::name(), summary)
 }
 
 #[cfg(feature = "sp1")]
-pub async fn sp1_sha_report() -> (String, ExecutionSummary) {
+pub async fn sp1_sha_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_sp1_artifacts::SHA2_CHAIN_ELF;
     use zkaleido_sp1_host::SP1Host;
     let host = SP1Host::init(&SHA2_CHAIN_ELF).await;
-    sha2_execution_report(&host)
+    sha2_execution_report(&host, config)
 }
 
 #[cfg(feature = "risc0")]
-pub async fn risc0_sha_report() -> (String, ExecutionSummary) {
+pub async fn risc0_sha_report(config: &RunConfig) -> (String, ExecutionSummary) {
     use zkaleido_risc0_artifacts::GUEST_RISC0_SHA2_CHAIN_ELF;
     use zkaleido_risc0_host::Risc0Host;
     let host = Risc0Host::init(GUEST_RISC0_SHA2_CHAIN_ELF);
-    sha2_execution_report(&host)
+    sha2_execution_report(&host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_sha_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use sha2_chain::process_sha2_chain;
+    use zkaleido_native_adapter::NativeHost;
+    let host = NativeHost::new_with_random_key(process_sha2_chain);
+    sha2_execution_report(&host, config)
 }
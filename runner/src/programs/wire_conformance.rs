@@ -0,0 +1,51 @@
+use fibonacci::program::FibProgram;
+use zkaleido::{
+    AggregationInput, ConformanceSuite, ExecutionSummary, RunConfig, ZkVmHost, ZkVmProgram,
+};
+
+/// Runs the wire-format conformance suite, with a fibonacci proof in the proof frame, and
+/// panics if any frame does not round-trip.
+fn conformance_execution_report<H: ZkVmHost>(
+    fib_host: &impl ZkVmHost,
+    conformance_host: &H,
+    config: &RunConfig,
+) -> (String, ExecutionSummary) {
+    let receipt = FibProgram::prove(&5, fib_host, config).unwrap();
+    let fib_vk = fib_host.program_id().to_vk_digest();
+    let suite =
+        ConformanceSuite::new().with_proof(AggregationInput::new(receipt, fib_host.vk()), fib_vk);
+
+    let input = suite.build_input::<H::Input<'_>>().unwrap();
+    let summary = conformance_host.execute(input, config).unwrap();
+    let report = suite.check(summary.public_values()).unwrap();
+    assert!(report.is_conformant(), "{report}");
+    ("wire conformance".to_string(), summary)
+}
+
+#[cfg(feature = "sp1")]
+pub async fn sp1_wire_conformance_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use zkaleido_sp1_artifacts::{FIBONACCI_ELF, WIRE_CONFORMANCE_ELF};
+    use zkaleido_sp1_host::SP1Host;
+    let fib_host = SP1Host::init(&FIBONACCI_ELF).await;
+    let conformance_host = SP1Host::init(&WIRE_CONFORMANCE_ELF).await;
+    conformance_execution_report(&fib_host, &conformance_host, config)
+}
+
+#[cfg(feature = "risc0")]
+pub async fn risc0_wire_conformance_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use zkaleido_risc0_artifacts::{GUEST_RISC0_FIBONACCI_ELF, GUEST_RISC0_WIRE_CONFORMANCE_ELF};
+    use zkaleido_risc0_host::Risc0Host;
+    let fib_host = Risc0Host::init(GUEST_RISC0_FIBONACCI_ELF);
+    let conformance_host = Risc0Host::init(GUEST_RISC0_WIRE_CONFORMANCE_ELF);
+    conformance_execution_report(&fib_host, &conformance_host, config)
+}
+
+#[cfg(feature = "native")]
+pub async fn native_wire_conformance_report(config: &RunConfig) -> (String, ExecutionSummary) {
+    use fibonacci::process_fibonacci;
+    use wire_conformance::process_wire_conformance;
+    use zkaleido_native_adapter::NativeHost;
+    let fib_host = NativeHost::new_with_random_key(process_fibonacci);
+    let conformance_host = NativeHost::new_with_random_key(process_wire_conformance);
+    conformance_execution_report(&fib_host, &conformance_host, config)
+}
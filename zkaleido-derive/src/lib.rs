@@ -0,0 +1,540 @@
+//! Derive macros for zkaleido.
+//!
+//! This crate provides `#[derive(ZkVmProgram)]`, which generates the
+//! [`ZkVmProgram`](https://docs.rs/zkaleido/latest/zkaleido/trait.ZkVmProgram.html) plumbing
+//! for a program from a handful of attributes. It is re-exported by `zkaleido` behind the
+//! `derive` feature and is not meant to be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Error, Fields, Ident, LitStr, Result, Type, parse_macro_input,
+    spanned::Spanned,
+};
+
+/// Derives `zkaleido::ZkVmProgram`.
+///
+/// The program is described by a `#[zkvm(...)]` attribute on the type:
+///
+/// - `name = "..."`: the program name. Defaults to the type name.
+/// - `proof_type = Core | Compressed | Groth16 | Plonk`: the proof type to generate.
+/// - `codec = Path`: the `ZkVmCodec` used for the output, and for the input unless overridden.
+/// - `output = Type`: the program output.
+/// - `input = Type` (optional): the program input, encoded as a whole with the codec.
+///
+/// With `input = Type`, the derive implements `ZkVmCodecProgram`, which provides `ZkVmProgram`
+/// as well as the guest-side `read_input` and `commit_output`.
+///
+/// Without it, the type itself is the input and must be a struct with named fields. The fields
+/// are written in declaration order, each with the program codec unless the field carries its
+/// own attribute:
+///
+/// - `#[zkvm(codec = Path)]`: write the field with a different codec.
+/// - `#[zkvm(proof)]`: the field is an `AggregationInput` written with `write_proof`. The optional
+///   `codec` names the codec the proven program committed its output with.
+///
+/// For such structs the derive also generates `commit_output`, and a `reader` returning a
+/// `<Type>Reader` with one method per field that reads it back inside the guest. Proof fields
+/// take the verification key digest and return the verified public output. Each method has a
+/// `try_` counterpart returning a `ZkVmEnvResult` instead of aborting the guest. The reader
+/// methods must be called in declaration order.
+///
+/// ```ignore
+/// #[derive(ZkVmProgram)]
+/// #[zkvm(name = "fibonacci", proof_type = Groth16, codec = RawCodec, input = u32, output = u32)]
+/// pub struct FibProgram;
+///
+/// #[derive(ZkVmProgram)]
+/// #[zkvm(proof_type = Compressed, codec = SerdeCodec, output = u32)]
+/// pub struct FibCompositionProgram {
+///     pub fib_program_id: ProgramId,
+///     #[zkvm(proof, codec = RawCodec)]
+///     pub fib_proof_with_vk: AggregationInput,
+/// }
+/// ```
+#[proc_macro_derive(ZkVmProgram, attributes(zkvm))]
+pub fn derive_zkvm_program(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Program-level `#[zkvm(...)]` attribute.
+#[derive(Default)]
+struct ProgramAttrs {
+    name: Option<LitStr>,
+    proof_type: Option<Ident>,
+    codec: Option<Type>,
+    input: Option<Type>,
+    output: Option<Type>,
+}
+
+/// Field-level `#[zkvm(...)]` attribute.
+#[derive(Default)]
+struct FieldAttrs {
+    proof: bool,
+    codec: Option<Type>,
+}
+
+/// An input field of a struct program.
+struct InputField {
+    ident: Ident,
+    ty: Type,
+    codec: Type,
+    proof: bool,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let attrs = parse_program_attrs(&input)?;
+    let ident = &input.ident;
+
+    let name = attrs
+        .name
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let proof_type = attrs
+        .proof_type
+        .ok_or_else(|| missing(&input, "proof_type"))?;
+    if !["Core", "Compressed", "Groth16", "Plonk"].contains(&proof_type.to_string().as_str()) {
+        return Err(Error::new(
+            proof_type.span(),
+            "expected one of `Core`, `Compressed`, `Groth16` or `Plonk`",
+        ));
+    }
+    let codec = attrs.codec.ok_or_else(|| missing(&input, "codec"))?;
+    let output = attrs.output.ok_or_else(|| missing(&input, "output"))?;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`ZkVmProgram` cannot be derived for generic types",
+        ));
+    }
+
+    if let Some(program_input) = attrs.input {
+        return Ok(quote! {
+            impl ::zkaleido::ZkVmCodecProgram for #ident {
+                type Input = #program_input;
+                type Output = #output;
+                type Codec = #codec;
+
+                fn name() -> ::std::string::String {
+                    ::std::string::String::from(#name)
+                }
+
+                fn proof_type() -> ::zkaleido::ProofType {
+                    ::zkaleido::ProofType::#proof_type
+                }
+            }
+        });
+    }
+
+    let fields = input_fields(&input, &codec)?;
+    let vis = &input.vis;
+    let reader = format_ident!("{}Reader", ident);
+
+    let writes = fields.iter().map(|field| {
+        let InputField {
+            ident, ty, codec, ..
+        } = field;
+        if field.proof {
+            quote! { builder.write_proof(&input.#ident)?; }
+        } else {
+            quote! { builder.write_with::<#codec, #ty>(&input.#ident)?; }
+        }
+    });
+
+    let reads = fields.iter().enumerate().map(|(index, field)| {
+        let InputField {
+            ident, ty, codec, ..
+        } = field;
+        let field_name = ident.to_string();
+        let try_ident = format_ident!("try_{}", ident);
+        if field.proof {
+            let doc = format!(
+                "Reads `{field_name}` and verifies its proof against `vk_digest`, returning the \
+                 public output committed by the proven program."
+            );
+            let try_doc = format!("Fallible version of [`Self::{ident}`].");
+            quote! {
+                #[doc = #doc]
+                pub fn #ident<T>(&self, vk_digest: &[u32; 8]) -> T
+                where
+                    #codec: ::zkaleido::ZkVmCodec<T>,
+                {
+                    self.advance(#index, #field_name);
+                    <#codec as ::zkaleido::ZkVmCodec<T>>::read_verified(self.env, vk_digest)
+                }
+
+                #[doc = #try_doc]
+                pub fn #try_ident<T>(
+                    &self,
+                    vk_digest: &[u32; 8],
+                ) -> ::zkaleido::ZkVmEnvResult<T>
+                where
+                    #codec: ::zkaleido::ZkVmCodec<T>,
+                {
+                    self.advance(#index, #field_name);
+                    <#codec as ::zkaleido::ZkVmCodec<T>>::try_read_verified(self.env, vk_digest)
+                }
+            }
+        } else {
+            let doc = format!("Reads `{field_name}`.");
+            let try_doc = format!("Fallible version of [`Self::{ident}`].");
+            quote! {
+                #[doc = #doc]
+                pub fn #ident(&self) -> #ty {
+                    self.advance(#index, #field_name);
+                    <#codec as ::zkaleido::ZkVmCodec<#ty>>::read(self.env)
+                }
+
+                #[doc = #try_doc]
+                pub fn #try_ident(&self) -> ::zkaleido::ZkVmEnvResult<#ty> {
+                    self.advance(#index, #field_name);
+                    <#codec as ::zkaleido::ZkVmCodec<#ty>>::try_read(self.env)
+                }
+            }
+        }
+    });
+
+    let reader_doc = format!(
+        "Guest-side reader for the input of [`{ident}`], returned by [`{ident}::reader`].\n\n\
+         Fields must be read in declaration order."
+    );
+
+    Ok(quote! {
+        impl ::zkaleido::ZkVmProgram for #ident {
+            type Input = Self;
+            type Output = #output;
+
+            fn name() -> ::std::string::String {
+                ::std::string::String::from(#name)
+            }
+
+            fn proof_type() -> ::zkaleido::ProofType {
+                ::zkaleido::ProofType::#proof_type
+            }
+
+            fn prepare_input<'a, B>(
+                input: &'a Self::Input,
+            ) -> ::zkaleido::ZkVmInputResult<B::Input>
+            where
+                B: ::zkaleido::ZkVmInputBuilder<'a>,
+            {
+                let mut builder = B::new();
+                #(#writes)*
+                builder.build()
+            }
+
+            fn process_output<H>(
+                public_values: &::zkaleido::PublicValues,
+            ) -> ::zkaleido::ZkVmResult<Self::Output>
+            where
+                H: ::zkaleido::ZkVmHost,
+            {
+                <H as ::zkaleido::ZkVmOutputExtractor>::extract_public_output::<#codec, #output>(
+                    public_values,
+                )
+            }
+        }
+
+        impl #ident {
+            /// Returns a reader for the program input inside the guest.
+            #vis fn reader<E: ::zkaleido::ZkVmEnvCodec>(env: &E) -> #reader<'_, E> {
+                #reader {
+                    env,
+                    next: ::std::cell::Cell::new(0),
+                }
+            }
+
+            /// Commits the program output inside the guest.
+            #vis fn commit_output<E: ::zkaleido::ZkVmEnvCodec>(env: &E, output: &#output) {
+                <#codec as ::zkaleido::ZkVmCodec<#output>>::commit(env, output)
+            }
+        }
+
+        #[doc = #reader_doc]
+        #vis struct #reader<'e, E> {
+            env: &'e E,
+            next: ::std::cell::Cell<usize>,
+        }
+
+        impl<E> ::std::fmt::Debug for #reader<'_, E> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!(#reader))
+                    .field("next", &self.next.get())
+                    .finish_non_exhaustive()
+            }
+        }
+
+        impl<E: ::zkaleido::ZkVmEnvCodec> #reader<'_, E> {
+            #(#reads)*
+
+            fn advance(&self, index: usize, field: &str) {
+                assert_eq!(
+                    self.next.get(),
+                    index,
+                    "`{field}` read out of order: input fields must be read in declaration order"
+                );
+                self.next.set(index + 1);
+            }
+        }
+    })
+}
+
+fn parse_program_attrs(input: &DeriveInput) -> Result<ProgramAttrs> {
+    let mut attrs = ProgramAttrs::default();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("zkvm")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                attrs.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("proof_type") {
+                attrs.proof_type = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("codec") {
+                attrs.codec = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("input") {
+                attrs.input = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("output") {
+                attrs.output = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported `zkvm` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+fn input_fields(input: &DeriveInput, default_codec: &Type) -> Result<Vec<InputField>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "expected a struct with named fields, or an `input = Type` attribute",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`ZkVmProgram` can only be derived for structs",
+            ));
+        }
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let mut attrs = FieldAttrs::default();
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("zkvm")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("proof") {
+                        attrs.proof = true;
+                    } else if meta.path.is_ident("codec") {
+                        attrs.codec = Some(meta.value()?.parse()?);
+                    } else {
+                        return Err(meta.error("unsupported `zkvm` field attribute"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            Ok(InputField {
+                ident: field.ident.clone().expect("named field"),
+                ty: field.ty.clone(),
+                codec: attrs.codec.unwrap_or_else(|| default_codec.clone()),
+                proof: attrs.proof,
+            })
+        })
+        .collect()
+}
+
+fn missing(input: &DeriveInput, attr: &str) -> Error {
+    Error::new(
+        Span::call_site(),
+        format!(
+            "missing `#[zkvm({attr} = ...)]` attribute on `{}`",
+            input.ident
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    /// Returns `tokens` without whitespace, to compare expansions regardless of formatting.
+    fn compact(tokens: impl ToString) -> String {
+        tokens.to_string().split_whitespace().collect()
+    }
+
+    /// Returns the positions of `snippets` in `expansion`, asserting that each of them occurs.
+    fn positions(expansion: &TokenStream2, snippets: &[TokenStream2]) -> Vec<usize> {
+        let expansion = compact(expansion);
+        snippets
+            .iter()
+            .map(|snippet| {
+                let snippet = compact(snippet);
+                expansion
+                    .find(&snippet)
+                    .unwrap_or_else(|| panic!("`{snippet}` not found in `{expansion}`"))
+            })
+            .collect()
+    }
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn expands_codec_programs() {
+        let expansion = expand(parse_quote! {
+            #[zkvm(name = "fibonacci", proof_type = Groth16, codec = RawCodec, input = u32, output = u32)]
+            pub struct FibProgram;
+        })
+        .unwrap();
+        positions(
+            &expansion,
+            &[
+                quote! { impl ::zkaleido::ZkVmCodecProgram for FibProgram },
+                quote! { type Input = u32; type Output = u32; type Codec = RawCodec; },
+                quote! { ::std::string::String::from("fibonacci") },
+                quote! { ::zkaleido::ProofType::Groth16 },
+            ],
+        );
+        assert!(!compact(&expansion).contains("Reader"));
+    }
+
+    #[test]
+    fn expands_struct_programs() {
+        let expansion = expand(parse_quote! {
+            #[zkvm(proof_type = Compressed, codec = SerdeCodec, output = u32)]
+            pub struct Composition {
+                pub program_id: ProgramId,
+                #[zkvm(codec = RawCodec)]
+                pub count: u32,
+                #[zkvm(proof, codec = RawCodec)]
+                pub fib_proof: AggregationInput,
+            }
+        })
+        .unwrap();
+
+        positions(
+            &expansion,
+            &[
+                quote! { impl ::zkaleido::ZkVmProgram for Composition },
+                // The name defaults to the type name.
+                quote! { ::std::string::String::from("Composition") },
+                quote! { ::zkaleido::ProofType::Compressed },
+                quote! { pub fn commit_output },
+                quote! { pub struct CompositionReader<'e, E> },
+                quote! { pub fn try_program_id(&self) -> ::zkaleido::ZkVmEnvResult<ProgramId> },
+                quote! { <RawCodec as ::zkaleido::ZkVmCodec<T>>::try_read_verified(self.env, vk_digest) },
+            ],
+        );
+
+        // Fields are written, and read back, in declaration order.
+        let writes = positions(
+            &expansion,
+            &[
+                quote! { builder.write_with::<SerdeCodec, ProgramId>(&input.program_id)?; },
+                quote! { builder.write_with::<RawCodec, u32>(&input.count)?; },
+                quote! { builder.write_proof(&input.fib_proof)?; },
+            ],
+        );
+        assert!(writes.is_sorted());
+        let reads = positions(
+            &expansion,
+            &[
+                quote! { self.advance(0usize, "program_id"); },
+                quote! { self.advance(1usize, "count"); },
+                quote! { self.advance(2usize, "fib_proof"); },
+            ],
+        );
+        assert!(reads.is_sorted());
+    }
+
+    #[test]
+    fn rejects_invalid_program_attributes() {
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Stark, codec = RawCodec, input = u32, output = u32)]
+                struct P;
+            }),
+            "expected one of `Core`, `Compressed`, `Groth16` or `Plonk`"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = "Groth16", codec = RawCodec, input = u32, output = u32)]
+                struct P;
+            }),
+            "expected identifier"
+        );
+        for (attr, input) in [
+            (
+                "proof_type",
+                parse_quote! { #[zkvm(codec = RawCodec, output = u32)] struct P; },
+            ),
+            (
+                "codec",
+                parse_quote! { #[zkvm(proof_type = Core, output = u32)] struct P; },
+            ),
+            (
+                "output",
+                parse_quote! { #[zkvm(proof_type = Core, codec = RawCodec)] struct P; },
+            ),
+        ] {
+            assert_eq!(
+                error(input),
+                format!("missing `#[zkvm({attr} = ...)]` attribute on `P`")
+            );
+        }
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Core, codec = RawCodec, output = u32, guest = "fib")]
+                struct P;
+            }),
+            "unsupported `zkvm` attribute"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Core, codec = RawCodec, input = u32, output = u32)]
+                struct P<T>(T);
+            }),
+            "`ZkVmProgram` cannot be derived for generic types"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Core, codec = RawCodec, output = u32)]
+                struct P(u32);
+            }),
+            "expected a struct with named fields, or an `input = Type` attribute"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Core, codec = RawCodec, output = u32)]
+                enum P { A }
+            }),
+            "`ZkVmProgram` can only be derived for structs"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[zkvm(proof_type = Core, codec = RawCodec, output = u32)]
+                struct P {
+                    #[zkvm(verified)]
+                    a: u32,
+                }
+            }),
+            "unsupported `zkvm` field attribute"
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! A host wrapper that reuses previously generated proofs.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+#[cfg(feature = "borsh")]
+use borsh::BorshDeserialize;
+#[cfg(feature = "serde")]
+use serde::{Serialize, de::DeserializeOwned};
+
+#[cfg(any(feature = "serde", feature = "borsh", feature = "ssz"))]
+use crate::PublicValues;
+use crate::{
+    AggregationInput, ExecutionSummary, InputDigest, InputDigestBuilder, ProgramId, ProofKey,
+    ProofReceiptWithMetadata, ProofStore, ProofType, RunConfig, VerifyingKey, ZkVm, ZkVmExecutor,
+    ZkVmHost, ZkVmInputBuilder, ZkVmInputResult, ZkVmOutputExtractor, ZkVmProofError, ZkVmProver,
+    ZkVmResult, ZkVmTypedVerifier, ZkVmVkProvider,
+};
+#[cfg(feature = "perf")]
+use crate::{PerformanceReport, ZkVmHostPerf};
+
+/// Decides which entries a [`CachingHost`] removes from its store.
+///
+/// The policy only sees keys; the host performs the actual deletions. Implementations are
+/// shared between clones of the host and must synchronize internally.
+pub trait EvictionPolicy: Send + Sync + Debug + 'static {
+    /// Records that the receipt stored under `key` was served from the cache.
+    fn touch(&self, key: &ProofKey);
+
+    /// Records that a receipt was stored under `key` and returns the keys to evict.
+    fn insert(&self, key: &ProofKey) -> Vec<ProofKey>;
+
+    /// Records that the receipt stored under `key` was removed from the store.
+    fn remove(&self, key: &ProofKey);
+}
+
+/// An [`EvictionPolicy`] that never evicts anything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoEviction;
+
+impl EvictionPolicy for NoEviction {
+    fn touch(&self, _key: &ProofKey) {}
+
+    fn insert(&self, _key: &ProofKey) -> Vec<ProofKey> {
+        Vec::new()
+    }
+
+    fn remove(&self, _key: &ProofKey) {}
+}
+
+/// An [`EvictionPolicy`] that keeps at most `capacity` receipts, evicting the least recently
+/// used ones first.
+///
+/// Recency is tracked in memory, so receipts already present in a persistent store when the
+/// host is created are only accounted for once they are served or re-inserted.
+#[derive(Debug)]
+pub struct LruEviction {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    /// Monotonic counter used as a logical clock.
+    tick: u64,
+    /// Last access time of every tracked key.
+    last_used: HashMap<ProofKey, u64>,
+}
+
+impl LruEviction {
+    /// Creates a policy that keeps at most `capacity` receipts.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LruState> {
+        // The state is never left half-updated, so a poisoned lock is still safe to use.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LruState {
+    fn bump(&mut self, key: &ProofKey) {
+        self.tick += 1;
+        self.last_used.insert(key.clone(), self.tick);
+    }
+}
+
+impl EvictionPolicy for LruEviction {
+    fn touch(&self, key: &ProofKey) {
+        self.state().bump(key);
+    }
+
+    fn insert(&self, key: &ProofKey) -> Vec<ProofKey> {
+        let mut state = self.state();
+        state.bump(key);
+
+        let mut evicted = Vec::new();
+        while state.last_used.len() > self.capacity {
+            let oldest = state
+                .last_used
+                .iter()
+                .min_by_key(|(_, tick)| **tick)
+                .map(|(key, _)| key.clone())
+                .expect("map is non-empty");
+            state.last_used.remove(&oldest);
+            evicted.push(oldest);
+        }
+        evicted
+    }
+
+    fn remove(&self, key: &ProofKey) {
+        self.state().last_used.remove(key);
+    }
+}
+
+/// A [`ZkVmHost`] wrapper that serves proofs from a [`ProofStore`] when the same program has
+/// already been proven on the same input.
+///
+/// Inputs built through [`CachingInputBuilder`] carry an [`InputDigest`] alongside the inner
+/// host's input. Together with the host's [`program_id`](ZkVmExecutor::program_id), the
+/// requested [`ProofType`] and the [`ZkVm`] it forms the [`ProofKey`] looked up on every call
+/// to [`prove_inner`](ZkVmProver::prove_inner). A stored receipt is only returned after it
+/// verifies against the inner host; receipts that fail verification are dropped and the proof
+/// is regenerated. Proofs requested in dev mode (see [`RunConfig::dev_mode`]) are neither
+/// looked up nor stored.
+///
+/// Execution, verification and output extraction are forwarded to the inner host unchanged.
+#[derive(Debug)]
+pub struct CachingHost<H, S> {
+    inner: H,
+    store: Arc<S>,
+    eviction: Arc<dyn EvictionPolicy>,
+}
+
+impl<H: Clone, S> Clone for CachingHost<H, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            store: Arc::clone(&self.store),
+            eviction: Arc::clone(&self.eviction),
+        }
+    }
+}
+
+impl<H, S> CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore,
+{
+    /// Wraps `inner`, caching its proofs in `store` without ever evicting them.
+    pub fn new(inner: H, store: S) -> Self {
+        Self::with_store(inner, Arc::new(store))
+    }
+
+    /// Wraps `inner`, caching its proofs in a store that may be shared with other hosts.
+    pub fn with_store(inner: H, store: Arc<S>) -> Self {
+        Self {
+            inner,
+            store,
+            eviction: Arc::new(NoEviction),
+        }
+    }
+
+    /// Sets the policy used to evict receipts from the store.
+    pub fn with_eviction(mut self, policy: impl EvictionPolicy) -> Self {
+        self.eviction = Arc::new(policy);
+        self
+    }
+
+    /// Returns the wrapped host.
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    /// Returns the store backing the cache.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the key under which a proof of the given input digest is cached.
+    pub fn proof_key(&self, input_digest: InputDigest, proof_type: ProofType) -> ProofKey {
+        ProofKey::new(
+            self.inner.program_id(),
+            input_digest,
+            proof_type,
+            self.inner.zkvm(),
+        )
+    }
+
+    /// Returns the cached receipt for `key` if there is one and it still verifies.
+    fn lookup(&self, key: &ProofKey) -> ZkVmResult<Option<ProofReceiptWithMetadata>> {
+        let Some(receipt) = self.store.get(key)? else {
+            return Ok(None);
+        };
+
+        if self.inner.verify(&receipt).is_err() {
+            // A receipt that no longer verifies (e.g. produced by an older program build) is
+            // worthless; drop it so that the fresh proof replaces it.
+            self.store.delete(key)?;
+            self.eviction.remove(key);
+            return Ok(None);
+        }
+
+        self.eviction.touch(key);
+        Ok(Some(receipt))
+    }
+
+    /// Stores `receipt` under `key` and applies the eviction policy.
+    fn insert(&self, key: &ProofKey, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        self.store.put(key, receipt)?;
+        for evicted in self.eviction.insert(key) {
+            self.store.delete(&evicted)?;
+        }
+        Ok(())
+    }
+}
+
+/// Input builder used by [`CachingHost`].
+///
+/// Forwards every write to the inner host's builder while computing the [`InputDigest`] of
+/// the same values with an [`InputDigestBuilder`].
+#[derive(Debug)]
+pub struct CachingInputBuilder<B> {
+    inner: B,
+    digest: InputDigestBuilder,
+}
+
+/// Input built by a [`CachingInputBuilder`].
+#[derive(Debug, Clone)]
+pub struct CachedInput<I> {
+    inner: I,
+    digest: InputDigest,
+}
+
+impl<I> CachedInput<I> {
+    /// Returns the input built for the inner host.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the digest of the input.
+    pub fn digest(&self) -> InputDigest {
+        self.digest
+    }
+}
+
+impl<'a, B: ZkVmInputBuilder<'a>> ZkVmInputBuilder<'a> for CachingInputBuilder<B> {
+    type Input = CachedInput<B::Input>;
+    type ZkVmProofReceipt = B::ZkVmProofReceipt;
+
+    fn new() -> Self {
+        Self {
+            inner: B::new(),
+            digest: InputDigestBuilder::new(),
+        }
+    }
+
+    fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self> {
+        self.inner.write_buf(item)?;
+        self.digest.write_buf(item)?;
+        Ok(self)
+    }
+
+    #[cfg(feature = "serde")]
+    fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        self.inner.write_serde(item)?;
+        self.digest.write_serde(item)?;
+        Ok(self)
+    }
+
+    #[cfg(feature = "borsh")]
+    fn write_borsh<T: borsh::BorshSerialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        self.inner.write_borsh(item)?;
+        self.digest.write_borsh(item)?;
+        Ok(self)
+    }
+
+    #[cfg(feature = "ssz")]
+    fn write_ssz<T: ssz::Encode>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        self.inner.write_ssz(item)?;
+        self.digest.write_ssz(item)?;
+        Ok(self)
+    }
+
+    fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self> {
+        self.inner.write_proof(item)?;
+        self.digest.write_proof(item)?;
+        Ok(self)
+    }
+
+    fn build(&mut self) -> ZkVmInputResult<Self::Input> {
+        Ok(CachedInput {
+            inner: self.inner.build()?,
+            digest: self.digest.build()?,
+        })
+    }
+}
+
+/// Receipt produced by [`CachingHost`], either freshly proven or served from the cache.
+#[derive(Debug, Clone)]
+pub struct CachedProofReceipt(ProofReceiptWithMetadata);
+
+impl TryFrom<CachedProofReceipt> for ProofReceiptWithMetadata {
+    type Error = ZkVmProofError;
+
+    fn try_from(receipt: CachedProofReceipt) -> Result<Self, Self::Error> {
+        Ok(receipt.0)
+    }
+}
+
+impl<H, S> ZkVmExecutor for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    type Input<'a> = CachingInputBuilder<H::Input<'a>>;
+
+    fn execute<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary> {
+        self.inner.execute(input.inner, config)
+    }
+
+    fn get_elf(&self) -> &[u8] {
+        self.inner.get_elf()
+    }
+
+    fn program_id(&self) -> ProgramId {
+        self.inner.program_id()
+    }
+}
+
+impl<H, S> ZkVmProver for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    type ZkVmProofReceipt = CachedProofReceipt;
+
+    fn prove_inner<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        proof_type: ProofType,
+        config: &RunConfig,
+    ) -> ZkVmResult<Self::ZkVmProofReceipt> {
+        // Dev-mode proofs must never be served in place of real ones, so they bypass the store.
+        if config.dev_mode {
+            let receipt = self.inner.prove(input.inner, proof_type, config)?;
+            return Ok(CachedProofReceipt(receipt));
+        }
+
+        let key = self.proof_key(input.digest, proof_type);
+        if let Some(receipt) = self.lookup(&key)? {
+            return Ok(CachedProofReceipt(receipt));
+        }
+
+        let receipt = self.inner.prove(input.inner, proof_type, config)?;
+        self.insert(&key, &receipt)?;
+        Ok(CachedProofReceipt(receipt))
+    }
+
+    fn compress(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.inner.compress(receipt)
+    }
+
+    fn wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.inner.wrap_groth16(receipt)
+    }
+}
+
+impl<H, S> ZkVmTypedVerifier for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    type ZkVmProofReceipt = <H as ZkVmTypedVerifier>::ZkVmProofReceipt;
+
+    fn verify_inner(&self, receipt: &Self::ZkVmProofReceipt) -> ZkVmResult<()> {
+        self.inner.verify_inner(receipt)
+    }
+}
+
+impl<H, S> ZkVmVkProvider for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    fn vk(&self) -> VerifyingKey {
+        self.inner.vk()
+    }
+}
+
+impl<H, S> ZkVmOutputExtractor for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    #[cfg(feature = "serde")]
+    fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
+        public_values: &PublicValues,
+    ) -> ZkVmResult<T> {
+        H::extract_serde_public_output(public_values)
+    }
+
+    #[cfg(feature = "borsh")]
+    fn extract_borsh_public_output<T: BorshDeserialize>(
+        public_values: &PublicValues,
+    ) -> ZkVmResult<T> {
+        H::extract_borsh_public_output(public_values)
+    }
+
+    #[cfg(feature = "ssz")]
+    fn extract_ssz_public_output<T: ssz::Decode>(public_values: &PublicValues) -> ZkVmResult<T> {
+        H::extract_ssz_public_output(public_values)
+    }
+}
+
+impl<H, S> ZkVmHost for CachingHost<H, S>
+where
+    H: ZkVmHost,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    fn zkvm(&self) -> ZkVm {
+        self.inner.zkvm()
+    }
+}
+
+#[cfg(feature = "perf")]
+impl<H, S> ZkVmHostPerf for CachingHost<H, S>
+where
+    H: ZkVmHostPerf,
+    S: ProofStore + Send + Sync + Debug + 'static,
+{
+    fn perf_report<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
+    ) -> PerformanceReport {
+        self.inner.perf_report(input.inner, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(input: u8) -> ProofKey {
+        ProofKey::new(
+            ProgramId::default(),
+            InputDigest([input; 32]),
+            ProofType::Core,
+            ZkVm::Native,
+        )
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let policy = LruEviction::new(2);
+        assert!(policy.insert(&key(1)).is_empty());
+        assert!(policy.insert(&key(2)).is_empty());
+
+        // Reading key 1 makes key 2 the eviction candidate.
+        policy.touch(&key(1));
+        assert_eq!(policy.insert(&key(3)), vec![key(2)]);
+
+        policy.remove(&key(1));
+        assert!(policy.insert(&key(4)).is_empty());
+        assert_eq!(policy.insert(&key(5)), vec![key(3)]);
+    }
+}
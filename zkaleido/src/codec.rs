@@ -0,0 +1,394 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde")]
+use serde::{Serialize, de::DeserializeOwned};
+#[cfg(feature = "ssz")]
+use ssz::{Decode, Encode};
+
+#[cfg(not(feature = "serde"))]
+use crate::ZkVmEnv as CodecEnv;
+#[cfg(feature = "borsh")]
+use crate::ZkVmEnvBorsh;
+#[cfg(feature = "serde")]
+use crate::ZkVmEnvSerde as CodecEnv;
+#[cfg(feature = "ssz")]
+use crate::ZkVmEnvSsz;
+#[cfg(feature = "serde")]
+use crate::ZkVmInputError;
+use crate::{
+    DataFormatError, PublicValues, ZkVmEnvResult, ZkVmError, ZkVmInputBuilder, ZkVmInputResult,
+    ZkVmOutputExtractor, ZkVmResult, env::unwrap_or_abort,
+};
+
+/// An encoding shared by the host and the guest for values of type `T`.
+///
+/// A codec ties together the three places where a value crosses the host/guest boundary:
+/// writing it with a [`ZkVmInputBuilder`], reading or committing it inside the guest through a
+/// [`ZkVmEnvCodec`], and extracting it from the [`PublicValues`] with a
+/// [`ZkVmOutputExtractor`]. Routing all three through the same codec rules out the host and the
+/// guest disagreeing on the encoding.
+///
+/// The codecs provided are [`SerdeCodec`], [`CanonicalSerdeCodec`], [`BorshCodec`], [`SszCodec`]
+/// and [`RawCodec`].
+pub trait ZkVmCodec<T> {
+    /// Writes `item` to the input builder.
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>;
+
+    /// Reads a value written with [`ZkVmCodec::write`] inside the guest.
+    ///
+    /// Aborts the guest if the value cannot be read, like the other guest-side readers.
+    fn read<E: ZkVmEnvCodec>(env: &E) -> T {
+        unwrap_or_abort(env, Self::try_read(env))
+    }
+
+    /// Fallible version of [`ZkVmCodec::read`].
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T>;
+
+    /// Reads a value committed by another guest with [`ZkVmCodec::commit`] and verifies the
+    /// proof of that guest against `vk_digest`.
+    fn read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> T {
+        unwrap_or_abort(env, Self::try_read_verified(env, vk_digest))
+    }
+
+    /// Fallible version of [`ZkVmCodec::read_verified`].
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T>;
+
+    /// Commits `item` to the public values stream inside the guest.
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T);
+
+    /// Extracts a value committed with [`ZkVmCodec::commit`] from the public values.
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T>;
+}
+
+/// Guest environments that support every [`ZkVmCodec`].
+///
+/// This is [`ZkVmEnv`](crate::ZkVmEnv) plus `ZkVmEnvSerde` when the `serde` feature is enabled, so
+/// it is implemented for every guest environment.
+pub trait ZkVmEnvCodec: CodecEnv {
+    /// Reads a value written by the host with the codec `C`.
+    fn read_with<C: ZkVmCodec<T>, T>(&self) -> T
+    where
+        Self: Sized,
+    {
+        C::read(self)
+    }
+
+    /// Fallible version of [`ZkVmEnvCodec::read_with`].
+    fn try_read_with<C: ZkVmCodec<T>, T>(&self) -> ZkVmEnvResult<T>
+    where
+        Self: Sized,
+    {
+        C::try_read(self)
+    }
+
+    /// Reads a value committed by another guest with the codec `C` and verifies its proof.
+    fn read_verified_with<C: ZkVmCodec<T>, T>(&self, vk_digest: &[u32; 8]) -> T
+    where
+        Self: Sized,
+    {
+        C::read_verified(self, vk_digest)
+    }
+
+    /// Commits a value to the public values stream with the codec `C`.
+    fn commit_with<C: ZkVmCodec<T>, T>(&self, item: &T)
+    where
+        Self: Sized,
+    {
+        C::commit(self, item)
+    }
+}
+
+/// Blanket implementation of [`ZkVmEnvCodec`] for all guest environments.
+impl<T: CodecEnv> ZkVmEnvCodec for T {}
+
+/// Codec using the zkVM's Serde support.
+///
+/// Inputs go through [`ZkVmInputBuilder::write_serde`] and
+/// [`read_serde`](crate::ZkVmEnvSerde::read_serde), so the exact encoding is chosen by each
+/// adapter.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerdeCodec;
+
+#[cfg(feature = "serde")]
+impl<T: Serialize + DeserializeOwned> ZkVmCodec<T> for SerdeCodec {
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        builder.write_serde(item)?;
+        Ok(())
+    }
+
+    fn read<E: ZkVmEnvCodec>(env: &E) -> T {
+        env.read_serde()
+    }
+
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T> {
+        env.try_read_serde()
+    }
+
+    fn read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> T {
+        env.read_verified_serde(vk_digest)
+    }
+
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        env.try_read_verified_serde(vk_digest)
+    }
+
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T) {
+        env.commit_serde(item)
+    }
+
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T> {
+        H::extract_serde_public_output(public_values)
+    }
+}
+
+/// Codec using Serde with the canonical encoding, `bincode`, on every zkVM.
+///
+/// Unlike [`SerdeCodec`], whose encoding is chosen by each adapter, this codec encodes inputs and
+/// outputs the same way everywhere. The same program therefore commits byte-identical public
+/// values on every zkVM, which is what multi-prover setups and host-agnostic output decoding
+/// rely on.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanonicalSerdeCodec;
+
+#[cfg(feature = "serde")]
+impl CanonicalSerdeCodec {
+    /// Decodes a value committed with this codec, without needing a host.
+    pub fn decode<T: DeserializeOwned>(public_values: &PublicValues) -> ZkVmResult<T> {
+        bincode::deserialize(public_values.as_bytes()).map_err(|e| {
+            ZkVmError::OutputExtractionError {
+                source: DataFormatError::Serde(e.to_string()),
+            }
+        })
+    }
+
+    fn decode_buf<T: DeserializeOwned>(buf: &[u8]) -> ZkVmEnvResult<T> {
+        bincode::deserialize(buf).map_err(|e| DataFormatError::Serde(e.to_string()).into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize + DeserializeOwned> ZkVmCodec<T> for CanonicalSerdeCodec {
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        let bytes = bincode::serialize(item)
+            .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
+        builder.write_buf(&bytes)?;
+        Ok(())
+    }
+
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T> {
+        Self::decode_buf(&env.try_read_buf()?)
+    }
+
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        Self::decode_buf(&env.try_read_verified_buf(vk_digest)?)
+    }
+
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T) {
+        // Committed directly rather than through `commit_serde`, which adapters may override.
+        let bytes = bincode::serialize(item).expect("bincode serialization failed");
+        env.commit_buf(&bytes);
+    }
+
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T> {
+        Self::decode(public_values)
+    }
+}
+
+/// Codec using Borsh.
+#[cfg(feature = "borsh")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BorshCodec;
+
+#[cfg(feature = "borsh")]
+impl<T: BorshSerialize + BorshDeserialize> ZkVmCodec<T> for BorshCodec {
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        builder.write_borsh(item)?;
+        Ok(())
+    }
+
+    fn read<E: ZkVmEnvCodec>(env: &E) -> T {
+        env.read_borsh()
+    }
+
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T> {
+        env.try_read_borsh()
+    }
+
+    fn read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> T {
+        env.read_verified_borsh(vk_digest)
+    }
+
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        env.try_read_verified_borsh(vk_digest)
+    }
+
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T) {
+        env.commit_borsh(item)
+    }
+
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T> {
+        H::extract_borsh_public_output(public_values)
+    }
+}
+
+/// Codec using SSZ.
+#[cfg(feature = "ssz")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SszCodec;
+
+#[cfg(feature = "ssz")]
+impl<T: Encode + Decode> ZkVmCodec<T> for SszCodec {
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        builder.write_ssz(item)?;
+        Ok(())
+    }
+
+    fn read<E: ZkVmEnvCodec>(env: &E) -> T {
+        env.read_ssz()
+    }
+
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T> {
+        env.try_read_ssz()
+    }
+
+    fn read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> T {
+        env.read_verified_ssz(vk_digest)
+    }
+
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        env.try_read_verified_ssz(vk_digest)
+    }
+
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T) {
+        env.commit_ssz(item)
+    }
+
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T> {
+        H::extract_ssz_public_output(public_values)
+    }
+}
+
+/// Codec passing raw bytes through unchanged, for types implementing [`RawEncoding`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawCodec;
+
+/// Types with a canonical raw byte representation, usable with [`RawCodec`].
+///
+/// Implemented for byte vectors, byte arrays and fixed-width integers (little-endian).
+pub trait RawEncoding: Sized {
+    /// Returns the raw bytes of the value.
+    fn to_raw(&self) -> Vec<u8>;
+
+    /// Parses a value from its raw bytes.
+    fn from_raw(bytes: &[u8]) -> Result<Self, DataFormatError>;
+}
+
+impl RawEncoding for Vec<u8> {
+    fn to_raw(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, DataFormatError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> RawEncoding for [u8; N] {
+    fn to_raw(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, DataFormatError> {
+        bytes.try_into().map_err(|_| invalid_length(N, bytes.len()))
+    }
+}
+
+macro_rules! impl_raw_encoding_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl RawEncoding for $int {
+                fn to_raw(&self) -> Vec<u8> {
+                    self.to_le_bytes().to_vec()
+                }
+
+                fn from_raw(bytes: &[u8]) -> Result<Self, DataFormatError> {
+                    let bytes = bytes
+                        .try_into()
+                        .map_err(|_| invalid_length(size_of::<$int>(), bytes.len()))?;
+                    Ok(<$int>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_raw_encoding_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+fn invalid_length(expected: usize, actual: usize) -> DataFormatError {
+    DataFormatError::Other(format!(
+        "invalid raw length: expected {expected} bytes, found {actual}"
+    ))
+}
+
+impl<T: RawEncoding> ZkVmCodec<T> for RawCodec {
+    fn write<'a, B>(builder: &mut B, item: &T) -> ZkVmInputResult<()>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        builder.write_buf(&item.to_raw())?;
+        Ok(())
+    }
+
+    fn try_read<E: ZkVmEnvCodec>(env: &E) -> ZkVmEnvResult<T> {
+        Ok(T::from_raw(&env.try_read_buf()?)?)
+    }
+
+    fn try_read_verified<E: ZkVmEnvCodec>(env: &E, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        Ok(T::from_raw(&env.try_read_verified_buf(vk_digest)?)?)
+    }
+
+    fn commit<E: ZkVmEnvCodec>(env: &E, item: &T) {
+        env.commit_buf(&item.to_raw())
+    }
+
+    fn extract<H: ZkVmOutputExtractor>(public_values: &PublicValues) -> ZkVmResult<T> {
+        T::from_raw(public_values.as_bytes())
+            .map_err(|source| ZkVmError::OutputExtractionError { source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_encoding_roundtrip() {
+        assert_eq!(u32::from_raw(&7u32.to_raw()).unwrap(), 7);
+        assert_eq!(7u32.to_raw(), vec![7, 0, 0, 0]);
+        assert_eq!(i64::from_raw(&(-3i64).to_raw()).unwrap(), -3);
+        assert_eq!(<[u8; 3]>::from_raw(&[1, 2, 3]).unwrap(), [1, 2, 3]);
+        assert_eq!(Vec::<u8>::from_raw(&[4, 5]).unwrap(), vec![4, 5]);
+    }
+
+    #[test]
+    fn raw_encoding_rejects_wrong_length() {
+        assert!(u32::from_raw(&[1, 2, 3]).is_err());
+        assert!(<[u8; 2]>::from_raw(&[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use std::{env::var, path::PathBuf};
+
+/// Per-run configuration passed explicitly to [`ZkVmProgram`](crate::ZkVmProgram) and the
+/// hosts.
+///
+/// Replaces the `ZKVM_PROOF_DUMP`, `ZKVM_PROFILING_DUMP` and `ZKVM_MOCK` environment variables,
+/// which were read (and in some adapters written) at proving time. Mutating the process
+/// environment at runtime is unsound in multithreaded programs, so the hosts only ever read
+/// this struct; [`RunConfig::from_env`] is kept for binaries that want the old behavior.
+///
+/// The default configuration dumps nothing and generates real proofs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Directory receipts are saved to after proving, named like
+    /// [`ProofReceiptWithMetadata::save`](crate::ProofReceiptWithMetadata::save). `None`
+    /// disables saving.
+    pub proof_output_dir: Option<PathBuf>,
+    /// Directory execution traces are written to, for hosts that support profiling. `None`
+    /// disables profiling.
+    pub profiling_output_dir: Option<PathBuf>,
+    /// Name of the program being run, prefixed to the names of its traces. Set by
+    /// [`ZkVmProgram`](crate::ZkVmProgram) to the program's name.
+    pub program_name: Option<String>,
+    /// When `true`, hosts that support it generate mock proofs that skip the expensive proving
+    /// step. Such proofs must never be used in production.
+    pub dev_mode: bool,
+}
+
+impl RunConfig {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a config from the legacy environment variables: `ZKVM_PROOF_DUMP` and
+    /// `ZKVM_PROFILING_DUMP` save proofs and traces to the current directory, and `ZKVM_MOCK`
+    /// enables dev mode. Each is enabled by `1` or `true` (case-insensitive).
+    pub fn from_env() -> Self {
+        let current_dir = || PathBuf::from(".");
+        Self {
+            proof_output_dir: env_flag("ZKVM_PROOF_DUMP").then(current_dir),
+            profiling_output_dir: env_flag("ZKVM_PROFILING_DUMP").then(current_dir),
+            dev_mode: env_flag("ZKVM_MOCK"),
+            program_name: None,
+        }
+    }
+
+    /// Saves receipts to `dir` after proving.
+    #[must_use]
+    pub fn with_proof_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.proof_output_dir = Some(dir.into());
+        self
+    }
+
+    /// Writes execution traces to `dir`.
+    #[must_use]
+    pub fn with_profiling_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.profiling_output_dir = Some(dir.into());
+        self
+    }
+
+    /// Enables or disables dev mode.
+    #[must_use]
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+        self
+    }
+
+    /// Sets the name of the program being run.
+    #[must_use]
+    pub fn with_program_name(mut self, program_name: impl Into<String>) -> Self {
+        self.program_name = Some(program_name.into());
+        self
+    }
+
+    /// Returns the path of the trace written by the host named `host_name`, prefixed with the
+    /// program name if one is set, or `None` if profiling is disabled.
+    pub fn trace_path(&self, host_name: &str) -> Option<PathBuf> {
+        let trace_name = match &self.program_name {
+            Some(program_name) => format!("{program_name}_{host_name}"),
+            None => host_name.to_string(),
+        };
+        self.profiling_output_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{trace_name}.trace_profile")))
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    var(name)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_paths_are_named_after_the_program_and_host() {
+        let config = RunConfig::new();
+        assert_eq!(config.trace_path("risc0_00"), None);
+
+        let config = config.with_profiling_output_dir("traces");
+        assert_eq!(
+            config.trace_path("risc0_00"),
+            Some(PathBuf::from("traces/risc0_00.trace_profile"))
+        );
+        assert_eq!(
+            config.with_program_name("fibonacci").trace_path("risc0_00"),
+            Some(PathBuf::from("traces/fibonacci_risc0_00.trace_profile"))
+        );
+    }
+}
@@ -0,0 +1,519 @@
+//! The zkaleido wire format and a suite checking that an adapter implements it.
+//!
+//! # Wire format
+//!
+//! The input of a guest is an ordered list of *frames*. Every `write_*` method of a
+//! [`ZkVmInputBuilder`] appends exactly one frame, and every `read_*` method of a
+//! [`ZkVmEnv`](crate::ZkVmEnv) consumes exactly one, in the order they were written. A frame
+//! carries a byte payload of any length, including zero, which the guest receives unchanged:
+//!
+//! | Frame | Written with  | Read with           | Payload                        |
+//! |-------|---------------|---------------------|--------------------------------|
+//! | buf   | `write_buf`   | `read_buf`          | the bytes, unchanged           |
+//! | serde | `write_serde` | `read_serde`        | `bincode` encoding of the item |
+//! | borsh | `write_borsh` | `read_borsh`        | `borsh` encoding of the item   |
+//! | ssz   | `write_ssz`   | `read_ssz`          | SSZ encoding of the item       |
+//! | proof | `write_proof` | `read_verified_buf` | public values of the receipt   |
+//!
+//! serde, borsh and ssz frames are buf frames, so a guest may read any of them with
+//! [`ZkVmEnv::read_buf`](crate::ZkVmEnv::read_buf) and decode the payload itself. A proof frame
+//! must be read with one of the `read_verified_*` methods: adapters may pass the proof, or the
+//! verification key, next to the public values, and only the verifying read consumes them.
+//!
+//! How a frame is laid out in the zkVM input stream is up to the adapter. The native adapter
+//! keeps one buffer per frame, SP1 one `SP1Stdin` buffer per frame, and RISC0 a `u32` length
+//! word followed by the payload.
+//!
+//! # Conformance suite
+//!
+//! [`ConformanceSuite`] writes every kind of frame with the builder of a host and runs
+//! [`conformance_guest`], which reads them back with the matching reads of the guest
+//! environment and commits what it read. The suite then compares that with what was written.
+//! A new adapter only has to build [`conformance_guest`] for its zkVM and run the suite against
+//! its host.
+
+use std::{
+    array,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+#[cfg(feature = "ssz")]
+use ssz::Encode;
+
+#[cfg(feature = "borsh")]
+use crate::ZkVmEnvBorsh;
+#[cfg(feature = "ssz")]
+use crate::ZkVmEnvSsz;
+#[cfg(not(all(feature = "borsh", feature = "ssz")))]
+use crate::ZkVmInputError;
+use crate::{
+    AggregationInput, CanonicalSerdeCodec, DataFormatError, PublicValues, RunConfig, ZkVmEnvCodec,
+    ZkVmEnvError, ZkVmEnvResult, ZkVmHost, ZkVmInputBuilder, ZkVmInputResult, ZkVmResult,
+};
+
+/// Set on a step of the script when the guest must read the frame with
+/// [`ZkVmEnv::try_read_buf`](crate::ZkVmEnv::try_read_buf) instead of the typed read of its kind.
+const RAW_READ: u8 = 0x80;
+
+/// Frame written after all others, so that data an adapter leaves behind in the input stream
+/// shows up as a mismatch.
+const TRAILING_BUF: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+/// The value written in serde and borsh frames.
+type Sample = (u8, u64, String, Vec<u32>, Option<bool>);
+
+/// The value written in ssz frames.
+#[cfg(feature = "ssz")]
+const SSZ_SAMPLE: u64 = 0x0102_0304_0506_0708;
+
+fn sample() -> Sample {
+    (
+        7,
+        u64::MAX - 1,
+        "frame".to_string(),
+        vec![1, 2, 3],
+        Some(true),
+    )
+}
+
+/// What the conformance guest commits: for each frame, the payload it read or why it could
+/// not read it.
+type GuestOutput = Vec<Result<Vec<u8>, String>>;
+
+/// The kind of a frame of the zkaleido wire format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum FrameKind {
+    /// A raw buffer.
+    Buf = 0,
+    /// A value encoded with `bincode`.
+    Serde = 1,
+    /// A value encoded with `borsh`.
+    Borsh = 2,
+    /// A value encoded with SSZ.
+    Ssz = 3,
+    /// The public values of a proof verified by the guest.
+    Proof = 4,
+}
+
+impl Display for FrameKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let s = match self {
+            FrameKind::Buf => "buf",
+            FrameKind::Serde => "serde",
+            FrameKind::Borsh => "borsh",
+            FrameKind::Ssz => "ssz",
+            FrameKind::Proof => "proof",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl TryFrom<u8> for FrameKind {
+    type Error = ZkVmEnvError;
+
+    fn try_from(tag: u8) -> ZkVmEnvResult<Self> {
+        match tag {
+            0 => Ok(FrameKind::Buf),
+            1 => Ok(FrameKind::Serde),
+            2 => Ok(FrameKind::Borsh),
+            3 => Ok(FrameKind::Ssz),
+            4 => Ok(FrameKind::Proof),
+            _ => Err(DataFormatError::Other(format!("unknown frame kind: {tag}")).into()),
+        }
+    }
+}
+
+/// The guest program of the conformance suite.
+///
+/// It reads the script written by [`ConformanceSuite`], then every frame it lists, and commits
+/// the payloads with [`CanonicalSerdeCodec`]. Typed
+/// frames are re-encoded with the encoding of their kind, so the committed payload equals the
+/// written one if the value round-tripped. Reading stops at the first frame that fails, since the
+/// position of the following frames in the input stream is then unknown.
+pub fn conformance_guest<E: ZkVmEnvCodec>(env: &E) {
+    let script = env.read_buf();
+    let mut steps = script.as_slice();
+    let mut output = GuestOutput::new();
+    while let Some((&step, rest)) = steps.split_first() {
+        steps = rest;
+        let result = read_step(env, step, &mut steps).map_err(|e| e.to_string());
+        let failed = result.is_err();
+        output.push(result);
+        if failed {
+            break;
+        }
+    }
+    env.commit_with::<CanonicalSerdeCodec, _>(&output);
+}
+
+/// Reads the frame described by `step`, taking the verification key digest of proof frames from
+/// the remaining `steps`.
+fn read_step<E: ZkVmEnvCodec>(env: &E, step: u8, steps: &mut &[u8]) -> ZkVmEnvResult<Vec<u8>> {
+    let kind = FrameKind::try_from(step & !RAW_READ)?;
+    if kind == FrameKind::Proof {
+        let vk_digest = take_vk_digest(steps)?;
+        return env.try_read_verified_buf(&vk_digest);
+    }
+    if step & RAW_READ != 0 {
+        return env.try_read_buf();
+    }
+    match kind {
+        FrameKind::Buf => env.try_read_buf(),
+        FrameKind::Serde => {
+            let value: Sample = env.try_read_serde()?;
+            Ok(bincode::serialize(&value).expect("bincode serialization failed"))
+        }
+        #[cfg(feature = "borsh")]
+        FrameKind::Borsh => {
+            let value: Sample = env.try_read_borsh()?;
+            Ok(borsh::to_vec(&value).expect("borsh serialization failed"))
+        }
+        #[cfg(feature = "ssz")]
+        FrameKind::Ssz => {
+            let value: u64 = env.try_read_ssz()?;
+            Ok(value.as_ssz_bytes())
+        }
+        _ => Err(DataFormatError::Other(format!("{kind} frames are not supported")).into()),
+    }
+}
+
+fn take_vk_digest(steps: &mut &[u8]) -> ZkVmEnvResult<[u32; 8]> {
+    let Some((bytes, rest)) = steps.split_first_chunk::<32>() else {
+        return Err(DataFormatError::Other("truncated verification key digest".into()).into());
+    };
+    *steps = rest;
+    let (words, _) = bytes.as_chunks::<4>();
+    Ok(array::from_fn(|i| u32::from_le_bytes(words[i])))
+}
+
+#[derive(Debug, Clone)]
+struct ConformanceCase {
+    name: &'static str,
+    kind: FrameKind,
+    raw_read: bool,
+    /// The payload the frame must carry, as specified by the wire format.
+    payload: Vec<u8>,
+}
+
+impl ConformanceCase {
+    fn buf(name: &'static str, payload: Vec<u8>) -> Self {
+        Self {
+            name,
+            kind: FrameKind::Buf,
+            raw_read: false,
+            payload,
+        }
+    }
+
+    fn typed(name: &'static str, kind: FrameKind, raw_read: bool, payload: Vec<u8>) -> Self {
+        Self {
+            name,
+            kind,
+            raw_read,
+            payload,
+        }
+    }
+
+    /// Returns a case reading a frame of `kind` with the typed read, followed by one reading it
+    /// as a buf.
+    fn typed_pair(names: [&'static str; 2], kind: FrameKind, payload: Vec<u8>) -> [Self; 2] {
+        [
+            Self::typed(names[0], kind, false, payload.clone()),
+            Self::typed(names[1], kind, true, payload),
+        ]
+    }
+}
+
+/// A proof written in a proof frame of the suite.
+#[derive(Debug, Clone)]
+struct ConformanceProof {
+    input: AggregationInput,
+    vk_digest: [u32; 8],
+}
+
+/// Checks that the input builder and the guest environment of an adapter agree on the
+/// zkaleido wire format.
+///
+/// The suite writes buf frames of various lengths, then serde, borsh and ssz frames, each read
+/// once with the typed read and once as a raw buffer, and an optional proof frame. The borsh and
+/// ssz frames are only written when the matching feature is enabled.
+///
+/// Run it with [`run`](Self::run) against a host whose guest is [`conformance_guest`], or with
+/// [`build_input`](Self::build_input) and [`check`](Self::check) to drive the execution yourself.
+#[derive(Debug, Clone)]
+pub struct ConformanceSuite {
+    cases: Vec<ConformanceCase>,
+    proof: Option<ConformanceProof>,
+}
+
+impl Default for ConformanceSuite {
+    fn default() -> Self {
+        let sample = sample();
+        let mut cases = vec![
+            ConformanceCase::buf("empty buf", Vec::new()),
+            ConformanceCase::buf("unaligned buf", vec![1, 2, 3]),
+            ConformanceCase::buf("large buf", (0..=255).cycle().take(4099).collect()),
+        ];
+        cases.extend(ConformanceCase::typed_pair(
+            ["serde", "serde read as buf"],
+            FrameKind::Serde,
+            bincode::serialize(&sample).expect("bincode serialization failed"),
+        ));
+        #[cfg(feature = "borsh")]
+        cases.extend(ConformanceCase::typed_pair(
+            ["borsh", "borsh read as buf"],
+            FrameKind::Borsh,
+            borsh::to_vec(&sample).expect("borsh serialization failed"),
+        ));
+        #[cfg(feature = "ssz")]
+        cases.extend(ConformanceCase::typed_pair(
+            ["ssz", "ssz read as buf"],
+            FrameKind::Ssz,
+            SSZ_SAMPLE.as_ssz_bytes(),
+        ));
+        Self { cases, proof: None }
+    }
+}
+
+impl ConformanceSuite {
+    /// Creates the suite with every frame kind except proof frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also writes `proof` in a proof frame, which the guest verifies against `vk_digest`.
+    ///
+    /// The receipt must be one the guest can verify on the zkVM under test, for example one
+    /// produced by the same adapter.
+    #[must_use]
+    pub fn with_proof(mut self, proof: AggregationInput, vk_digest: [u32; 8]) -> Self {
+        self.proof = Some(ConformanceProof {
+            input: proof,
+            vk_digest,
+        });
+        self
+    }
+
+    /// Returns the cases in the order they are written, closing with a buf frame that catches
+    /// leftover data.
+    fn cases(&self) -> Vec<ConformanceCase> {
+        let mut cases = self.cases.clone();
+        if let Some(proof) = &self.proof {
+            let public_values = proof.input.receipt().receipt().public_values();
+            cases.push(ConformanceCase::typed(
+                "proof",
+                FrameKind::Proof,
+                false,
+                public_values.as_bytes().to_vec(),
+            ));
+        }
+        cases.push(ConformanceCase::buf("trailing buf", TRAILING_BUF.to_vec()));
+        cases
+    }
+
+    /// Builds the input of [`conformance_guest`] with the builder `B`.
+    pub fn build_input<'a, B: ZkVmInputBuilder<'a>>(&self) -> ZkVmInputResult<B::Input> {
+        let cases = self.cases();
+        let mut builder = B::new();
+
+        let mut script = Vec::with_capacity(cases.len());
+        for case in &cases {
+            script.push(case.kind as u8 | if case.raw_read { RAW_READ } else { 0 });
+            if case.kind == FrameKind::Proof {
+                let proof = self.proof.as_ref().expect("proof case without a proof");
+                script.extend(proof.vk_digest.iter().flat_map(|word| word.to_le_bytes()));
+            }
+        }
+        builder.write_buf(&script)?;
+
+        for case in &cases {
+            self.write_case(&mut builder, case)?;
+        }
+        builder.build()
+    }
+
+    fn write_case<'a, B: ZkVmInputBuilder<'a>>(
+        &self,
+        builder: &mut B,
+        case: &ConformanceCase,
+    ) -> ZkVmInputResult<()> {
+        match case.kind {
+            FrameKind::Buf => builder.write_buf(&case.payload)?,
+            FrameKind::Serde => builder.write_serde(&sample())?,
+            #[cfg(feature = "borsh")]
+            FrameKind::Borsh => builder.write_borsh(&sample())?,
+            #[cfg(feature = "ssz")]
+            FrameKind::Ssz => builder.write_ssz(&SSZ_SAMPLE)?,
+            FrameKind::Proof => {
+                let proof = self.proof.as_ref().expect("proof case without a proof");
+                builder.write_proof(&proof.input)?
+            }
+            #[cfg(not(all(feature = "borsh", feature = "ssz")))]
+            kind => {
+                return Err(ZkVmInputError::DataFormat(DataFormatError::Other(format!(
+                    "{kind} frames are not supported"
+                ))));
+            }
+        };
+        Ok(())
+    }
+
+    /// Checks the public values committed by [`conformance_guest`] against the written frames.
+    pub fn check(&self, public_values: &PublicValues) -> ZkVmResult<ConformanceReport> {
+        let output: GuestOutput = CanonicalSerdeCodec::decode(public_values)?;
+        let frames = self
+            .cases()
+            .into_iter()
+            .enumerate()
+            .map(|(index, case)| {
+                let outcome = match output.get(index) {
+                    Some(Ok(payload)) if *payload == case.payload => FrameOutcome::Passed,
+                    Some(Ok(payload)) => FrameOutcome::Mismatch {
+                        expected: case.payload,
+                        actual: payload.clone(),
+                    },
+                    Some(Err(message)) => FrameOutcome::ReadFailed(message.clone()),
+                    None => FrameOutcome::NotRead,
+                };
+                FrameReport {
+                    index,
+                    name: case.name,
+                    kind: case.kind,
+                    outcome,
+                }
+            })
+            .collect();
+        Ok(ConformanceReport { frames })
+    }
+
+    /// Runs the suite against `host`, whose guest must be [`conformance_guest`].
+    ///
+    /// Fails if the input cannot be built or the guest does not run to completion; frames that
+    /// do not round-trip are reported in the returned [`ConformanceReport`].
+    pub fn run<H: ZkVmHost>(&self, host: &H) -> ZkVmResult<ConformanceReport> {
+        let input = self.build_input::<H::Input<'_>>()?;
+        let summary = host.execute(input, &RunConfig::default())?;
+        self.check(summary.public_values())
+    }
+}
+
+/// The result of reading back one frame in the conformance guest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameOutcome {
+    /// The guest read the written payload.
+    Passed,
+    /// The guest read a different payload.
+    Mismatch {
+        /// The payload required by the wire format.
+        expected: Vec<u8>,
+        /// The payload the guest read.
+        actual: Vec<u8>,
+    },
+    /// The guest failed to read or decode the frame.
+    ReadFailed(String),
+    /// The guest stopped before this frame because an earlier one failed.
+    NotRead,
+}
+
+/// The outcome of one frame of a [`ConformanceSuite`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameReport {
+    /// Position of the frame among the frames checked, not counting the script.
+    pub index: usize,
+    /// Name of the case.
+    pub name: &'static str,
+    /// Kind of the frame.
+    pub kind: FrameKind,
+    /// Whether the frame round-tripped.
+    pub outcome: FrameOutcome,
+}
+
+/// The outcome of a [`ConformanceSuite`] run.
+///
+/// Its [`Display`] implementation lists the frames that failed, for use in assertion messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    frames: Vec<FrameReport>,
+}
+
+impl ConformanceReport {
+    /// Returns the outcome of every frame, in the order they were written.
+    pub fn frames(&self) -> &[FrameReport] {
+        &self.frames
+    }
+
+    /// Returns the frames that did not round-trip.
+    pub fn failures(&self) -> impl Iterator<Item = &FrameReport> {
+        self.frames
+            .iter()
+            .filter(|frame| frame.outcome != FrameOutcome::Passed)
+    }
+
+    /// Returns `true` if every frame round-tripped.
+    pub fn is_conformant(&self) -> bool {
+        self.failures().next().is_none()
+    }
+}
+
+impl Display for ConformanceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let passed = self.frames.len() - self.failures().count();
+        write!(f, "{passed}/{} frames conform", self.frames.len())?;
+        for frame in self.failures() {
+            write!(
+                f,
+                "\n  frame {} ({}, {}): ",
+                frame.index, frame.name, frame.kind
+            )?;
+            match &frame.outcome {
+                FrameOutcome::Passed => {}
+                FrameOutcome::Mismatch { expected, actual } => {
+                    write!(f, "expected {expected:02x?}, read {actual:02x?}")?
+                }
+                FrameOutcome::ReadFailed(message) => write!(f, "read failed: {message}")?,
+                FrameOutcome::NotRead => write!(f, "not read")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_failed_frames() {
+        let suite = ConformanceSuite::new();
+        let cases = suite.cases();
+        let mut output: GuestOutput = cases.iter().map(|case| Ok(case.payload.clone())).collect();
+        output[1] = Ok(vec![1, 2]);
+        output[2] = Err("no more inputs to read".to_string());
+        output.truncate(3);
+        let public_values = PublicValues::new(bincode::serialize(&output).unwrap());
+
+        let report = suite.check(&public_values).unwrap();
+        assert!(!report.is_conformant());
+        assert_eq!(report.frames()[0].outcome, FrameOutcome::Passed);
+        assert_eq!(
+            report.frames()[1].outcome,
+            FrameOutcome::Mismatch {
+                expected: vec![1, 2, 3],
+                actual: vec![1, 2],
+            }
+        );
+        assert!(matches!(
+            report.frames()[2].outcome,
+            FrameOutcome::ReadFailed(_)
+        ));
+        assert_eq!(report.failures().count(), cases.len() - 1);
+        assert!(
+            report
+                .frames()
+                .iter()
+                .skip(3)
+                .all(|frame| frame.outcome == FrameOutcome::NotRead)
+        );
+    }
+}
@@ -0,0 +1,667 @@
+//! Self-describing binary container for [`ProofReceiptWithMetadata`].
+//!
+//! The current container (format version 2) is laid out as:
+//!
+//! ```text
+//! [magic: 8][format_version: u16 LE][body][checksum: 32]
+//! ```
+//!
+//! where `checksum` is the SHA-256 digest of every byte that precedes it and `body` is:
+//!
+//! ```text
+//! [proof_len: u64 LE][proof][pv_len: u64 LE][public_values][zkvm: u8]
+//! [ver_len: u64 LE][version][program_id: 32][proof_type: u8][flags: u8]
+//! ```
+//!
+//! The lowest bit of `flags` marks receipts generated in dev mode; the other bits are reserved
+//! and must be zero. Format version 1 has no `flags` byte, and its receipts decode as not in dev
+//! mode.
+//!
+//! Receipts written before the container existed consist of a bare `body` with no header or
+//! checksum. They are still accepted by [`ProofReceiptWithMetadata::decode`] and treated as
+//! format version 0. The magic is chosen so that it can never be mistaken for the leading
+//! `proof_len` of such a receipt: read as a little-endian `u64` it is larger than any proof that
+//! fits in memory.
+//!
+//! Both directions are streaming: the checksum is computed while bytes are written or read, so
+//! receipts never need to be buffered in full on top of their decoded form.
+
+use std::io::{self, ErrorKind, Read, Write};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    ProgramId, Proof, ProofMetadata, ProofReceipt, ProofReceiptWithMetadata, ProofType,
+    PublicValues, ReceiptDecodeError, ZkVm,
+};
+
+/// Magic bytes that open every versioned receipt container.
+pub const RECEIPT_MAGIC: [u8; 8] = [0x89, b'Z', b'K', b'R', b'C', b'P', b'T', 0x0a];
+
+/// Format version written by [`ProofReceiptWithMetadata::encode`].
+pub const RECEIPT_FORMAT_VERSION: u16 = 2;
+
+/// The format version before the `flags` byte was added.
+const FORMAT_VERSION_WITHOUT_FLAGS: u16 = 1;
+
+/// Bit of the `flags` byte set for receipts generated in dev mode.
+const DEV_MODE_FLAG: u8 = 1;
+
+/// Length of the SHA-256 checksum trailer.
+const CHECKSUM_LEN: usize = 32;
+
+/// Upper bound on the buffer reserved up front for a length-prefixed field.
+///
+/// Larger fields still decode, the buffer just grows as bytes arrive. This keeps a corrupted
+/// length prefix from triggering a huge allocation before any payload has been read.
+const MAX_PREALLOC: u64 = 64 * 1024 * 1024;
+
+impl ProofReceiptWithMetadata {
+    /// Encodes the receipt into the current versioned container format.
+    ///
+    /// Layout: `[magic: 8][format_version: u16 LE][body][checksum: 32]`, where `body` is
+    /// `[proof_len: u64 LE][proof][pv_len: u64 LE][public_values][zkvm: u8][ver_len: u64
+    /// LE][version][program_id: 32][proof_type: u8][flags: u8]` and `checksum` is the SHA-256
+    /// digest of everything before it.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        self.encode_to(&mut buf)
+            .expect("writing to a Vec never fails");
+        buf
+    }
+
+    /// Streams the receipt into `writer` using the layout described in [`encode`](Self::encode).
+    ///
+    /// The writer is flushed once the checksum has been written. Callers that need durability
+    /// (e.g. for files) are responsible for syncing the underlying sink.
+    pub fn encode_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = HashingWriter::new(writer);
+        writer.write_all(&RECEIPT_MAGIC)?;
+        writer.write_all(&RECEIPT_FORMAT_VERSION.to_le_bytes())?;
+        write_body(self, &mut writer)?;
+        writer.write_all(&[flags(self.metadata())])?;
+
+        let (mut inner, checksum) = writer.finalize();
+        inner.write_all(&checksum)?;
+        inner.flush()
+    }
+
+    /// Decodes a receipt produced by [`encode`](Self::encode).
+    ///
+    /// Receipts without a container header are decoded as format version 0. In both cases the
+    /// whole input must be consumed; trailing bytes are rejected.
+    pub fn decode(data: &[u8]) -> Result<Self, ReceiptDecodeError> {
+        Self::decode_from(data)
+    }
+
+    /// Streams a receipt out of `reader`, with the same rules as [`decode`](Self::decode).
+    ///
+    /// The reader is drained to its end so that trailing bytes can be detected. Wrap unbuffered
+    /// sources such as [`File`](std::fs::File) in a [`BufReader`](std::io::BufReader).
+    pub fn decode_from<R: Read>(reader: R) -> Result<Self, ReceiptDecodeError> {
+        let mut reader = Reader::new(reader);
+
+        let lead: [u8; 8] = reader.array("magic")?;
+        if lead != RECEIPT_MAGIC {
+            // A legacy receipt: the eight bytes just read are the proof length.
+            let proof = reader.bytes(u64::from_le_bytes(lead), "proof", 0)?;
+            let raw = RawReceipt::read_after_proof(&mut reader, proof)?;
+            reader.finish()?;
+            return raw.into_receipt();
+        }
+
+        let version_offset = reader.offset;
+        let version = u16::from_le_bytes(reader.array("format_version")?);
+        // Version 0 is implied by the absence of a header, so it is never valid inside one.
+        if !matches!(
+            version,
+            FORMAT_VERSION_WITHOUT_FLAGS | RECEIPT_FORMAT_VERSION
+        ) {
+            return Err(ReceiptDecodeError::UnsupportedVersion {
+                offset: version_offset,
+                version,
+            });
+        }
+
+        let proof = reader.len_prefixed("proof")?;
+        let mut raw = RawReceipt::read_after_proof(&mut reader, proof)?;
+        if version != FORMAT_VERSION_WITHOUT_FLAGS {
+            raw.flags = (reader.offset, reader.array::<1>("flags")?[0]);
+        }
+        let checksum_offset = reader.offset;
+        let computed = reader.take_checksum();
+        let checksum: [u8; CHECKSUM_LEN] = reader.array("checksum")?;
+        reader.finish()?;
+
+        if computed != checksum {
+            return Err(ReceiptDecodeError::ChecksumMismatch {
+                offset: checksum_offset,
+            });
+        }
+
+        raw.into_receipt()
+    }
+
+    /// Returns the number of bytes [`encode`](Self::encode) produces for this receipt.
+    fn encoded_len(&self) -> usize {
+        let proof = self.receipt().proof().as_bytes().len();
+        let pv = self.receipt().public_values().as_bytes().len();
+        let version = self.metadata().version().len();
+        let body_len = 8 + proof + 8 + pv + 1 + 8 + version + 32 + 1 + 1;
+        RECEIPT_MAGIC.len() + 2 + body_len + CHECKSUM_LEN
+    }
+}
+
+/// Writes the receipt body shared by every format version to `writer`.
+fn write_body<W: Write>(receipt: &ProofReceiptWithMetadata, writer: &mut W) -> io::Result<()> {
+    let proof = receipt.receipt().proof().as_bytes();
+    let pv = receipt.receipt().public_values().as_bytes();
+    let metadata = receipt.metadata();
+    let version = metadata.version().as_bytes();
+
+    writer.write_all(&(proof.len() as u64).to_le_bytes())?;
+    writer.write_all(proof)?;
+    writer.write_all(&(pv.len() as u64).to_le_bytes())?;
+    writer.write_all(pv)?;
+    writer.write_all(&[*metadata.zkvm() as u8])?;
+    writer.write_all(&(version.len() as u64).to_le_bytes())?;
+    writer.write_all(version)?;
+    writer.write_all(&metadata.program_id().0)?;
+    writer.write_all(&[metadata.proof_type() as u8])
+}
+
+/// Returns the `flags` byte of a receipt with `metadata`.
+fn flags(metadata: &ProofMetadata) -> u8 {
+    if metadata.is_dev_mode() {
+        DEV_MODE_FLAG
+    } else {
+        0
+    }
+}
+
+/// Receipt body fields as read from the wire, before tags and strings are validated.
+///
+/// Validation is deferred so that a corrupted versioned container is reported as a checksum
+/// mismatch rather than as whichever field the corruption happened to land in.
+struct RawReceipt {
+    proof: Vec<u8>,
+    public_values: Vec<u8>,
+    zkvm: (usize, u8),
+    version: (usize, Vec<u8>),
+    program_id: [u8; 32],
+    proof_type: (usize, u8),
+    /// Zero for format versions without a `flags` byte.
+    flags: (usize, u8),
+}
+
+impl RawReceipt {
+    /// Reads the body fields that follow the proof.
+    fn read_after_proof<R: Read>(
+        reader: &mut Reader<R>,
+        proof: Vec<u8>,
+    ) -> Result<Self, ReceiptDecodeError> {
+        let public_values = reader.len_prefixed("public_values")?;
+        let zkvm = (reader.offset, reader.array::<1>("zkvm")?[0]);
+        let version = (reader.offset, reader.len_prefixed("version")?);
+        let program_id = reader.array("program_id")?;
+        let proof_type = (reader.offset, reader.array::<1>("proof_type")?[0]);
+
+        Ok(Self {
+            proof,
+            public_values,
+            zkvm,
+            version,
+            program_id,
+            proof_type,
+            flags: (reader.offset, 0),
+        })
+    }
+
+    fn into_receipt(self) -> Result<ProofReceiptWithMetadata, ReceiptDecodeError> {
+        let invalid = |offset, field, reason: String| ReceiptDecodeError::InvalidField {
+            offset,
+            field,
+            reason,
+        };
+
+        let (offset, tag) = self.zkvm;
+        let zkvm = ZkVm::try_from(tag).map_err(|e| invalid(offset, "zkvm", e.to_string()))?;
+
+        let (offset, bytes) = self.version;
+        let version =
+            String::from_utf8(bytes).map_err(|e| invalid(offset, "version", e.to_string()))?;
+
+        let (offset, tag) = self.proof_type;
+        let proof_type =
+            ProofType::try_from(tag).map_err(|e| invalid(offset, "proof_type", e.to_string()))?;
+
+        let (offset, flags) = self.flags;
+        if flags & !DEV_MODE_FLAG != 0 {
+            return Err(invalid(
+                offset,
+                "flags",
+                format!("unknown flags {:#04x}", flags & !DEV_MODE_FLAG),
+            ));
+        }
+
+        Ok(ProofReceiptWithMetadata::new(
+            ProofReceipt::new(
+                Proof::new(self.proof),
+                PublicValues::new(self.public_values),
+            ),
+            ProofMetadata::new(zkvm, ProgramId(self.program_id), version, proof_type)
+                .with_dev_mode(flags & DEV_MODE_FLAG != 0),
+        ))
+    }
+}
+
+/// Reader over an encoded receipt that tracks the absolute offset for error reporting and
+/// hashes every byte it hands out until [`take_checksum`](Self::take_checksum) is called.
+struct Reader<R> {
+    inner: R,
+    offset: usize,
+    hasher: Option<Sha256>,
+}
+
+impl<R: Read> Reader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            offset: 0,
+            hasher: Some(Sha256::new()),
+        }
+    }
+
+    fn array<const N: usize>(
+        &mut self,
+        field: &'static str,
+    ) -> Result<[u8; N], ReceiptDecodeError> {
+        let mut buf = [0u8; N];
+        self.inner
+            .read_exact(&mut buf)
+            .map_err(|e| read_error(e, self.offset, field))?;
+        self.consumed(&buf);
+        Ok(buf)
+    }
+
+    /// Reads exactly `len` bytes belonging to the field that starts at `start`.
+    fn bytes(
+        &mut self,
+        len: u64,
+        field: &'static str,
+        start: usize,
+    ) -> Result<Vec<u8>, ReceiptDecodeError> {
+        let mut buf = Vec::with_capacity(len.min(MAX_PREALLOC) as usize);
+        let read = (&mut self.inner)
+            .take(len)
+            .read_to_end(&mut buf)
+            .map_err(|e| read_error(e, start, field))?;
+        if (read as u64) < len {
+            return Err(ReceiptDecodeError::UnexpectedEof {
+                offset: start,
+                field,
+            });
+        }
+        self.consumed(&buf);
+        Ok(buf)
+    }
+
+    /// Reads a `u64 LE` length followed by that many bytes.
+    fn len_prefixed(&mut self, field: &'static str) -> Result<Vec<u8>, ReceiptDecodeError> {
+        let start = self.offset;
+        let len = u64::from_le_bytes(self.array(field)?);
+        self.bytes(len, field, start)
+    }
+
+    /// Stops hashing and returns the digest of everything read so far.
+    fn take_checksum(&mut self) -> [u8; CHECKSUM_LEN] {
+        self.hasher
+            .take()
+            .expect("checksum is taken once")
+            .finalize()
+            .into()
+    }
+
+    /// Fails if any input remains unread.
+    fn finish(mut self) -> Result<(), ReceiptDecodeError> {
+        let len = io::copy(&mut self.inner, &mut io::sink())
+            .map_err(|e| read_error(e, self.offset, "trailing data"))?;
+        if len != 0 {
+            return Err(ReceiptDecodeError::TrailingData {
+                offset: self.offset,
+                len: len as usize,
+            });
+        }
+        Ok(())
+    }
+
+    fn consumed(&mut self, buf: &[u8]) {
+        self.offset += buf.len();
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(buf);
+        }
+    }
+}
+
+/// Maps an I/O error hit while reading `field` at `offset` to a [`ReceiptDecodeError`].
+fn read_error(err: io::Error, offset: usize, field: &'static str) -> ReceiptDecodeError {
+    if err.kind() == ErrorKind::UnexpectedEof {
+        ReceiptDecodeError::UnexpectedEof { offset, field }
+    } else {
+        ReceiptDecodeError::Io {
+            offset,
+            field,
+            source: err,
+        }
+    }
+}
+
+/// Writer adapter that hashes every byte written through it.
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Returns the inner writer and the digest of everything written so far.
+    fn finalize(self) -> (W, [u8; CHECKSUM_LEN]) {
+        (self.inner, self.hasher.finalize().into())
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ProofReceiptWithMetadata {
+        ProofReceiptWithMetadata::new(
+            ProofReceipt::new(Proof::new(vec![1, 2, 3]), PublicValues::new(vec![4, 5])),
+            ProofMetadata::new(
+                ZkVm::SP1,
+                ProgramId([7; 32]),
+                "v1.0.0",
+                ProofType::Compressed,
+            ),
+        )
+    }
+
+    fn legacy_encode(receipt: &ProofReceiptWithMetadata) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_body(receipt, &mut buf).unwrap();
+        buf
+    }
+
+    /// Encodes `receipt` as format version 1, which has no `flags` byte.
+    fn v1_encode(receipt: &ProofReceiptWithMetadata) -> Vec<u8> {
+        let mut buf = RECEIPT_MAGIC.to_vec();
+        buf.extend_from_slice(&FORMAT_VERSION_WITHOUT_FLAGS.to_le_bytes());
+        write_body(receipt, &mut buf).unwrap();
+        let checksum = Sha256::digest(&buf);
+        buf.extend_from_slice(&checksum);
+        buf
+    }
+
+    /// Reader that hands out at most one byte per call, to exercise short reads.
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            match buf.first_mut() {
+                Some(slot) => {
+                    *slot = *first;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+    }
+
+    /// Reader that fails with a non-EOF error once `remaining` bytes have been served.
+    struct FailAfter<'a> {
+        data: &'a [u8],
+        remaining: usize,
+    }
+
+    impl Read for FailAfter<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.remaining == 0 {
+                return Err(io::Error::other("disk on fire"));
+            }
+            let n = buf.len().min(self.remaining).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            self.remaining -= n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn encode_writes_header_and_checksum() {
+        let encoded = sample().encode();
+        assert!(encoded.starts_with(&RECEIPT_MAGIC));
+        assert_eq!(
+            encoded[RECEIPT_MAGIC.len()..RECEIPT_MAGIC.len() + 2],
+            RECEIPT_FORMAT_VERSION.to_le_bytes()
+        );
+        let (body, checksum) = encoded.split_at(encoded.len() - CHECKSUM_LEN);
+        assert_eq!(Sha256::digest(body)[..], *checksum);
+    }
+
+    #[test]
+    fn encode_to_matches_encode() {
+        let receipt = sample();
+        let mut streamed = Vec::new();
+        receipt.encode_to(&mut streamed).unwrap();
+        assert_eq!(streamed, receipt.encode());
+        assert_eq!(streamed.len(), receipt.encoded_len());
+    }
+
+    #[test]
+    fn decode_from_handles_short_reads() {
+        let original = sample();
+        let encoded = original.encode();
+        let decoded = ProofReceiptWithMetadata::decode_from(ByteByByte(&encoded)).unwrap();
+        assert_eq!(decoded, original);
+
+        let legacy = legacy_encode(&original);
+        let decoded = ProofReceiptWithMetadata::decode_from(ByteByByte(&legacy)).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn reports_reader_errors_with_offset() {
+        let encoded = sample().encode();
+        let reader = FailAfter {
+            data: &encoded,
+            remaining: 10,
+        };
+        let err = ProofReceiptWithMetadata::decode_from(reader).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::Io {
+                offset: 10,
+                field: "proof",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn oversized_length_prefix_is_eof_not_oom() {
+        let mut encoded = sample().encode();
+        encoded[10..18].copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+        let err = ProofReceiptWithMetadata::decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::UnexpectedEof {
+                offset: 10,
+                field: "proof"
+            }
+        ));
+    }
+
+    #[test]
+    fn decodes_legacy_layout_as_v0() {
+        let original = sample();
+        let decoded = ProofReceiptWithMetadata::decode(&legacy_encode(&original)).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn decodes_v1_as_not_dev_mode() {
+        let original = sample();
+        let decoded = ProofReceiptWithMetadata::decode(&v1_encode(&original)).unwrap();
+        assert_eq!(decoded, original);
+        assert!(!decoded.metadata().is_dev_mode());
+    }
+
+    #[test]
+    fn round_trips_dev_mode_flag() {
+        let original = sample();
+        let dev = ProofReceiptWithMetadata::new(
+            original.receipt().clone(),
+            original.metadata().clone().with_dev_mode(true),
+        );
+        let encoded = dev.encode();
+        assert_eq!(encoded[encoded.len() - CHECKSUM_LEN - 1], DEV_MODE_FLAG);
+        let decoded = ProofReceiptWithMetadata::decode(&encoded).unwrap();
+        assert!(decoded.metadata().is_dev_mode());
+        assert_eq!(decoded, dev);
+        assert!(
+            !ProofReceiptWithMetadata::decode(&original.encode())
+                .unwrap()
+                .metadata()
+                .is_dev_mode()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let mut encoded = sample().encode();
+        let flags_offset = encoded.len() - CHECKSUM_LEN - 1;
+        encoded[flags_offset] = 0x80;
+        let (body, checksum) = encoded.split_at_mut(flags_offset + 1);
+        checksum.copy_from_slice(&Sha256::digest(body));
+        let err = ProofReceiptWithMetadata::decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::InvalidField {
+                offset,
+                field: "flags",
+                ..
+            } if offset == flags_offset
+        ));
+    }
+
+    #[test]
+    fn decodes_checked_in_legacy_proof() {
+        let bytes = include_bytes!(
+            "../../adapters/sp1/groth16-verifier/proofs/fibonacci_SP1_v6.1.0.proof.bin"
+        );
+        let receipt = ProofReceiptWithMetadata::decode(bytes).unwrap();
+        assert_eq!(receipt.metadata().zkvm(), &ZkVm::SP1);
+        assert_eq!(receipt.metadata().proof_type(), ProofType::Groth16);
+    }
+
+    #[test]
+    fn rejects_trailing_data() {
+        let mut encoded = sample().encode();
+        let len = encoded.len();
+        encoded.push(0);
+        let err = ProofReceiptWithMetadata::decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::TrailingData { offset, len: 1 } if offset == len
+        ));
+
+        let mut legacy = legacy_encode(&sample());
+        let len = legacy.len();
+        legacy.extend_from_slice(&[0, 0]);
+        let err = ProofReceiptWithMetadata::decode(&legacy).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::TrailingData { offset, len: 2 } if offset == len
+        ));
+    }
+
+    #[test]
+    fn rejects_corrupted_payload() {
+        let mut encoded = sample().encode();
+        // Flip a bit inside the proof bytes.
+        encoded[RECEIPT_MAGIC.len() + 2 + 8] ^= 1;
+        let err = ProofReceiptWithMetadata::decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::ChecksumMismatch { offset } if offset == encoded.len() - CHECKSUM_LEN
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut encoded = sample().encode();
+        encoded[RECEIPT_MAGIC.len()..RECEIPT_MAGIC.len() + 2].copy_from_slice(&99u16.to_le_bytes());
+        let err = ProofReceiptWithMetadata::decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::UnsupportedVersion {
+                offset: 8,
+                version: 99
+            }
+        ));
+    }
+
+    #[test]
+    fn reports_offset_and_field_on_truncation() {
+        let encoded = sample().encode();
+        // Cut inside the public values: header (10) + proof (8 + 3) + pv length (8) + 1 byte.
+        let truncated = &encoded[..10 + 11 + 8 + 1];
+        let err = ProofReceiptWithMetadata::decode(truncated).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::UnexpectedEof {
+                offset: 21,
+                field: "public_values"
+            }
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_tag() {
+        let mut legacy = legacy_encode(&sample());
+        // proof (8 + 3) + public values (8 + 2)
+        let zkvm_offset = 21;
+        legacy[zkvm_offset] = 0xff;
+        let err = ProofReceiptWithMetadata::decode(&legacy).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiptDecodeError::InvalidField {
+                offset: 21,
+                field: "zkvm",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+//! A type-erased host interface for selecting the zkVM backend at runtime.
+
+use std::fmt::Debug;
+
+use crate::{
+    AggregationInput, ExecutionSummary, ProgramId, ProofReceiptWithMetadata, ProofType, RunConfig,
+    VerifyingKey, ZkVm, ZkVmExecutor, ZkVmHost, ZkVmInputBuilder, ZkVmInputResult, ZkVmProver,
+    ZkVmResult, ZkVmTypedVerifier, ZkVmVkProvider,
+};
+
+/// A single raw input handed to the guest, in the order the guest reads it.
+///
+/// A list of frames is the backend-independent equivalent of the input built by a
+/// [`ZkVmInputBuilder`]: every frame is replayed onto the builder of the selected host.
+#[derive(Debug, Clone)]
+pub enum InputFrame {
+    /// A pre-serialized buffer, written with [`ZkVmInputBuilder::write_buf`] and read by the
+    /// guest with [`ZkVmEnv::read_buf`](crate::ZkVmEnv::read_buf).
+    Buf(Vec<u8>),
+
+    /// A proof to be verified by the guest, written with [`ZkVmInputBuilder::write_proof`] and
+    /// read with [`ZkVmEnv::read_verified_buf`](crate::ZkVmEnv::read_verified_buf).
+    Proof(AggregationInput),
+}
+
+impl InputFrame {
+    /// Writes this frame to `builder`.
+    pub fn write_to<'a, B: ZkVmInputBuilder<'a>>(&self, builder: &mut B) -> ZkVmInputResult<()> {
+        match self {
+            InputFrame::Buf(buf) => builder.write_buf(buf)?,
+            InputFrame::Proof(proof) => builder.write_proof(proof)?,
+        };
+        Ok(())
+    }
+}
+
+impl From<Vec<u8>> for InputFrame {
+    fn from(buf: Vec<u8>) -> Self {
+        InputFrame::Buf(buf)
+    }
+}
+
+impl From<AggregationInput> for InputFrame {
+    fn from(proof: AggregationInput) -> Self {
+        InputFrame::Proof(proof)
+    }
+}
+
+/// Builds the input of `B` from a list of frames.
+pub fn build_input_from_frames<'a, B: ZkVmInputBuilder<'a>>(
+    frames: &[InputFrame],
+) -> ZkVmInputResult<B::Input> {
+    let mut builder = B::new();
+    for frame in frames {
+        frame.write_to(&mut builder)?;
+    }
+    builder.build()
+}
+
+/// An object-safe counterpart of [`ZkVmHost`].
+///
+/// [`ZkVmHost`] cannot be used as a trait object because of its generic input type and output
+/// extractors. This trait takes raw [`InputFrame`]s instead and is implemented for every
+/// [`ZkVmHost`], so the backend can be chosen at runtime behind a `Box<dyn DynZkVmHost>`.
+///
+/// The methods carry a `dyn_` prefix so they do not clash with the [`ZkVmHost`] methods when
+/// both traits are in scope. Public values are returned as bytes; outputs are decoded by the
+/// caller, for example with [`ZkVmCodec`](crate::ZkVmCodec)s that do not depend on the backend.
+pub trait DynZkVmHost: Send + Sync + Debug {
+    /// Returns the [`ZkVm`] backend this host represents.
+    fn dyn_zkvm(&self) -> ZkVm;
+
+    /// Returns the program identifier of the loaded program.
+    fn dyn_program_id(&self) -> ProgramId;
+
+    /// Returns the verification key of the loaded program.
+    fn dyn_vk(&self) -> VerifyingKey;
+
+    /// Executes the guest on `frames` without generating a proof.
+    fn dyn_execute(
+        &self,
+        frames: &[InputFrame],
+        config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary>;
+
+    /// Executes the guest on `frames` and generates a proof of type `proof_type`.
+    fn dyn_prove(
+        &self,
+        frames: &[InputFrame],
+        proof_type: ProofType,
+        config: &RunConfig,
+    ) -> ZkVmResult<ProofReceiptWithMetadata>;
+
+    /// Compresses a receipt produced by this host, see [`ZkVmProver::compress`].
+    fn dyn_compress(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata>;
+
+    /// Wraps a receipt produced by this host into a Groth16 receipt, see
+    /// [`ZkVmProver::wrap_groth16`].
+    fn dyn_wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata>;
+
+    /// Verifies a receipt produced by this host.
+    fn dyn_verify(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()>;
+}
+
+impl<H: ZkVmHost> DynZkVmHost for H {
+    fn dyn_zkvm(&self) -> ZkVm {
+        ZkVmHost::zkvm(self)
+    }
+
+    fn dyn_program_id(&self) -> ProgramId {
+        ZkVmExecutor::program_id(self)
+    }
+
+    fn dyn_vk(&self) -> VerifyingKey {
+        ZkVmVkProvider::vk(self)
+    }
+
+    fn dyn_execute(
+        &self,
+        frames: &[InputFrame],
+        config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary> {
+        let input = build_input_from_frames::<H::Input<'_>>(frames)?;
+        ZkVmExecutor::execute(self, input, config)
+    }
+
+    fn dyn_prove(
+        &self,
+        frames: &[InputFrame],
+        proof_type: ProofType,
+        config: &RunConfig,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        let input = build_input_from_frames::<H::Input<'_>>(frames)?;
+        ZkVmProver::prove(self, input, proof_type, config)
+    }
+
+    fn dyn_compress(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        ZkVmProver::compress(self, receipt)
+    }
+
+    fn dyn_wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        ZkVmProver::wrap_groth16(self, receipt)
+    }
+
+    fn dyn_verify(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        ZkVmTypedVerifier::verify(self, receipt)
+    }
+}
@@ -0,0 +1,315 @@
+//! Up-front validation of guest ELFs.
+//!
+//! zkVM SDKs tend to fail deep inside program setup, often with a panic, when they are handed the
+//! wrong artifact. Hosts check the ELF header against an [`ElfTarget`] first, so that loading a
+//! binary built for another target is reported as a recoverable [`ZkVmError::InvalidELF`].
+//!
+//! Only little-endian executables are accepted, which covers every RISC-V zkVM target.
+
+use crate::{ZkVmError, ZkVmResult};
+
+/// `e_machine` of RISC-V executables.
+pub const EM_RISCV: u16 = 243;
+
+/// `e_type` of executables.
+const ET_EXEC: u16 = 2;
+
+/// `p_type` of loadable segments.
+const PT_LOAD: u32 = 1;
+
+/// `p_flags` bit of executable segments.
+const PF_X: u32 = 1;
+
+/// Width of the addresses and offsets of an ELF file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfClass {
+    /// 32-bit ELF (`ELFCLASS32`).
+    Elf32,
+    /// 64-bit ELF (`ELFCLASS64`).
+    Elf64,
+}
+
+/// The kind of ELF a zkVM host loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfTarget {
+    /// Name of the target, used in error messages.
+    pub name: &'static str,
+    /// Required ELF class.
+    pub class: ElfClass,
+    /// Required `e_machine`.
+    pub machine: u16,
+    /// Sections the executable must contain.
+    pub required_sections: &'static [&'static str],
+}
+
+impl ElfTarget {
+    /// Checks that `elf` is a little-endian executable for this target, whose entry point lies in
+    /// an executable segment and which contains every required section.
+    ///
+    /// Returns [`ZkVmError::InvalidELF`] describing the first mismatch.
+    pub fn validate(&self, elf: &[u8]) -> ZkVmResult<()> {
+        self.check(elf)
+            .map_err(|reason| ZkVmError::InvalidELF(format!("not a {} ELF: {reason}", self.name)))
+    }
+
+    fn check(&self, bytes: &[u8]) -> Result<(), String> {
+        if bytes.get(..4) != Some(b"\x7fELF".as_slice()) {
+            return Err("missing ELF magic".to_string());
+        }
+        let class = match bytes.get(4) {
+            Some(1) => ElfClass::Elf32,
+            Some(2) => ElfClass::Elf64,
+            other => return Err(format!("unknown ELF class {other:?}")),
+        };
+        if class != self.class {
+            return Err(format!("expected {:?}, got {class:?}", self.class));
+        }
+        if bytes.get(5) != Some(&1) {
+            return Err("not little-endian".to_string());
+        }
+        let elf = Elf { bytes, class };
+
+        let e_type = elf.u16(16)?;
+        if e_type != ET_EXEC {
+            return Err(format!(
+                "expected an executable (type {ET_EXEC}), got type {e_type}"
+            ));
+        }
+        let machine = elf.u16(18)?;
+        if machine != self.machine {
+            return Err(format!(
+                "expected machine {}, got machine {machine}",
+                self.machine
+            ));
+        }
+
+        let entry = elf.word(24)?;
+        let in_segment =
+            |&(vaddr, memsz): &(u64, u64)| (vaddr..vaddr.saturating_add(memsz)).contains(&entry);
+        if !elf.segments()?.iter().any(in_segment) {
+            return Err(format!(
+                "entry point {entry:#x} is outside every executable segment"
+            ));
+        }
+
+        let sections = elf.section_names()?;
+        for required in self.required_sections {
+            if !sections.contains(&required.as_bytes()) {
+                return Err(format!("missing section {required}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A little-endian ELF file whose class is known.
+struct Elf<'a> {
+    bytes: &'a [u8],
+    class: ElfClass,
+}
+
+impl<'a> Elf<'a> {
+    fn slice(&self, offset: u64, len: u64) -> Result<&'a [u8], String> {
+        let end = offset.checked_add(len);
+        usize::try_from(offset)
+            .ok()
+            .zip(end.and_then(|end| usize::try_from(end).ok()))
+            .and_then(|(start, end)| self.bytes.get(start..end))
+            .ok_or_else(|| format!("truncated at offset {offset:#x}"))
+    }
+
+    fn u16(&self, offset: u64) -> Result<u16, String> {
+        let bytes = self.slice(offset, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32, String> {
+        let bytes = self.slice(offset, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads an address or offset, whose width depends on the class.
+    fn word(&self, offset: u64) -> Result<u64, String> {
+        match self.class {
+            ElfClass::Elf32 => self.u32(offset).map(u64::from),
+            ElfClass::Elf64 => {
+                let bytes = self.slice(offset, 8)?;
+                let mut word = [0u8; 8];
+                word.copy_from_slice(bytes);
+                Ok(u64::from_le_bytes(word))
+            }
+        }
+    }
+
+    /// Returns the offsets of `e_phoff`, `e_shoff` and `e_phentsize`, which depend on the class.
+    fn header_offsets(&self) -> (u64, u64, u64) {
+        match self.class {
+            ElfClass::Elf32 => (28, 32, 42),
+            ElfClass::Elf64 => (32, 40, 54),
+        }
+    }
+
+    /// Returns the table at the offset stored at `offset_field`, whose entry size and count
+    /// follow at `size_field`.
+    fn table(&self, offset_field: u64, size_field: u64) -> Result<(u64, u64, u64), String> {
+        let offset = self.word(offset_field)?;
+        let entry_size = u64::from(self.u16(size_field)?);
+        let count = u64::from(self.u16(size_field + 2)?);
+        Ok((offset, entry_size, count))
+    }
+
+    /// Returns `(p_vaddr, p_memsz)` of every executable loadable segment.
+    fn segments(&self) -> Result<Vec<(u64, u64)>, String> {
+        let (phoff, _, phentsize) = self.header_offsets();
+        let (offset, entry_size, count) = self.table(phoff, phentsize)?;
+        // (p_flags, p_vaddr, p_memsz)
+        let fields = match self.class {
+            ElfClass::Elf32 => (24, 8, 20),
+            ElfClass::Elf64 => (4, 16, 40),
+        };
+        let mut segments = Vec::new();
+        for i in 0..count {
+            let header = offset.saturating_add(i * entry_size);
+            if self.u32(header)? == PT_LOAD
+                && self.u32(header.saturating_add(fields.0))? & PF_X != 0
+            {
+                segments.push((
+                    self.word(header.saturating_add(fields.1))?,
+                    self.word(header.saturating_add(fields.2))?,
+                ));
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Returns the names of every section, read from the section header string table.
+    fn section_names(&self) -> Result<Vec<&'a [u8]>, String> {
+        let (_, shoff, phentsize) = self.header_offsets();
+        // e_shentsize follows e_phentsize and e_phnum.
+        let (offset, entry_size, count) = self.table(shoff, phentsize + 4)?;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let shstrndx = u64::from(self.u16(phentsize + 8)?);
+        if shstrndx >= count {
+            return Err(format!(
+                "section name table index {shstrndx} is out of range"
+            ));
+        }
+
+        // (sh_offset, sh_size)
+        let fields = match self.class {
+            ElfClass::Elf32 => (16, 20),
+            ElfClass::Elf64 => (24, 32),
+        };
+        let strtab_header = offset.saturating_add(shstrndx * entry_size);
+        let strtab = self.slice(
+            self.word(strtab_header.saturating_add(fields.0))?,
+            self.word(strtab_header.saturating_add(fields.1))?,
+        )?;
+
+        let mut names = Vec::new();
+        for i in 0..count {
+            let name = usize::try_from(self.u32(offset.saturating_add(i * entry_size))?)
+                .ok()
+                .and_then(|start| strtab.get(start..))
+                .ok_or("section name is outside the section name table")?;
+            let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            names.push(&name[..len]);
+        }
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: ElfTarget = ElfTarget {
+        name: "test",
+        class: ElfClass::Elf64,
+        machine: EM_RISCV,
+        required_sections: &[".text"],
+    };
+
+    /// A 64-bit executable with one executable segment at `0x1000` and the sections `.text` and
+    /// `.shstrtab`.
+    fn elf64(machine: u16, entry: u64) -> Vec<u8> {
+        let strtab = b"\0.text\0.shstrtab\0";
+        let mut elf = vec![0u8; 64];
+        elf[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        elf[16..18].copy_from_slice(&ET_EXEC.to_le_bytes());
+        elf[18..20].copy_from_slice(&machine.to_le_bytes());
+        elf[24..32].copy_from_slice(&entry.to_le_bytes());
+        // One program header right after the file header.
+        elf[32..40].copy_from_slice(&64u64.to_le_bytes());
+        elf[54..56].copy_from_slice(&56u16.to_le_bytes());
+        elf[56..58].copy_from_slice(&1u16.to_le_bytes());
+
+        let mut phdr = [0u8; 56];
+        phdr[..4].copy_from_slice(&PT_LOAD.to_le_bytes());
+        phdr[4..8].copy_from_slice(&PF_X.to_le_bytes());
+        phdr[16..24].copy_from_slice(&0x1000u64.to_le_bytes());
+        phdr[40..48].copy_from_slice(&0x100u64.to_le_bytes());
+        elf.extend_from_slice(&phdr);
+
+        let strtab_offset = elf.len() as u64;
+        elf.extend_from_slice(strtab);
+
+        // Section headers: the null section, `.text` and `.shstrtab`.
+        let shoff = elf.len() as u64;
+        elf[40..48].copy_from_slice(&shoff.to_le_bytes());
+        elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+        elf[60..62].copy_from_slice(&3u16.to_le_bytes());
+        elf[62..64].copy_from_slice(&2u16.to_le_bytes());
+        for (name, offset, size) in [
+            (0u32, 0u64, 0u64),
+            (1, 0, 0),
+            (7, strtab_offset, strtab.len() as u64),
+        ] {
+            let mut shdr = [0u8; 64];
+            shdr[..4].copy_from_slice(&name.to_le_bytes());
+            shdr[24..32].copy_from_slice(&offset.to_le_bytes());
+            shdr[32..40].copy_from_slice(&size.to_le_bytes());
+            elf.extend_from_slice(&shdr);
+        }
+        elf
+    }
+
+    fn reason(result: ZkVmResult<()>) -> String {
+        match result {
+            Err(ZkVmError::InvalidELF(reason)) => reason,
+            other => panic!("expected InvalidELF, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_matching_elf() {
+        TARGET.validate(&elf64(EM_RISCV, 0x1000)).unwrap();
+        TARGET.validate(&elf64(EM_RISCV, 0x10ff)).unwrap();
+    }
+
+    #[test]
+    fn rejects_mismatched_elf() {
+        let elf = elf64(EM_RISCV, 0x1000);
+
+        assert!(reason(TARGET.validate(b"not an elf")).contains("magic"));
+        assert!(reason(TARGET.validate(&elf[..40])).contains("truncated"));
+
+        let elf32 = ElfTarget {
+            class: ElfClass::Elf32,
+            ..TARGET
+        };
+        assert!(reason(elf32.validate(&elf)).contains("expected Elf32, got Elf64"));
+
+        // x86-64
+        assert!(reason(TARGET.validate(&elf64(62, 0x1000))).contains("got machine 62"));
+        assert!(reason(TARGET.validate(&elf64(EM_RISCV, 0x1100))).contains("entry point"));
+
+        let data = ElfTarget {
+            required_sections: &[".text", ".data"],
+            ..TARGET
+        };
+        assert!(reason(data.validate(&elf)).contains("missing section .data"));
+    }
+}
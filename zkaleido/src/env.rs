@@ -5,6 +5,10 @@ use serde::{Serialize, de::DeserializeOwned};
 #[cfg(feature = "ssz")]
 use ssz::{Decode, Encode};
 
+#[cfg(any(feature = "serde", feature = "borsh", feature = "ssz"))]
+use crate::DataFormatError;
+use crate::{ZkVmEnvError, ZkVmEnvResult};
+
 /// A trait representing a Zero-Knowledge Virtual Machine (ZkVM) interface.
 /// Provides methods for reading inputs, committing outputs, and verifying proofs
 /// within the ZkVM environment.
@@ -19,6 +23,15 @@ pub trait ZkVmEnv {
     /// The input is expected to be written with [`write_buf`](crate::ZkVmInputBuilder::write_buf).
     fn read_buf(&self) -> Vec<u8>;
 
+    /// Reads a serialized byte buffer from the guest code, returning an error instead of
+    /// panicking when no input is left.
+    ///
+    /// The default implementation delegates to [`ZkVmEnv::read_buf`], so adapters that can
+    /// detect a missing input should override it.
+    fn try_read_buf(&self) -> ZkVmEnvResult<Vec<u8>> {
+        Ok(self.read_buf())
+    }
+
     /// Commits a pre-serialized buffer to the public values stream.
     ///
     /// This method is intended for cases where the data has already been serialized
@@ -32,6 +45,20 @@ pub trait ZkVmEnv {
     /// and public values. It will panic if the proof fails to verify.
     fn verify_native_proof(&self, vk_digest: &[u32; 8], public_values: &[u8]);
 
+    /// Fallible version of [`ZkVmEnv::verify_native_proof`], returning
+    /// [`ZkVmEnvError::ProofVerification`] instead of panicking when the proof fails to verify.
+    ///
+    /// The default implementation delegates to [`ZkVmEnv::verify_native_proof`], so adapters that
+    /// can detect a failed verification should override it.
+    fn try_verify_native_proof(
+        &self,
+        vk_digest: &[u32; 8],
+        public_values: &[u8],
+    ) -> ZkVmEnvResult<()> {
+        self.verify_native_proof(vk_digest, public_values);
+        Ok(())
+    }
+
     /// Reads and verifies a committed output from another guest function.
     ///
     /// This is equivalent to calling [`ZkVmEnv::read_buf`] and [`ZkVmEnv::verify_native_proof`],
@@ -42,6 +69,49 @@ pub trait ZkVmEnv {
         self.verify_native_proof(vk_digest, &public_values_raw);
         public_values_raw
     }
+
+    /// Fallible version of [`ZkVmEnv::read_verified_buf`].
+    ///
+    /// Reads the buffer with [`ZkVmEnv::try_read_buf`] and checks it with
+    /// [`ZkVmEnv::try_verify_native_proof`].
+    fn try_read_verified_buf(&self, vk_digest: &[u32; 8]) -> ZkVmEnvResult<Vec<u8>> {
+        let public_values_raw = self.try_read_buf()?;
+        self.try_verify_native_proof(vk_digest, &public_values_raw)?;
+        Ok(public_values_raw)
+    }
+
+    /// Marks the start of a region of guest code whose cost the host reports separately.
+    ///
+    /// Regions are closed with [`ZkVmEnv::cycle_tracker_end`] using the same label, and may be
+    /// nested. SP1 reports the cycles spent in each region, and the native adapter reports the
+    /// wall-clock time in [`ExecutionStats::regions`](crate::ExecutionStats::regions).
+    ///
+    /// The default implementation does nothing.
+    fn cycle_tracker_start(&self, _label: &str) {}
+
+    /// Marks the end of a region opened with [`ZkVmEnv::cycle_tracker_start`].
+    ///
+    /// The default implementation does nothing.
+    fn cycle_tracker_end(&self, _label: &str) {}
+
+    /// Aborts the guest with the given exit code and message.
+    ///
+    /// SP1 and RISC0 halt the guest with `exit_code`, so the host sees a failed execution with a
+    /// non-zero exit code instead of a panic. The native adapter unwinds with the code and
+    /// message as the panic payload. An `exit_code` of `0` is reported as `1`, since `0` means
+    /// success.
+    ///
+    /// The default implementation panics with the code and message.
+    fn abort(&self, exit_code: u8, message: &str) -> ! {
+        panic!("guest aborted with exit code {exit_code}: {message}")
+    }
+}
+
+/// Unwraps the result of a fallible guest-side read, aborting the guest on error.
+///
+/// The exit code is taken from [`ZkVmEnvError::exit_code`].
+pub(crate) fn unwrap_or_abort<E: ZkVmEnv + ?Sized, T>(env: &E, result: ZkVmEnvResult<T>) -> T {
+    result.unwrap_or_else(|e: ZkVmEnvError| env.abort(e.exit_code(), &e.to_string()))
 }
 
 /// Extension trait for [`ZkVmEnv`] providing Serde serialization support.
@@ -50,8 +120,11 @@ pub trait ZkVmEnv {
 /// within the ZkVM environment. Default implementations use `bincode` for
 /// serialization/deserialization via the underlying buffer operations on [`ZkVmEnv`].
 ///
-/// Adapters that use a different serialization protocol (e.g. SP1 and RISC0 guest
-/// environments, which use VM-specific I/O) should override these methods.
+/// Inputs follow the zkaleido wire format, in which a serde input is a `bincode` buffer, so
+/// adapters should keep the default reads. Adapters that commit through VM-specific I/O (e.g. SP1
+/// and RISC0 guest environments) may override [`ZkVmEnvSerde::commit_serde`]; outputs that must
+/// be the same on every zkVM are committed with [`CanonicalSerdeCodec`](crate::CanonicalSerdeCodec)
+/// instead.
 #[cfg(feature = "serde")]
 pub trait ZkVmEnvSerde: ZkVmEnv {
     /// Reads a serialized object from the guest code, deserializing it using Serde.
@@ -59,19 +132,28 @@ pub trait ZkVmEnvSerde: ZkVmEnv {
     /// The input is expected to be written with
     /// [`write_serde`](crate::ZkVmInputBuilder::write_serde).
     ///
-    /// The default implementation reads a buffer via [`ZkVmEnv::read_buf`] and
-    /// deserializes it using `bincode`.
+    /// The guest aborts through [`ZkVmEnv::abort`] if no input is left or it cannot be
+    /// deserialized; use [`ZkVmEnvSerde::try_read_serde`] to handle these cases instead.
     fn read_serde<T: DeserializeOwned>(&self) -> T {
-        let buf = self.read_buf();
-        bincode::deserialize(&buf).expect("bincode deserialization failed")
+        unwrap_or_abort(self, self.try_read_serde())
+    }
+
+    /// Fallible version of [`ZkVmEnvSerde::read_serde`].
+    ///
+    /// The default implementation reads a buffer via [`ZkVmEnv::try_read_buf`] and
+    /// deserializes it using `bincode`.
+    fn try_read_serde<T: DeserializeOwned>(&self) -> ZkVmEnvResult<T> {
+        let buf = self.try_read_buf()?;
+        bincode::deserialize(&buf).map_err(|e| DataFormatError::Serde(e.to_string()).into())
     }
 
     /// Commits a Serde-serializable object to the public values stream.
     ///
     /// Values that are committed can be proven as public parameters.
     ///
-    /// The default implementation serializes using `bincode` and commits via
-    /// [`ZkVmEnv::commit_buf`].
+    /// The default implementation commits the `bincode` encoding, like
+    /// [`CanonicalSerdeCodec`](crate::CanonicalSerdeCodec). Adapters may override it with their
+    /// own encoding, so the same output can produce different public values on different zkVMs.
     fn commit_serde<T: Serialize>(&self, output: &T) {
         let bytes = bincode::serialize(output).expect("bincode serialization failed");
         self.commit_buf(&bytes);
@@ -86,13 +168,24 @@ pub trait ZkVmEnvSerde: ZkVmEnv {
     ///
     /// This is equivalent to calling [`ZkVmEnvSerde::read_serde`] and
     /// [`ZkVmEnv::verify_native_proof`], but avoids double serialization and deserialization.
-    /// The function will panic if the proof fails to verify.
     ///
-    /// The default implementation reads a verified buffer via [`ZkVmEnv::read_verified_buf`]
-    /// and deserializes it using `bincode`.
+    /// The guest aborts through [`ZkVmEnv::abort`] if the output cannot be read or deserialized,
+    /// or if the proof fails to verify.
     fn read_verified_serde<T: DeserializeOwned>(&self, vk_digest: &[u32; 8]) -> T {
-        let verified_buf = self.read_verified_buf(vk_digest);
-        bincode::deserialize(&verified_buf).expect("bincode deserialization failed")
+        unwrap_or_abort(self, self.try_read_verified_serde(vk_digest))
+    }
+
+    /// Fallible version of [`ZkVmEnvSerde::read_verified_serde`].
+    ///
+    /// The default implementation reads a verified buffer via
+    /// [`ZkVmEnv::try_read_verified_buf`] and deserializes it using `bincode`.
+    fn try_read_verified_serde<T: DeserializeOwned>(
+        &self,
+        vk_digest: &[u32; 8],
+    ) -> ZkVmEnvResult<T> {
+        let verified_buf = self.try_read_verified_buf(vk_digest)?;
+        bincode::deserialize(&verified_buf)
+            .map_err(|e| DataFormatError::Serde(e.to_string()).into())
     }
 }
 
@@ -110,9 +203,17 @@ pub trait ZkVmEnvBorsh: ZkVmEnv {
     ///
     /// The input is expected to be written with
     /// [`write_borsh`](`crate::ZkVmInputBuilder::write_borsh).
+    ///
+    /// The guest aborts through [`ZkVmEnv::abort`] if no input is left or it cannot be
+    /// deserialized; use [`ZkVmEnvBorsh::try_read_borsh`] to handle these cases instead.
     fn read_borsh<T: BorshDeserialize>(&self) -> T {
-        let buf = self.read_buf();
-        borsh::from_slice(&buf).expect("borsh deserialization failed")
+        unwrap_or_abort(self, self.try_read_borsh())
+    }
+
+    /// Fallible version of [`ZkVmEnvBorsh::read_borsh`].
+    fn try_read_borsh<T: BorshDeserialize>(&self) -> ZkVmEnvResult<T> {
+        let buf = self.try_read_buf()?;
+        Ok(borsh::from_slice(&buf).map_err(DataFormatError::from)?)
     }
 
     /// Commits a Borsh-serializable object to the public values stream.
@@ -131,10 +232,20 @@ pub trait ZkVmEnvBorsh: ZkVmEnv {
     ///
     /// This is equivalent to calling [`ZkVmEnvBorsh::read_borsh`] and
     /// [`ZkVmEnv::verify_native_proof`], but avoids double serialization and deserialization.
-    /// The function will panic if the proof fails to verify.
+    ///
+    /// The guest aborts through [`ZkVmEnv::abort`] if the output cannot be read or deserialized,
+    /// or if the proof fails to verify.
     fn read_verified_borsh<T: BorshDeserialize>(&self, vk_digest: &[u32; 8]) -> T {
-        let verified_public_values_buf = self.read_verified_buf(vk_digest);
-        borsh::from_slice(&verified_public_values_buf).expect("failed borsh deserialization")
+        unwrap_or_abort(self, self.try_read_verified_borsh(vk_digest))
+    }
+
+    /// Fallible version of [`ZkVmEnvBorsh::read_verified_borsh`].
+    fn try_read_verified_borsh<T: BorshDeserialize>(
+        &self,
+        vk_digest: &[u32; 8],
+    ) -> ZkVmEnvResult<T> {
+        let verified_public_values_buf = self.try_read_verified_buf(vk_digest)?;
+        Ok(borsh::from_slice(&verified_public_values_buf).map_err(DataFormatError::from)?)
     }
 }
 
@@ -156,9 +267,17 @@ pub trait ZkVmEnvSsz: ZkVmEnv {
     ///
     /// The input is expected to be written with
     /// [`write_ssz`](`crate::ZkVmInputBuilder::write_ssz).
+    ///
+    /// The guest aborts through [`ZkVmEnv::abort`] if no input is left or it cannot be
+    /// deserialized; use [`ZkVmEnvSsz::try_read_ssz`] to handle these cases instead.
     fn read_ssz<T: Decode>(&self) -> T {
-        let buf = self.read_buf();
-        T::from_ssz_bytes(&buf).expect("ssz deserialization failed")
+        unwrap_or_abort(self, self.try_read_ssz())
+    }
+
+    /// Fallible version of [`ZkVmEnvSsz::read_ssz`].
+    fn try_read_ssz<T: Decode>(&self) -> ZkVmEnvResult<T> {
+        let buf = self.try_read_buf()?;
+        Ok(T::from_ssz_bytes(&buf).map_err(DataFormatError::from)?)
     }
 
     /// Commits an SSZ-serializable object to the public values stream.
@@ -177,10 +296,17 @@ pub trait ZkVmEnvSsz: ZkVmEnv {
     ///
     /// This is equivalent to calling [`ZkVmEnvSsz::read_ssz`] and
     /// [`ZkVmEnv::verify_native_proof`], but avoids double serialization and deserialization.
-    /// The function will panic if the proof fails to verify.
+    ///
+    /// The guest aborts through [`ZkVmEnv::abort`] if the output cannot be read or deserialized,
+    /// or if the proof fails to verify.
     fn read_verified_ssz<T: Decode>(&self, vk_digest: &[u32; 8]) -> T {
-        let verified_buf = self.read_verified_buf(vk_digest);
-        T::from_ssz_bytes(&verified_buf).expect("ssz deserialization failed")
+        unwrap_or_abort(self, self.try_read_verified_ssz(vk_digest))
+    }
+
+    /// Fallible version of [`ZkVmEnvSsz::read_verified_ssz`].
+    fn try_read_verified_ssz<T: Decode>(&self, vk_digest: &[u32; 8]) -> ZkVmEnvResult<T> {
+        let verified_buf = self.try_read_verified_buf(vk_digest)?;
+        Ok(T::from_ssz_bytes(&verified_buf).map_err(DataFormatError::from)?)
     }
 }
 
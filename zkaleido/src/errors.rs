@@ -1,14 +1,22 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
 
 #[cfg(feature = "borsh")]
 use borsh::io::Error as BorshIoError;
 use thiserror::Error;
 
-use crate::{ProofType, ZkVm};
+use crate::{MultiProofReport, ProgramId, ProofType, PublicValues, ZkVm};
 
 /// A convenient alias for results in the ZkVM.
 pub type ZkVmResult<T> = Result<T, ZkVmError>;
 
+/// A convenient alias for results of the fallible guest-side readers.
+pub type ZkVmEnvResult<T> = Result<T, ZkVmEnvError>;
+
 /// General ZkVM error types.
 #[derive(Debug, Error)]
 pub enum ZkVmError {
@@ -16,6 +24,34 @@ pub enum ZkVmError {
     #[error("Execution failed: {0}")]
     ExecutionError(String),
 
+    /// This error is returned when the guest itself stopped with a non-zero exit code, either by
+    /// calling [`ZkVmEnv::abort`](crate::ZkVmEnv::abort) or by panicking.
+    ///
+    /// Unlike [`ZkVmError::ExecutionError`], this means the program ran and rejected its input,
+    /// so retrying with the same input will fail the same way.
+    #[error("Guest aborted with exit code {exit_code}: {message}")]
+    GuestAborted {
+        /// The exit code of the guest.
+        exit_code: u32,
+        /// The abort or panic message, when the adapter can recover it.
+        message: String,
+        /// The number of cycles executed before the guest stopped, when known.
+        cycles: Option<u64>,
+        /// The public values committed before the guest stopped, when the adapter can recover
+        /// them.
+        public_values: Option<PublicValues>,
+    },
+
+    /// This error is returned when the guest did not finish within the configured wall-clock
+    /// timeout.
+    #[error("Execution timed out after {timeout:?}")]
+    ExecutionTimeout {
+        /// The timeout the guest exceeded.
+        timeout: Duration,
+        /// The public values committed before the timeout expired.
+        public_values: PublicValues,
+    },
+
     /// Network (or RPC) error that can occur while using prover network.
     #[error("Network error: {0}")]
     NetworkRetryableError(String),
@@ -28,6 +64,15 @@ pub enum ZkVmError {
     #[error("Proof verification failed: {0}")]
     ProofVerificationError(String),
 
+    /// This error is returned when a receipt generated in dev mode reaches a verifier that does
+    /// not accept them. Wrap the verifier in [`AllowDevMode`](crate::AllowDevMode) to accept
+    /// them.
+    #[error("Refusing to verify a dev-mode {zkvm} receipt")]
+    DevModeReceipt {
+        /// The zkVM named in the receipt.
+        zkvm: ZkVm,
+    },
+
     /// This error indicates that input validation has failed.
     /// It wraps the underlying [`ZkVmInputError`].
     #[error("Input validation failed: {0}")]
@@ -55,6 +100,27 @@ pub enum ZkVmError {
         source: DataFormatError,
     },
 
+    /// This error is returned when an encoded proof receipt cannot be decoded.
+    /// It wraps the underlying [`ReceiptDecodeError`].
+    #[error("Receipt decoding failed: {0}")]
+    ReceiptDecode(#[from] ReceiptDecodeError),
+
+    /// This error is returned when a proof store operation fails.
+    /// It wraps the underlying [`ProofStoreError`].
+    #[error("Proof store error: {0}")]
+    ProofStore(#[from] ProofStoreError),
+
+    /// This error is returned when a [`VerifierRegistry`](crate::VerifierRegistry) has no
+    /// verifier for a receipt.
+    /// It wraps the underlying [`VerifierRegistryError`].
+    #[error("Verifier registry error: {0}")]
+    VerifierRegistry(#[from] VerifierRegistryError),
+
+    /// This error is returned when a multi-proof receipt is malformed or fails verification.
+    /// It wraps the underlying [`MultiProofError`].
+    #[error("Multi-proof verification failed: {0}")]
+    MultiProof(#[from] MultiProofError),
+
     /// This error is returned when a proof is requested before it is ready.
     #[error("Proof is not ready")]
     ProofNotReady,
@@ -114,6 +180,43 @@ pub enum ZkVmInputError {
     InputBuild(String),
 }
 
+/// Errors returned by the fallible guest-side readers, such as
+/// [`ZkVmEnv::try_read_buf`](crate::ZkVmEnv::try_read_buf).
+#[derive(Debug, Error)]
+pub enum ZkVmEnvError {
+    /// The guest tried to read more inputs than the host provided.
+    #[error("no more inputs to read")]
+    InputExhausted,
+
+    /// An input could not be decoded into the requested type.
+    #[error("failed to decode input: {0}")]
+    Decode(#[from] DataFormatError),
+
+    /// A proof read by the guest did not verify.
+    #[error("proof verification failed: {0}")]
+    ProofVerification(String),
+}
+
+impl ZkVmEnvError {
+    /// Exit code reported when the guest aborts because it ran out of inputs.
+    pub const INPUT_EXHAUSTED_EXIT_CODE: u8 = 2;
+
+    /// Exit code reported when the guest aborts because an input could not be decoded.
+    pub const DECODE_EXIT_CODE: u8 = 3;
+
+    /// Exit code reported when the guest aborts because a proof it read did not verify.
+    pub const PROOF_VERIFICATION_EXIT_CODE: u8 = 4;
+
+    /// Returns the exit code the guest reports when it aborts on this error.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            ZkVmEnvError::InputExhausted => Self::INPUT_EXHAUSTED_EXIT_CODE,
+            ZkVmEnvError::Decode(_) => Self::DECODE_EXIT_CODE,
+            ZkVmEnvError::ProofVerification(_) => Self::PROOF_VERIFICATION_EXIT_CODE,
+        }
+    }
+}
+
 /// Errors related to verification key usage or parsing in ZkVM.
 #[derive(Debug, Error)]
 pub enum ZkVmVerifyingKeyError {
@@ -159,6 +262,161 @@ pub enum ZkVmProofError {
     VersionMismatch(#[from] Mismatched<String>),
 }
 
+/// Errors produced while decoding an encoded
+/// [`ProofReceiptWithMetadata`](crate::ProofReceiptWithMetadata).
+///
+/// Every variant carries the byte offset (from the start of the encoding) at which decoding
+/// failed, and where applicable the name of the field that was being read.
+#[derive(Debug, Error)]
+pub enum ReceiptDecodeError {
+    /// The input ended before the field could be read in full.
+    #[error("unexpected end of data while reading `{field}` at offset {offset}")]
+    UnexpectedEof {
+        /// Offset at which the field starts.
+        offset: usize,
+        /// Name of the field being read.
+        field: &'static str,
+    },
+
+    /// The container header names a format version this build cannot decode.
+    #[error("unsupported receipt format version {version} at offset {offset}")]
+    UnsupportedVersion {
+        /// Offset of the version field.
+        offset: usize,
+        /// The version found in the header.
+        version: u16,
+    },
+
+    /// A field was read in full but holds a value that is not valid for it.
+    #[error("invalid `{field}` at offset {offset}: {reason}")]
+    InvalidField {
+        /// Offset at which the field starts.
+        offset: usize,
+        /// Name of the invalid field.
+        field: &'static str,
+        /// Why the value was rejected.
+        reason: String,
+    },
+
+    /// The stored checksum does not match the checksum of the decoded bytes.
+    #[error("checksum mismatch at offset {offset}")]
+    ChecksumMismatch {
+        /// Offset of the checksum field.
+        offset: usize,
+    },
+
+    /// Bytes remain after the last field of the receipt.
+    #[error("{len} trailing bytes after the receipt at offset {offset}")]
+    TrailingData {
+        /// Offset of the first trailing byte.
+        offset: usize,
+        /// Number of trailing bytes.
+        len: usize,
+    },
+
+    /// The underlying reader failed for a reason other than reaching the end of the input.
+    #[error("I/O error while reading `{field}` at offset {offset}")]
+    Io {
+        /// Offset at which the field starts.
+        offset: usize,
+        /// Name of the field being read.
+        field: &'static str,
+        /// The source I/O error.
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Errors produced by the [`ProofStore`](crate::ProofStore) backends shipped with this crate.
+#[derive(Debug, Error)]
+pub enum ProofStoreError {
+    /// Reading or writing a file of the store failed.
+    #[error("I/O error at {}", path.display())]
+    Io {
+        /// Path of the file or directory being accessed.
+        path: PathBuf,
+        /// The source I/O error.
+        #[source]
+        source: io::Error,
+    },
+
+    /// A stored receipt could not be decoded.
+    #[error("failed to decode receipt at {}", path.display())]
+    Decode {
+        /// Path of the stored receipt.
+        path: PathBuf,
+        /// The source decoding error.
+        #[source]
+        source: ReceiptDecodeError,
+    },
+
+    /// A store entry is malformed.
+    #[error("corrupt store entry at {}: {reason}", path.display())]
+    CorruptEntry {
+        /// Path of the malformed entry.
+        path: PathBuf,
+        /// Why the entry was rejected.
+        reason: String,
+    },
+}
+
+/// Errors returned by a [`VerifierRegistry`](crate::VerifierRegistry) when it cannot route a
+/// receipt to a verifier.
+#[derive(Debug, Error)]
+pub enum VerifierRegistryError {
+    /// No verifier is registered for the zkVM and program named in the receipt metadata.
+    #[error("no verifier registered for program {program_id} on {zkvm}")]
+    UnknownProgram {
+        /// The zkVM named in the receipt metadata.
+        zkvm: ZkVm,
+        /// The program id named in the receipt metadata.
+        program_id: ProgramId,
+    },
+
+    /// A verifier is registered for the program, but not for the proof type of the receipt.
+    #[error("{proof_type:?} proofs of program {program_id} on {zkvm} are not supported")]
+    UnsupportedProofType {
+        /// The zkVM named in the receipt metadata.
+        zkvm: ZkVm,
+        /// The program id named in the receipt metadata.
+        program_id: ProgramId,
+        /// The proof type of the receipt.
+        proof_type: ProofType,
+    },
+}
+
+/// Errors related to [`MultiProofReceipt`](crate::MultiProofReceipt)s and their verification.
+#[derive(Debug, Error)]
+pub enum MultiProofError {
+    /// A multi-proof receipt was created without any receipt.
+    #[error("no receipts")]
+    Empty,
+
+    /// The public values of a receipt differ from those of the first receipt.
+    #[error("public values of receipt {index} differ from those of receipt 0")]
+    PublicValuesMismatch {
+        /// Index of the offending receipt.
+        index: usize,
+    },
+
+    /// Two receipts were generated by the same zkVM.
+    #[error("more than one receipt from {0}")]
+    DuplicateZkVm(ZkVm),
+
+    /// A backend required by the verifier has no receipt.
+    #[error("no receipt from {0}")]
+    MissingReceipt(ZkVm),
+
+    /// Fewer backends than required verified.
+    #[error("only {} of the {required} required backends verified", report.passed().len())]
+    ThresholdNotMet {
+        /// Number of backends that had to verify.
+        required: usize,
+        /// Which backends passed and which failed.
+        report: MultiProofReport,
+    },
+}
+
 /// Errors that can occur when attempting to parse or handle a verification key.
 #[derive(Debug, Error)]
 pub enum InvalidVerifyingKeySource {
@@ -0,0 +1,70 @@
+//! Filesystem helpers shared by zkaleido and its adapters.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Writes a file at `path` through `write`, replacing any existing file atomically.
+///
+/// The contents are written to a temporary file next to `path`, synced, and then renamed into
+/// place. The temporary file is removed if any step fails.
+pub(crate) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let tmp_path = temp_path_for(path);
+
+    let result = File::create_new(&tmp_path)
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            write(&mut writer)?;
+            writer.into_inner().map_err(|e| e.into_error())?.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        // Best effort: the original error is more useful than a failed cleanup.
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Returns a unique temporary path in the same directory as `path`.
+///
+/// Staying in the same directory keeps a final rename or hard link to `path` on a single
+/// filesystem, which is what makes it atomic. Paths are unique within the process and across
+/// processes, but the file is not created.
+pub fn temp_path_for(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_paths_are_unique_siblings() {
+        let path = Path::new("dir/receipt.bin");
+        let first = temp_path_for(path);
+        let second = temp_path_for(path);
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+        let name = first.file_name().unwrap().to_str().unwrap();
+        assert!(
+            name.starts_with("receipt.bin.") && name.ends_with(".tmp"),
+            "{name}"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "remote-prover")]
 use crate::ZkVmRemoteProver;
 #[cfg(feature = "perf")]
-use crate::{PerformanceReport, input::ZkVmInputBuilder};
+use crate::{PerformanceReport, RunConfig, input::ZkVmInputBuilder};
 use crate::{ZkVm, ZkVmOutputExtractor, ZkVmProver, ZkVmTypedVerifier, ZkVmVkProvider};
 
 /// A trait implemented by the host of a zkVM program.
@@ -28,6 +28,7 @@ pub trait ZkVmHostPerf: ZkVmHost {
     ///
     /// # Parameters
     /// - `input`: The input generated by a type implementing [`ZkVmInputBuilder`].
+    /// - `config`: The run configuration. In dev mode, hosts skip the proof metrics.
     ///
     /// # Returns
     /// A [`PerformanceReport`] containing metrics and other performance-related data
@@ -35,6 +36,7 @@ pub trait ZkVmHostPerf: ZkVmHost {
     fn perf_report<'a>(
         &self,
         input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
     ) -> PerformanceReport;
 }
 
@@ -1,4 +1,4 @@
-use crate::{AggregationInput, ProofReceiptWithMetadata, ZkVmInputError};
+use crate::{AggregationInput, ProofReceiptWithMetadata, ZkVmCodec, ZkVmInputError};
 
 /// A convenient alias for results in the ZkVM.
 pub type ZkVmInputResult<T> = Result<T, ZkVmInputError>;
@@ -25,6 +25,10 @@ pub trait ZkVmInputBuilder<'a> {
     fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self>;
 
     /// Serializes the given item using Serde and appends it to the list of inputs.
+    ///
+    /// Implementations must write the `bincode` encoding of the item as with
+    /// [`write_buf`](ZkVmInputBuilder::write_buf), so that the guest can read it with either
+    /// `read_serde` or `read_buf`.
     #[cfg(feature = "serde")]
     fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self>;
 
@@ -49,6 +53,18 @@ pub trait ZkVmInputBuilder<'a> {
         self.write_buf(&item.as_ssz_bytes())
     }
 
+    /// Encodes the given item with the codec `C` and appends it to the list of inputs.
+    ///
+    /// The guest reads it back with [`read_with`](crate::ZkVmEnvCodec::read_with) using the same
+    /// codec.
+    fn write_with<C: ZkVmCodec<T>, T>(&mut self, item: &T) -> ZkVmInputResult<&mut Self>
+    where
+        Self: Sized,
+    {
+        C::write(self, item)?;
+        Ok(self)
+    }
+
     /// Adds an `AggregationInput` to the list of aggregation/composition inputs.
     ///
     /// This method is specifically used for cases where proof aggregation or composition
@@ -9,9 +9,16 @@
 //!   formats) before handing it off to the ZkVM for proof generation.
 //! - **[`ZkVmHost`]**: A trait for the "host," i.e., the environment or system responsible for
 //!   generating and verifying proofs.
+//! - **[`DynZkVmHost`]**: An object-safe version of [`ZkVmHost`] fed with raw [`InputFrame`]s, for
+//!   choosing the backend at runtime.
+//! - **Wire format**: every input written by a [`ZkVmInputBuilder`] is a frame read back whole by
+//!   the guest. With the `conformance` feature, `ConformanceSuite` checks that an adapter follows
+//!   it.
 //! - **[`ZkVmProgram`]**: A high-level interface for logic-specific proof generation. Implementers
 //!   define custom `Input` and `Output` types, then rely on a chosen host to actually run or verify
 //!   the proof.
+//! - **[`MockHost`]**: Wraps any [`ZkVmHost`] to execute instead of prove, producing receipts
+//!   flagged as dev mode. Verifiers reject them unless wrapped in [`AllowDevMode`].
 //! - **Error Handling**: A set of error enums (e.g., `ZkVmError`) provides comprehensive error
 //!   reporting and integration with Rust's `thiserror` crate for detailed diagnostics.
 
@@ -24,31 +31,58 @@ use borsh::{BorshDeserialize, BorshSerialize};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+mod cache;
+mod codec;
+mod config;
+#[cfg(feature = "conformance")]
+mod conformance;
+mod container;
+mod dyn_host;
+mod elf;
 mod env;
 mod errors;
+pub mod fs;
 mod host;
 mod input;
+mod mock;
+mod multi_proof;
 #[cfg(feature = "perf")]
 mod perf;
 mod program;
 mod proof;
 mod prover;
+mod registry;
 #[cfg(feature = "remote-prover")]
 mod remote_prover;
+mod store;
 mod verifier;
 
+pub use cache::*;
+pub use codec::*;
+pub use config::*;
+#[cfg(feature = "conformance")]
+pub use conformance::*;
+pub use container::{RECEIPT_FORMAT_VERSION, RECEIPT_MAGIC};
+pub use dyn_host::*;
+pub use elf::*;
 pub use env::*;
 pub use errors::*;
 pub use host::*;
 pub use input::*;
+pub use mock::*;
+pub use multi_proof::*;
 #[cfg(feature = "perf")]
 pub use perf::*;
 pub use program::*;
 pub use proof::*;
 pub use prover::*;
+pub use registry::*;
 #[cfg(feature = "remote-prover")]
 pub use remote_prover::*;
+pub use store::*;
 pub use verifier::*;
+#[cfg(feature = "derive")]
+pub use zkaleido_derive::ZkVmProgram;
 
 /// Represents the ZkVm host used for proof generation.
 ///
@@ -0,0 +1,239 @@
+//! Dev-mode proving, for tests and local development.
+//!
+//! [`MockHost`] wraps any [`ZkVmHost`] and replaces proving with plain execution: its receipts
+//! carry the public values of the guest and an empty proof, and are flagged as dev mode in their
+//! [`ProofMetadata`]. Every verifier rejects such receipts with
+//! [`ZkVmError::DevModeReceipt`] unless it is wrapped in [`AllowDevMode`].
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, de::DeserializeOwned};
+
+#[cfg(feature = "serde")]
+use crate::PublicValues;
+use crate::{
+    DataFormatError, ExecutionSummary, ProgramId, Proof, ProofMetadata, ProofReceipt,
+    ProofReceiptWithMetadata, ProofType, RunConfig, VerifyingKey, ZkVm, ZkVmError, ZkVmExecutor,
+    ZkVmHost, ZkVmInputBuilder, ZkVmOutputExtractor, ZkVmProofError, ZkVmProver, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVkProvider,
+};
+
+/// Version string of the receipts generated by a [`MockHost`].
+const MOCK_VERSION: &str = "mock";
+
+/// A host that executes the guest of `H` without proving it.
+///
+/// The receipts of a `MockHost` name the zkVM and program of the wrapped host, so they are routed
+/// like real receipts, but they prove nothing. They only verify with a `MockHost` of the same
+/// program wrapped in [`AllowDevMode`].
+#[derive(Debug, Clone)]
+pub struct MockHost<H> {
+    inner: H,
+}
+
+impl<H: ZkVmHost> MockHost<H> {
+    /// Wraps `inner`, whose guest is executed instead of proven.
+    pub fn new(inner: H) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped host.
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    /// Returns a copy of `receipt` flagged with `proof_type`, once it is checked to be a receipt of
+    /// this host.
+    fn relabel(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+        proof_type: ProofType,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        let receipt = MockProofReceipt::try_from(receipt.clone())?;
+        self.verify_inner(&receipt)?;
+        let metadata = receipt.0.metadata();
+        let metadata = ProofMetadata::new(
+            *metadata.zkvm(),
+            metadata.program_id().clone(),
+            metadata.version(),
+            proof_type,
+        )
+        .with_dev_mode(true);
+        Ok(ProofReceiptWithMetadata::new(
+            receipt.0.receipt().clone(),
+            metadata,
+        ))
+    }
+}
+
+/// A receipt generated by a [`MockHost`].
+///
+/// Only receipts flagged as dev mode convert to a `MockProofReceipt`.
+#[derive(Debug, Clone)]
+pub struct MockProofReceipt(ProofReceiptWithMetadata);
+
+impl TryFrom<ProofReceiptWithMetadata> for MockProofReceipt {
+    type Error = ZkVmProofError;
+
+    fn try_from(value: ProofReceiptWithMetadata) -> Result<Self, Self::Error> {
+        if !value.metadata().is_dev_mode() {
+            return Err(ZkVmProofError::DataFormat(DataFormatError::Other(
+                "receipt was not generated in dev mode".to_string(),
+            )));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl TryFrom<MockProofReceipt> for ProofReceiptWithMetadata {
+    type Error = ZkVmProofError;
+
+    fn try_from(value: MockProofReceipt) -> Result<Self, Self::Error> {
+        Ok(value.0)
+    }
+}
+
+impl<H: ZkVmHost> ZkVmExecutor for MockHost<H> {
+    type Input<'a> = H::Input<'a>;
+
+    fn execute<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary> {
+        self.inner.execute(input, config)
+    }
+
+    fn get_elf(&self) -> &[u8] {
+        self.inner.get_elf()
+    }
+
+    fn program_id(&self) -> ProgramId {
+        ZkVmExecutor::program_id(&self.inner)
+    }
+}
+
+impl<H: ZkVmHost> ZkVmProver for MockHost<H> {
+    type ZkVmProofReceipt = MockProofReceipt;
+
+    /// Executes the guest and returns a dev-mode receipt of its public values, whatever the dev
+    /// mode setting of `config`.
+    fn prove_inner<'a>(
+        &self,
+        input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        proof_type: ProofType,
+        config: &RunConfig,
+    ) -> ZkVmResult<MockProofReceipt> {
+        let summary = self.inner.execute(input, config)?;
+        let receipt = ProofReceipt::new(Proof::new(Vec::new()), summary.public_values().clone());
+        let metadata = ProofMetadata::new(
+            self.inner.zkvm(),
+            ZkVmExecutor::program_id(&self.inner),
+            MOCK_VERSION,
+            proof_type,
+        )
+        .with_dev_mode(true);
+        Ok(MockProofReceipt(ProofReceiptWithMetadata::new(
+            receipt, metadata,
+        )))
+    }
+
+    /// Relabels a receipt of this host as compressed. There is no proof to compress.
+    fn compress(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.relabel(receipt, ProofType::Compressed)
+    }
+
+    /// Relabels a receipt of this host as Groth16. There is no proof to wrap.
+    fn wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        self.relabel(receipt, ProofType::Groth16)
+    }
+}
+
+impl<H: ZkVmHost> ZkVmTypedVerifier for MockHost<H> {
+    type ZkVmProofReceipt = MockProofReceipt;
+
+    /// Checks that the receipt names the zkVM and program of the wrapped host. There is no proof
+    /// to check.
+    fn verify_inner(&self, receipt: &MockProofReceipt) -> ZkVmResult<()> {
+        let metadata = receipt.0.metadata();
+        if *metadata.zkvm() != self.inner.zkvm() {
+            return Err(ZkVmError::ProofVerificationError(format!(
+                "expected a {} receipt, got a {} receipt",
+                self.inner.zkvm(),
+                metadata.zkvm()
+            )));
+        }
+        if *metadata.program_id() != ZkVmExecutor::program_id(&self.inner) {
+            return Err(ZkVmError::ProofVerificationError(
+                "receipt is for another program".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<H: ZkVmHost> ZkVmOutputExtractor for MockHost<H> {
+    #[cfg(feature = "serde")]
+    fn extract_serde_public_output<T: Serialize + DeserializeOwned>(
+        public_values: &PublicValues,
+    ) -> ZkVmResult<T> {
+        H::extract_serde_public_output(public_values)
+    }
+}
+
+impl<H: ZkVmHost> ZkVmVkProvider for MockHost<H> {
+    fn vk(&self) -> VerifyingKey {
+        self.inner.vk()
+    }
+}
+
+impl<H: ZkVmHost> ZkVmHost for MockHost<H> {
+    fn zkvm(&self) -> ZkVm {
+        self.inner.zkvm()
+    }
+}
+
+/// A verifier that also accepts receipts generated in dev mode.
+///
+/// Receipts not flagged as dev mode are verified by the wrapped verifier as usual. Dev-mode
+/// receipts skip the rejection and go straight to
+/// [`verify_inner`](ZkVmTypedVerifier::verify_inner) of the wrapped verifier, so they still have
+/// to pass whatever checks it makes: wrap a [`MockHost`] to accept its receipts, or a host whose
+/// backend verifies its own dev-mode proofs.
+///
+/// Like any [`ZkVmTypedVerifier`], it can be registered in a
+/// [`VerifierRegistry`](crate::VerifierRegistry) or used as a backend of an
+/// [`AllOf`](crate::AllOf) or [`Threshold`](crate::Threshold). Verifiers that only see the
+/// [`ProofReceipt`] always reject dev-mode receipts.
+#[derive(Debug, Clone)]
+pub struct AllowDevMode<V>(V);
+
+impl<V: ZkVmTypedVerifier> AllowDevMode<V> {
+    /// Wraps `verifier` so that it also accepts dev-mode receipts.
+    pub fn new(verifier: V) -> Self {
+        Self(verifier)
+    }
+
+    /// Returns the wrapped verifier.
+    pub fn inner(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<V: ZkVmTypedVerifier> ZkVmTypedVerifier for AllowDevMode<V> {
+    type ZkVmProofReceipt = V::ZkVmProofReceipt;
+
+    fn verify_inner(&self, receipt: &Self::ZkVmProofReceipt) -> ZkVmResult<()> {
+        self.0.verify_inner(receipt)
+    }
+
+    fn verify(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        if receipt.metadata().is_dev_mode() {
+            self.0.verify_inner(&receipt.clone().try_into()?)
+        } else {
+            self.0.verify(receipt)
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Verification of a statement proven on several zkVMs.
+
+use std::sync::Arc;
+
+use crate::{
+    MultiProofError, ProofReceiptWithMetadata, PublicValues, ZkVm, ZkVmError, ZkVmResult,
+    ZkVmTypedVerifier, ZkVmVerifier,
+    registry::{ErasedVerifier, UntypedVerifier},
+};
+
+/// Proofs of the same statement generated by several zkVMs.
+///
+/// All receipts commit to byte-identical public values and come from distinct zkVMs, so that a
+/// bug in a single backend is not enough to convince an [`AllOf`] or [`Threshold`] verifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProofReceipt {
+    receipts: Vec<ProofReceiptWithMetadata>,
+}
+
+impl MultiProofReceipt {
+    /// Creates a multi-proof receipt from `receipts`.
+    ///
+    /// Fails if `receipts` is empty, if two receipts come from the same zkVM or if their public
+    /// values are not byte-identical.
+    pub fn new(receipts: Vec<ProofReceiptWithMetadata>) -> ZkVmResult<Self> {
+        let Some(first) = receipts.first() else {
+            return Err(MultiProofError::Empty.into());
+        };
+        let public_values = first.receipt().public_values();
+        for (index, receipt) in receipts.iter().enumerate().skip(1) {
+            if receipt.receipt().public_values() != public_values {
+                return Err(MultiProofError::PublicValuesMismatch { index }.into());
+            }
+            let zkvm = *receipt.metadata().zkvm();
+            if receipts[..index]
+                .iter()
+                .any(|other| *other.metadata().zkvm() == zkvm)
+            {
+                return Err(MultiProofError::DuplicateZkVm(zkvm).into());
+            }
+        }
+        Ok(Self { receipts })
+    }
+
+    /// Returns the public values shared by all receipts.
+    pub fn public_values(&self) -> &PublicValues {
+        self.receipts[0].receipt().public_values()
+    }
+
+    /// Returns the receipts, in the order they were given.
+    pub fn receipts(&self) -> &[ProofReceiptWithMetadata] {
+        &self.receipts
+    }
+
+    /// Returns the receipt generated by `zkvm`, if any.
+    pub fn receipt(&self, zkvm: ZkVm) -> Option<&ProofReceiptWithMetadata> {
+        self.receipts
+            .iter()
+            .find(|receipt| *receipt.metadata().zkvm() == zkvm)
+    }
+
+    /// Consumes the multi-proof receipt and returns the receipts.
+    pub fn into_receipts(self) -> Vec<ProofReceiptWithMetadata> {
+        self.receipts
+    }
+}
+
+/// Outcome of checking a [`MultiProofReceipt`] against the backends of an [`AllOf`] or
+/// [`Threshold`] verifier.
+///
+/// Only the configured backends are reported; receipts from other zkVMs are ignored.
+#[derive(Debug, Default)]
+pub struct MultiProofReport {
+    passed: Vec<ZkVm>,
+    failed: Vec<(ZkVm, ZkVmError)>,
+}
+
+impl MultiProofReport {
+    /// Returns the backends whose receipt verified.
+    pub fn passed(&self) -> &[ZkVm] {
+        &self.passed
+    }
+
+    /// Returns the backends whose receipt is missing or failed to verify, with the reason.
+    pub fn failed(&self) -> &[(ZkVm, ZkVmError)] {
+        &self.failed
+    }
+}
+
+/// The verifiers of an [`AllOf`] or [`Threshold`], one per zkVM.
+#[derive(Debug, Clone, Default)]
+struct BackendVerifiers(Vec<(ZkVm, Arc<dyn ErasedVerifier>)>);
+
+impl BackendVerifiers {
+    fn insert(&mut self, zkvm: ZkVm, verifier: Arc<dyn ErasedVerifier>) {
+        self.0.retain(|(existing, _)| *existing != zkvm);
+        self.0.push((zkvm, verifier));
+    }
+
+    fn check(&self, multi_receipt: &MultiProofReceipt) -> MultiProofReport {
+        let mut report = MultiProofReport::default();
+        for (zkvm, verifier) in &self.0 {
+            let result = match multi_receipt.receipt(*zkvm) {
+                Some(receipt) => verifier.verify_receipt(receipt),
+                None => Err(MultiProofError::MissingReceipt(*zkvm).into()),
+            };
+            match result {
+                Ok(()) => report.passed.push(*zkvm),
+                Err(error) => report.failed.push((*zkvm, error)),
+            }
+        }
+        report
+    }
+
+    fn verify(
+        &self,
+        multi_receipt: &MultiProofReceipt,
+        required: usize,
+    ) -> ZkVmResult<MultiProofReport> {
+        let report = self.check(multi_receipt);
+        if report.passed.len() < required {
+            return Err(MultiProofError::ThresholdNotMet { required, report }.into());
+        }
+        Ok(report)
+    }
+}
+
+/// Accepts a [`MultiProofReceipt`] only if the receipt of every configured backend verifies.
+///
+/// An `AllOf` without any backend rejects every receipt.
+#[derive(Debug, Clone, Default)]
+pub struct AllOf {
+    verifiers: BackendVerifiers,
+}
+
+impl AllOf {
+    /// Creates a verifier without any backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires a receipt from `zkvm` that verifies with `verifier`.
+    #[must_use]
+    pub fn with_verifier<V: ZkVmTypedVerifier>(mut self, zkvm: ZkVm, verifier: V) -> Self {
+        self.verifiers.insert(zkvm, Arc::new(verifier));
+        self
+    }
+
+    /// Requires a receipt from `zkvm` that verifies with `verifier`, which only sees the
+    /// [`ProofReceipt`](crate::ProofReceipt) and not its metadata.
+    #[must_use]
+    pub fn with_receipt_verifier<V: ZkVmVerifier>(mut self, zkvm: ZkVm, verifier: V) -> Self {
+        self.verifiers
+            .insert(zkvm, Arc::new(UntypedVerifier(verifier)));
+        self
+    }
+
+    /// Verifies `multi_receipt`, returning which backends passed.
+    ///
+    /// Fails with [`MultiProofError::ThresholdNotMet`], which carries the full report, if any
+    /// backend failed.
+    pub fn verify(&self, multi_receipt: &MultiProofReceipt) -> ZkVmResult<MultiProofReport> {
+        self.verifiers
+            .verify(multi_receipt, self.verifiers.0.len().max(1))
+    }
+}
+
+/// Accepts a [`MultiProofReceipt`] if the receipts of at least `threshold` of the configured
+/// backends verify.
+#[derive(Debug, Clone)]
+pub struct Threshold {
+    threshold: usize,
+    verifiers: BackendVerifiers,
+}
+
+impl Threshold {
+    /// Creates a verifier requiring `threshold` backends to pass, without any backend yet.
+    ///
+    /// A `threshold` of zero is treated as one.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            verifiers: BackendVerifiers::default(),
+        }
+    }
+
+    /// Adds `zkvm` as a backend whose receipts are checked with `verifier`.
+    #[must_use]
+    pub fn with_verifier<V: ZkVmTypedVerifier>(mut self, zkvm: ZkVm, verifier: V) -> Self {
+        self.verifiers.insert(zkvm, Arc::new(verifier));
+        self
+    }
+
+    /// Adds `zkvm` as a backend whose receipts are checked with `verifier`, which only sees the
+    /// [`ProofReceipt`](crate::ProofReceipt) and not its metadata.
+    #[must_use]
+    pub fn with_receipt_verifier<V: ZkVmVerifier>(mut self, zkvm: ZkVm, verifier: V) -> Self {
+        self.verifiers
+            .insert(zkvm, Arc::new(UntypedVerifier(verifier)));
+        self
+    }
+
+    /// Verifies `multi_receipt`, returning which backends passed.
+    ///
+    /// Fails with [`MultiProofError::ThresholdNotMet`], which carries the full report, if fewer
+    /// than `threshold` backends passed.
+    pub fn verify(&self, multi_receipt: &MultiProofReceipt) -> ZkVmResult<MultiProofReport> {
+        self.verifiers.verify(multi_receipt, self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoopVerifier, ProgramId, Proof, ProofMetadata, ProofReceipt, ProofType};
+
+    /// A verifier that rejects every receipt.
+    #[derive(Debug, Clone)]
+    struct RejectingVerifier;
+
+    impl ZkVmVerifier for RejectingVerifier {
+        fn verify(&self, _receipt: &ProofReceipt) -> ZkVmResult<()> {
+            Err(ZkVmError::ProofVerificationError("rejected".to_string()))
+        }
+    }
+
+    fn receipt(zkvm: ZkVm, public_values: &[u8]) -> ProofReceiptWithMetadata {
+        ProofReceiptWithMetadata::new(
+            ProofReceipt::new(Proof::default(), PublicValues::new(public_values.to_vec())),
+            ProofMetadata::new(zkvm, ProgramId::default(), "0", ProofType::Core),
+        )
+    }
+
+    #[test]
+    fn rejects_mismatched_public_values_and_duplicate_zkvms() {
+        let mismatched = vec![receipt(ZkVm::SP1, &[1]), receipt(ZkVm::Risc0, &[2])];
+        assert!(matches!(
+            MultiProofReceipt::new(mismatched),
+            Err(ZkVmError::MultiProof(
+                MultiProofError::PublicValuesMismatch { index: 1 }
+            ))
+        ));
+
+        let duplicate = vec![receipt(ZkVm::SP1, &[1]), receipt(ZkVm::SP1, &[1])];
+        assert!(matches!(
+            MultiProofReceipt::new(duplicate),
+            Err(ZkVmError::MultiProof(MultiProofError::DuplicateZkVm(
+                ZkVm::SP1
+            )))
+        ));
+    }
+
+    #[test]
+    fn reports_passed_and_failed_backends() {
+        let multi_receipt =
+            MultiProofReceipt::new(vec![receipt(ZkVm::SP1, &[1]), receipt(ZkVm::Risc0, &[1])])
+                .unwrap();
+
+        let threshold = Threshold::new(1)
+            .with_receipt_verifier(ZkVm::SP1, NoopVerifier)
+            .with_receipt_verifier(ZkVm::Risc0, RejectingVerifier)
+            .with_receipt_verifier(ZkVm::Native, NoopVerifier);
+        let report = threshold.verify(&multi_receipt).unwrap();
+        assert_eq!(report.passed(), &[ZkVm::SP1]);
+        assert!(matches!(
+            report.failed(),
+            [
+                (ZkVm::Risc0, ZkVmError::ProofVerificationError(_)),
+                (
+                    ZkVm::Native,
+                    ZkVmError::MultiProof(MultiProofError::MissingReceipt(ZkVm::Native))
+                ),
+            ]
+        ));
+
+        let all_of = AllOf::new()
+            .with_receipt_verifier(ZkVm::SP1, NoopVerifier)
+            .with_receipt_verifier(ZkVm::Risc0, RejectingVerifier);
+        match all_of.verify(&multi_receipt) {
+            Err(ZkVmError::MultiProof(MultiProofError::ThresholdNotMet { required, report })) => {
+                assert_eq!(required, 2);
+                assert_eq!(report.passed(), &[ZkVm::SP1]);
+                assert_eq!(report.failed().len(), 1);
+            }
+            other => panic!("expected ThresholdNotMet, got {other:?}"),
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use std::env::var;
 #[cfg(feature = "remote-prover")]
 use std::future::Future;
 
 #[cfg(feature = "remote-prover")]
 use crate::ZkVmRemoteHost;
 use crate::{
-    ExecutionSummary, ProofReceiptWithMetadata, ProofType, PublicValues, ZkVmInputResult,
+    ExecutionSummary, InputDigest, InputDigestBuilder, ProofKey, ProofReceiptWithMetadata,
+    ProofStore, ProofType, PublicValues, RunConfig, ZkVmCodec, ZkVmEnvCodec, ZkVmInputResult,
     ZkVmResult, host::ZkVmHost, input::ZkVmInputBuilder,
 };
 #[cfg(feature = "perf")]
@@ -59,43 +59,46 @@ pub trait ZkVmProgram {
     where
         H: ZkVmHost;
 
-    /// Prepares the program’s input for the ZkVM, and—if the `ZKVM_PROFILING_DUMP` environment
-    /// variable is set to `1` or `true` (case‐insensitive)—automatically saves a host trace.
-    fn prepare_input_with_profiling<'a, H>(
-        input: &'a Self::Input,
-        host: &H,
-    ) -> ZkVmInputResult<<H::Input<'a> as ZkVmInputBuilder<'a>>::Input>
+    /// Computes the [`InputDigest`] of `input`.
+    ///
+    /// The digest is taken over the values written by [`Self::prepare_input`], so it is the same
+    /// for every host.
+    fn input_digest(input: &Self::Input) -> ZkVmInputResult<InputDigest> {
+        Self::prepare_input::<InputDigestBuilder>(input)
+    }
+
+    /// Returns the [`ProofKey`] under which a proof of `input` generated by `host` is stored.
+    fn proof_key<H>(input: &Self::Input, host: &H) -> ZkVmResult<ProofKey>
     where
         H: ZkVmHost,
-        H::Input<'a>: ZkVmInputBuilder<'a>,
     {
-        // 1) Check for profiling flag
-        if var("ZKVM_PROFILING_DUMP")
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(false)
-        {
-            // 2) Dump a trace via the host before building inputs
-            host.save_trace(&Self::name());
-        }
-
-        // 3) Delegate to the implementor’s original prepare_input
-        Self::prepare_input::<H::Input<'a>>(input)
+        Ok(ProofKey::new(
+            host.program_id(),
+            Self::input_digest(input)?,
+            Self::proof_type(),
+            host.zkvm(),
+        ))
     }
 
     /// Executes the computation using any zkVM host and returns the [`ExecutionSummary`].
     ///
     /// The summary contains the public values, cycle count, and optional gas usage. The output
     /// is validated against [`Self::Output`] before returning.
-    fn execute<'a, H>(input: &'a Self::Input, host: &H) -> ZkVmResult<ExecutionSummary>
+    fn execute<'a, H>(
+        input: &'a Self::Input,
+        host: &H,
+        config: &RunConfig,
+    ) -> ZkVmResult<ExecutionSummary>
     where
         H: ZkVmHost,
         H::Input<'a>: ZkVmInputBuilder<'a>,
     {
         // Prepare the input using the host's input builder.
-        let zkvm_input = Self::prepare_input_with_profiling(input, host)?;
+        let zkvm_input = Self::prepare_input::<H::Input<'a>>(input)?;
 
-        // Use the host to execute.
-        let execution_result = host.execute(zkvm_input)?;
+        // Use the host to execute, naming its trace after the program.
+        let config = config.clone().with_program_name(Self::name());
+        let execution_result = host.execute(zkvm_input, &config)?;
 
         // Validate that the public values parse as the expected output type.
         Self::process_output::<H>(execution_result.public_values())?;
@@ -104,30 +107,118 @@ pub trait ZkVmProgram {
     }
 
     /// Proves the computation using any zkVM host.
-    fn prove<'a, H>(input: &'a Self::Input, host: &H) -> ZkVmResult<ProofReceiptWithMetadata>
+    ///
+    /// If `config` sets a proof output directory, the receipt is also saved there.
+    fn prove<'a, H>(
+        input: &'a Self::Input,
+        host: &H,
+        config: &RunConfig,
+    ) -> ZkVmResult<ProofReceiptWithMetadata>
     where
         H: ZkVmHost,
         H::Input<'a>: ZkVmInputBuilder<'a>,
     {
         // Prepare the input using the host's input builder.
-        let zkvm_input = Self::prepare_input_with_profiling(input, host)?;
+        let zkvm_input = Self::prepare_input::<H::Input<'a>>(input)?;
 
-        // Use the host to prove.
-        let receipt_with_metadata = host.prove(zkvm_input, Self::proof_type())?;
+        // Use the host to prove, naming its trace after the program.
+        let config = config.clone().with_program_name(Self::name());
+        let receipt_with_metadata = host.prove(zkvm_input, Self::proof_type(), &config)?;
 
         // Process output to see if we are getting the expected type.
         let _ = Self::process_output::<H>(receipt_with_metadata.receipt().public_values())?;
 
-        // Dump the proof to file if flag is enabled
-        if var("ZKVM_PROOF_DUMP")
-            .map(|v| v == "1" || v.to_lowercase() == "true")
-            .unwrap_or(false)
-        {
-            receipt_with_metadata.save(Self::name()).unwrap();
+        // Save the proof if an output directory is configured.
+        if let Some(dir) = &config.proof_output_dir {
+            receipt_with_metadata.save_in(dir, Self::name())?;
         }
 
         Ok(receipt_with_metadata)
     }
+
+    /// Proves the computation using any zkVM host and writes the receipt to `store`.
+    ///
+    /// The receipt is proven with [`Self::prove`], which also saves it to the proof output
+    /// directory of `config` if one is set, and stored under [`Self::proof_key`].
+    fn prove_to_store<'a, H, S>(
+        input: &'a Self::Input,
+        host: &H,
+        store: &S,
+        config: &RunConfig,
+    ) -> ZkVmResult<ProofReceiptWithMetadata>
+    where
+        H: ZkVmHost,
+        H::Input<'a>: ZkVmInputBuilder<'a>,
+        S: ProofStore + ?Sized,
+    {
+        let key = Self::proof_key(input, host)?;
+        let receipt_with_metadata = Self::prove(input, host, config)?;
+        store.put(&key, &receipt_with_metadata)?;
+        Ok(receipt_with_metadata)
+    }
+}
+
+/// A [`ZkVmProgram`] whose input and output cross the host/guest boundary through a single
+/// [`ZkVmCodec`].
+///
+/// Implementing this trait instead of [`ZkVmProgram`] derives `prepare_input` and
+/// `process_output` from [`Self::Codec`], and gives the guest matching
+/// [`read_input`](Self::read_input) and [`commit_output`](Self::commit_output) helpers, so the
+/// host and the guest cannot disagree on the encoding.
+pub trait ZkVmCodecProgram {
+    /// Represents the input data needed by the program. See [`ZkVmProgram::Input`].
+    type Input: Send + Sync;
+
+    /// Represents the final, verifiable output produced by the program. See
+    /// [`ZkVmProgram::Output`].
+    type Output;
+
+    /// The codec used for both the input and the output.
+    type Codec: ZkVmCodec<Self::Input> + ZkVmCodec<Self::Output>;
+
+    /// Returns a human-readable name for this program.
+    fn name() -> String;
+
+    /// Returns the type of proof this program generates.
+    fn proof_type() -> ProofType;
+
+    /// Reads the program input inside the guest.
+    fn read_input<E: ZkVmEnvCodec>(env: &E) -> Self::Input {
+        <Self::Codec as ZkVmCodec<Self::Input>>::read(env)
+    }
+
+    /// Commits the program output inside the guest.
+    fn commit_output<E: ZkVmEnvCodec>(env: &E, output: &Self::Output) {
+        <Self::Codec as ZkVmCodec<Self::Output>>::commit(env, output)
+    }
+}
+
+/// Blanket implementation of [`ZkVmProgram`] for every [`ZkVmCodecProgram`].
+impl<P: ZkVmCodecProgram> ZkVmProgram for P {
+    type Input = <P as ZkVmCodecProgram>::Input;
+    type Output = <P as ZkVmCodecProgram>::Output;
+
+    fn name() -> String {
+        <P as ZkVmCodecProgram>::name()
+    }
+
+    fn proof_type() -> ProofType {
+        <P as ZkVmCodecProgram>::proof_type()
+    }
+
+    fn prepare_input<'a, B>(input: &'a Self::Input) -> ZkVmInputResult<B::Input>
+    where
+        B: ZkVmInputBuilder<'a>,
+    {
+        B::new().write_with::<P::Codec, _>(input)?.build()
+    }
+
+    fn process_output<H>(public_values: &PublicValues) -> ZkVmResult<Self::Output>
+    where
+        H: ZkVmHost,
+    {
+        H::extract_public_output::<P::Codec, _>(public_values)
+    }
 }
 
 /// Extends the [`ZkVmProgram`] trait by providing functionality to generate performance reports.
@@ -137,16 +228,21 @@ pub trait ZkVmProgram {
 #[cfg(feature = "perf")]
 pub trait ZkVmProgramPerf: ZkVmProgram {
     /// Generates a performance report for the proof process using a specified host.
-    fn perf_report<'a, H>(input: &'a Self::Input, host: &H) -> ZkVmResult<PerformanceReport>
+    fn perf_report<'a, H>(
+        input: &'a Self::Input,
+        host: &H,
+        config: &RunConfig,
+    ) -> ZkVmResult<PerformanceReport>
     where
         H: ZkVmHostPerf,
         H::Input<'a>: ZkVmInputBuilder<'a>,
     {
         // Prepare the input using the host's input builder.
-        let input = Self::prepare_input_with_profiling(input, host)?;
+        let input = Self::prepare_input::<H::Input<'a>>(input)?;
 
         // Generate the perf report and set proper name in the report
-        let mut perf_report = host.perf_report(input);
+        let config = config.clone().with_program_name(Self::name());
+        let mut perf_report = host.perf_report(input, &config);
         perf_report.name = Self::name();
 
         Ok(perf_report)
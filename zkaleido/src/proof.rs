@@ -1,9 +1,4 @@
-use std::{
-    fmt,
-    fs::File,
-    io::{Read as _, Write as _},
-    path::Path,
-};
+use std::{array, fmt, fs::File, io::BufReader, path::Path};
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -12,7 +7,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{ZkVm, ZkVmError, ZkVmResult};
+use crate::{ZkVm, ZkVmError, ZkVmResult, fs::write_atomic};
 
 /// Macro to define a newtype wrapper around `Vec<u8>` with common implementations.
 macro_rules! define_byte_wrapper {
@@ -73,12 +68,25 @@ define_byte_wrapper!(PublicValues);
 define_byte_wrapper!(VerifyingKey);
 
 /// Identifier of a zkVM program, derived deterministically from its ELF binary.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub struct ProgramId(pub [u8; 32]);
 
+impl ProgramId {
+    /// Returns the digest identifying the program's verification key inside guests, as passed to
+    /// [`ZkVmEnv::read_verified_buf`](crate::ZkVmEnv::read_verified_buf).
+    ///
+    /// The program id doubles as this digest: its bytes are read as eight little-endian words.
+    pub fn to_vk_digest(&self) -> [u32; 8] {
+        array::from_fn(|i| {
+            let word = &self.0[4 * i..4 * i + 4];
+            u32::from_le_bytes([word[0], word[1], word[2], word[3]])
+        })
+    }
+}
+
 impl fmt::Display for ProgramId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for byte in &self.0 {
@@ -103,6 +111,52 @@ pub struct ExecutionSummary {
     cycles: u64,
     /// Gas consumed during execution, if applicable.
     gas: Option<u64>,
+    /// I/O and timing statistics, if the backend records them.
+    ///
+    /// Left out of the serde and Borsh layouts, which must keep decoding summaries encoded before
+    /// the field existed: decoded summaries carry no statistics.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "borsh", borsh(skip))]
+    stats: Option<ExecutionStats>,
+}
+
+/// I/O and timing statistics of an execution, recorded by backends without a cycle count, such
+/// as the native adapter.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct ExecutionStats {
+    /// The number of input buffers read by the guest.
+    pub reads: u64,
+    /// The total size of the input buffers read by the guest, in bytes.
+    pub input_bytes: u64,
+    /// The number of buffers committed by the guest.
+    pub commits: u64,
+    /// The total size of the buffers committed by the guest, in bytes.
+    pub output_bytes: u64,
+    /// The wall-clock time of the execution, in nanoseconds.
+    pub wall_time_nanos: u64,
+    /// The regions tracked with
+    /// [`ZkVmEnv::cycle_tracker_start`](crate::ZkVmEnv::cycle_tracker_start), in the order they
+    /// were first completed.
+    pub regions: Vec<TrackedRegion>,
+}
+
+/// A region of guest code tracked with
+/// [`ZkVmEnv::cycle_tracker_start`](crate::ZkVmEnv::cycle_tracker_start) and
+/// [`ZkVmEnv::cycle_tracker_end`](crate::ZkVmEnv::cycle_tracker_end).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct TrackedRegion {
+    /// The label passed when entering and leaving the region.
+    pub label: String,
+    /// The number of times the region was left.
+    pub entries: u64,
+    /// The wall-clock time spent in the region over all entries, in nanoseconds.
+    pub wall_time_nanos: u64,
 }
 
 impl ExecutionSummary {
@@ -112,9 +166,17 @@ impl ExecutionSummary {
             public_values,
             cycles,
             gas,
+            stats: None,
         }
     }
 
+    /// Attaches the I/O and timing statistics recorded during execution.
+    #[must_use]
+    pub fn with_stats(mut self, stats: ExecutionStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     /// Returns the public values produced by the execution.
     pub fn public_values(&self) -> &PublicValues {
         &self.public_values
@@ -130,6 +192,11 @@ impl ExecutionSummary {
         self.gas
     }
 
+    /// Returns the I/O and timing statistics, if the backend records them.
+    pub fn stats(&self) -> Option<&ExecutionStats> {
+        self.stats.as_ref()
+    }
+
     /// Consumes the `ExecutionResult` and returns the public values.
     pub fn into_public_values(self) -> PublicValues {
         self.public_values
@@ -189,6 +256,17 @@ pub struct ProofMetadata {
     /// Adapters use this on the decode path to dispatch between encodings (e.g. on-chain raw
     /// bytes for Groth16 vs. bincode-serialized inner proofs for Core/Compressed).
     proof_type: ProofType,
+    /// Whether the proof was generated in dev mode, in which case it proves nothing.
+    ///
+    /// Verifiers reject such receipts unless they are wrapped in
+    /// [`AllowDevMode`](crate::AllowDevMode).
+    ///
+    /// Left out of the serde and Borsh layouts, which must keep decoding metadata encoded before
+    /// the flag existed, so decoded metadata is never in dev mode and dev-mode proofs then fail
+    /// verification. The versioned container of [`ProofReceiptWithMetadata::encode`] carries it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "borsh", borsh(skip))]
+    dev_mode: bool,
 }
 
 impl ProofMetadata {
@@ -204,9 +282,17 @@ impl ProofMetadata {
             program_id,
             version: version.into(),
             proof_type,
+            dev_mode: false,
         }
     }
 
+    /// Flags the proof as generated in dev mode.
+    #[must_use]
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+        self
+    }
+
     /// Returns the ZKVM that generated this proof.
     pub fn zkvm(&self) -> &ZkVm {
         &self.zkvm
@@ -226,6 +312,11 @@ impl ProofMetadata {
     pub fn proof_type(&self) -> ProofType {
         self.proof_type
     }
+
+    /// Returns `true` if the proof was generated in dev mode and proves nothing.
+    pub fn is_dev_mode(&self) -> bool {
+        self.dev_mode
+    }
 }
 
 /// A receipt containing a `Proof` and associated `PublicValues`.
@@ -256,99 +347,55 @@ impl ProofReceiptWithMetadata {
         &self.metadata
     }
 
-    /// Encodes the receipt into a binary format.
+    /// Saves the proof to a file named `{program_name}_{zkvm}_{version}.proof` in the current
+    /// directory.
     ///
-    /// Layout: `[proof_len: u64 LE][proof][pv_len: u64 LE][public_values][zkvm: u8][ver_len: u64
-    /// LE][version][program_id: 32 bytes][proof_type: u8]`
-    pub fn encode(&self) -> Vec<u8> {
-        let proof = self.receipt.proof.as_bytes();
-        let pv = self.receipt.public_values.as_bytes();
-        let zkvm_tag = self.metadata.zkvm as u8;
-        let version = self.metadata.version().as_bytes();
-        let program_id = &self.metadata.program_id.0;
-        let proof_type_tag = self.metadata.proof_type as u8;
-
-        let capacity = 8 + proof.len() + 8 + pv.len() + 1 + 8 + version.len() + 32 + 1;
-        let mut buf = Vec::with_capacity(capacity);
-
-        buf.extend_from_slice(&(proof.len() as u64).to_le_bytes());
-        buf.extend_from_slice(proof);
-        buf.extend_from_slice(&(pv.len() as u64).to_le_bytes());
-        buf.extend_from_slice(pv);
-        buf.push(zkvm_tag);
-        buf.extend_from_slice(&(version.len() as u64).to_le_bytes());
-        buf.extend_from_slice(version);
-        buf.extend_from_slice(program_id);
-        buf.push(proof_type_tag);
-
-        buf
-    }
-
-    /// Decodes a receipt from the binary format produced by [`encode`](Self::encode).
-    pub fn decode(mut data: &[u8]) -> ZkVmResult<Self> {
-        let err = || ZkVmError::Other("unexpected end of data".into());
-
-        let read_bytes = |d: &mut &[u8]| -> ZkVmResult<Vec<u8>> {
-            let (len_bytes, rest) = d.split_at_checked(8).ok_or_else(err)?;
-            let len = u64::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
-            let (payload, rest) = rest.split_at_checked(len).ok_or_else(err)?;
-            *d = rest;
-            Ok(payload.to_vec())
-        };
-
-        let proof = read_bytes(&mut data)?;
-        let public_values = read_bytes(&mut data)?;
-        let (&zkvm_tag, rest) = data.split_first().ok_or_else(err)?;
-        data = rest;
-        let version_bytes = read_bytes(&mut data)?;
-
-        let (pid_bytes, rest) = data.split_at_checked(32).ok_or_else(err)?;
-        let program_id = ProgramId(pid_bytes.try_into().unwrap());
-        data = rest;
-
-        let (&proof_type_tag, rest) = data.split_first().ok_or_else(err)?;
-        data = rest;
-
-        // Silence unused-variable warning; all data should be consumed.
-        let _ = data;
-
-        Ok(Self {
-            receipt: ProofReceipt {
-                proof: Proof::new(proof),
-                public_values: PublicValues::new(public_values),
-            },
-            metadata: ProofMetadata {
-                zkvm: ZkVm::try_from(zkvm_tag)?,
-                program_id,
-                version: String::from_utf8(version_bytes)
-                    .map_err(|e| ZkVmError::Other(format!("invalid utf-8 in version: {e}")))?,
-                proof_type: ProofType::try_from(proof_type_tag)?,
-            },
-        })
+    /// See [`save_to`](Self::save_to) for the write guarantees.
+    pub fn save(&self, program_name: impl AsRef<str>) -> ZkVmResult<()> {
+        self.save_in(".", program_name)
     }
 
-    /// Saves the proof to a file named `{program_name}_{zkvm}_{version}.proof`.
-    pub fn save(&self, program_name: impl AsRef<str>) -> ZkVmResult<()> {
+    /// Saves the proof to a file in `dir`, named like [`Self::save`].
+    pub fn save_in(&self, dir: impl AsRef<Path>, program_name: impl AsRef<str>) -> ZkVmResult<()> {
         let filename = format!(
             "{}_{}_{}.proof",
             program_name.as_ref(),
             self.metadata.zkvm(),
             self.metadata.version()
         );
-        let mut file = File::create(filename)
-            .map_err(|e| ZkVmError::Other(format!("failed to create file: {e}")))?;
-        file.write_all(&self.encode())
-            .map_err(|e| ZkVmError::Other(format!("failed to write proof: {e}")))
+        self.save_to(dir.as_ref().join(filename))
+    }
+
+    /// Saves the proof to `path`, replacing any existing file.
+    ///
+    /// The receipt is streamed into a temporary file next to `path`, synced, and then renamed
+    /// into place, so readers never observe a partially written proof.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> ZkVmResult<()> {
+        write_atomic(path.as_ref(), |writer| self.encode_to(writer))
+            .map_err(|e| ZkVmError::Other(format!("failed to save proof: {e}")))
     }
 
     /// Loads a proof from a path.
     pub fn load(path: impl AsRef<Path>) -> ZkVmResult<Self> {
-        let mut file =
+        let file =
+            File::open(path).map_err(|e| ZkVmError::Other(format!("failed to open file: {e}")))?;
+        Ok(Self::decode_from(BufReader::new(file))?)
+    }
+
+    /// Loads a proof from a path by memory-mapping the file instead of reading it into a buffer.
+    ///
+    /// This avoids an extra copy of large proofs. The file must not be modified while it is being
+    /// loaded; files written with [`save_to`](Self::save_to) are only ever replaced, never
+    /// modified in place.
+    #[cfg(feature = "mmap")]
+    pub fn load_mmap(path: impl AsRef<Path>) -> ZkVmResult<Self> {
+        let file =
             File::open(path).map_err(|e| ZkVmError::Other(format!("failed to open file: {e}")))?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)
-            .map_err(|e| ZkVmError::Other(format!("failed to read proof: {e}")))?;
-        Self::decode(&buf)
+        // SAFETY: the mapping is read-only and dropped before returning. Concurrent truncation or
+        // modification of the file by another process is outside our control, as documented above.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }
+            .map_err(|e| ZkVmError::Other(format!("failed to map file: {e}")))?;
+        Ok(Self::decode(&mmap)?)
     }
 }
 
@@ -395,6 +442,8 @@ pub enum ProofType {
     Compressed = 1,
     /// Represents a Groth16 proof.
     Groth16 = 2,
+    /// Represents a Plonk proof, which needs no circuit-specific trusted setup.
+    Plonk = 3,
 }
 
 impl TryFrom<u8> for ProofType {
@@ -405,6 +454,7 @@ impl TryFrom<u8> for ProofType {
             0 => Ok(ProofType::Core),
             1 => Ok(ProofType::Compressed),
             2 => Ok(ProofType::Groth16),
+            3 => Ok(ProofType::Plonk),
             _ => Err(ZkVmError::Other(format!("unknown proof type tag: {tag}"))),
         }
     }
@@ -412,6 +462,8 @@ impl TryFrom<u8> for ProofType {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use proptest::prelude::*;
 
     use super::*;
@@ -425,6 +477,7 @@ mod tests {
             Just(ProofType::Core),
             Just(ProofType::Compressed),
             Just(ProofType::Groth16),
+            Just(ProofType::Plonk),
         ]
     }
 
@@ -452,4 +505,141 @@ mod tests {
             prop_assert_eq!(original, decoded);
         }
     }
+
+    fn sample() -> ProofReceiptWithMetadata {
+        ProofReceiptWithMetadata::new(
+            ProofReceipt::new(Proof::new(vec![1, 2, 3]), PublicValues::new(vec![4, 5])),
+            ProofMetadata::new(ZkVm::Native, ProgramId([3; 32]), "0.1.0", ProofType::Core),
+        )
+    }
+
+    /// Borsh encoding of [`sample`] from before the dev-mode flag was added.
+    #[cfg(feature = "borsh")]
+    const PRE_DEV_MODE_BORSH: &str = concat!(
+        "03000000010203",
+        "020000000405",
+        "00",
+        "0303030303030303030303030303030303030303030303030303030303030303",
+        "05000000302e312e30",
+        "00",
+    );
+
+    /// Bincode encoding of [`sample`] from before the dev-mode flag was added.
+    #[cfg(feature = "serde")]
+    const PRE_DEV_MODE_BINCODE: &str = concat!(
+        "0300000000000000010203",
+        "02000000000000000405",
+        "00000000",
+        "0303030303030303030303030303030303030303030303030303030303030303",
+        "0500000000000000302e312e30",
+        "00000000",
+    );
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_decodes_pre_dev_mode_receipts() {
+        let bytes = hex(PRE_DEV_MODE_BORSH);
+        let decoded: ProofReceiptWithMetadata = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, sample());
+
+        // Dev-mode receipts keep the same layout.
+        let dev = ProofReceiptWithMetadata::new(
+            sample().receipt().clone(),
+            sample().metadata().clone().with_dev_mode(true),
+        );
+        assert_eq!(borsh::to_vec(&dev).unwrap(), bytes);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_decodes_pre_dev_mode_receipts() {
+        let bytes = hex(PRE_DEV_MODE_BINCODE);
+        let decoded: ProofReceiptWithMetadata = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, sample());
+        assert_eq!(bincode::serialize(&sample()).unwrap(), bytes);
+    }
+
+    /// `ExecutionSummary` with public values `[4, 5]`, 7 cycles and 9 gas, encoded before
+    /// statistics were added.
+    #[cfg(any(feature = "borsh", feature = "serde"))]
+    fn pre_stats_summary() -> ExecutionSummary {
+        ExecutionSummary::new(PublicValues::new(vec![4, 5]), 7, Some(9))
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_decodes_pre_stats_summaries() {
+        let bytes = hex(concat!(
+            "020000000405",
+            "0700000000000000",
+            "010900000000000000"
+        ));
+        let summary: ExecutionSummary = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(summary, pre_stats_summary());
+
+        let with_stats = summary.with_stats(ExecutionStats::default());
+        assert_eq!(borsh::to_vec(&with_stats).unwrap(), bytes);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_decodes_pre_stats_summaries() {
+        let bytes = hex(concat!(
+            "02000000000000000405",
+            "0700000000000000",
+            "010900000000000000"
+        ));
+        let summary: ExecutionSummary = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(summary, pre_stats_summary());
+
+        let with_stats = summary.with_stats(ExecutionStats::default());
+        assert_eq!(bincode::serialize(&with_stats).unwrap(), bytes);
+    }
+
+    #[cfg(any(feature = "borsh", feature = "serde"))]
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn save_to_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("receipt.proof");
+        let receipt = sample();
+
+        receipt.save_to(&path).unwrap();
+        assert_eq!(ProofReceiptWithMetadata::load(&path).unwrap(), receipt);
+        #[cfg(feature = "mmap")]
+        assert_eq!(ProofReceiptWithMetadata::load_mmap(&path).unwrap(), receipt);
+
+        // Only the final file is left behind.
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn save_to_replaces_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("receipt.proof");
+        fs::write(&path, b"stale").unwrap();
+
+        sample().save_to(&path).unwrap();
+        assert_eq!(ProofReceiptWithMetadata::load(&path).unwrap(), sample());
+    }
+
+    #[test]
+    fn save_to_cleans_up_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        // Renaming a file over a non-empty directory fails after the temp file was written.
+        let path = dir.path().join("occupied");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("child"), b"").unwrap();
+
+        assert!(sample().save_to(&path).is_err());
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
 }
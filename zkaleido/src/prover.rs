@@ -1,8 +1,8 @@
 use std::fmt::Debug;
 
 use crate::{
-    ExecutionSummary, ProgramId, ProofReceiptWithMetadata, ProofType, ZkVmError, ZkVmProofError,
-    ZkVmResult, input::ZkVmInputBuilder,
+    ExecutionSummary, ProgramId, ProofReceiptWithMetadata, ProofType, RunConfig, ZkVmError,
+    ZkVmProofError, ZkVmResult, input::ZkVmInputBuilder,
 };
 
 /// A trait implemented by types that execute zkVM programs.
@@ -13,9 +13,11 @@ pub trait ZkVmExecutor: Send + Sync + Clone + Debug + 'static {
     /// Executes the guest code within the VM returning the `ExecutionResult`.
     ///
     /// The `ExecutionResult` contains the public values, cycle count, and optional gas usage.
+    /// If `config` sets a profiling directory, hosts that support profiling write a trace there.
     fn execute<'a>(
         &self,
         input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
+        config: &RunConfig,
     ) -> ZkVmResult<ExecutionSummary>;
 
     /// Returns the ELF for the loaded program
@@ -23,9 +25,6 @@ pub trait ZkVmExecutor: Send + Sync + Clone + Debug + 'static {
 
     /// Returns the program identifier, derived deterministically from the ELF.
     fn program_id(&self) -> ProgramId;
-
-    /// Save the generated trace
-    fn save_trace(&self, trace_name: &str);
 }
 
 /// A trait implemented by types that not only execute zkVM programs, but also produce proofs.
@@ -42,10 +41,13 @@ pub trait ZkVmProver: ZkVmExecutor {
 
     /// Executes the guest code within the VM, generating and returning ZkVm specific validity
     /// proof.
+    ///
+    /// Hosts that support it generate a mock proof when `config` enables dev mode.
     fn prove_inner<'a>(
         &self,
         input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
+        config: &RunConfig,
     ) -> ZkVmResult<Self::ZkVmProofReceipt>;
 
     /// A higher-level proof function that generates a proof by calling `prove_inner` and
@@ -54,8 +56,39 @@ pub trait ZkVmProver: ZkVmExecutor {
         &self,
         input: <Self::Input<'a> as ZkVmInputBuilder<'a>>::Input,
         proof_type: ProofType,
+        config: &RunConfig,
     ) -> ZkVmResult<ProofReceiptWithMetadata> {
-        let receipt = self.prove_inner(input, proof_type)?;
+        let receipt = self.prove_inner(input, proof_type, config)?;
         receipt.try_into().map_err(ZkVmError::InvalidProofReceipt)
     }
+
+    /// Compresses an existing receipt of this program into a [`ProofType::Compressed`] receipt,
+    /// without executing the guest again.
+    ///
+    /// This lets callers keep cheap core proofs around and only pay for recursion when a smaller
+    /// proof is needed. Hosts that cannot upgrade receipts return
+    /// [`ZkVmError::ProofGenerationError`].
+    fn compress(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<ProofReceiptWithMetadata> {
+        Err(unsupported_upgrade(receipt, ProofType::Compressed))
+    }
+
+    /// Wraps an existing receipt of this program into a [`ProofType::Groth16`] receipt, without
+    /// executing the guest again.
+    ///
+    /// Core receipts are compressed first. Hosts that cannot upgrade receipts return
+    /// [`ZkVmError::ProofGenerationError`].
+    fn wrap_groth16(
+        &self,
+        receipt: &ProofReceiptWithMetadata,
+    ) -> ZkVmResult<ProofReceiptWithMetadata> {
+        Err(unsupported_upgrade(receipt, ProofType::Groth16))
+    }
+}
+
+/// The error returned by hosts that cannot upgrade `receipt` to `proof_type`.
+fn unsupported_upgrade(receipt: &ProofReceiptWithMetadata, proof_type: ProofType) -> ZkVmError {
+    ZkVmError::ProofGenerationError(format!(
+        "cannot upgrade a {:?} receipt to {proof_type:?} with this host",
+        receipt.metadata().proof_type()
+    ))
 }
@@ -0,0 +1,122 @@
+//! Routing of receipts from several zkVMs and programs to the matching verifier.
+
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use crate::{
+    ProgramId, ProofReceiptWithMetadata, ProofType, VerifierRegistryError, ZkVm, ZkVmExecutor,
+    ZkVmHost, ZkVmResult, ZkVmTypedVerifier, ZkVmVerifier, verifier::reject_dev_mode,
+};
+
+/// Every proof type, registered by [`VerifierRegistry::register_host`].
+const ALL_PROOF_TYPES: [ProofType; 4] = [
+    ProofType::Core,
+    ProofType::Compressed,
+    ProofType::Groth16,
+    ProofType::Plonk,
+];
+
+/// Object-safe view of a verifier, so that verifiers of different backends can be stored side
+/// by side.
+pub(crate) trait ErasedVerifier: Send + Sync + Debug {
+    fn verify_receipt(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()>;
+}
+
+impl<V: ZkVmTypedVerifier> ErasedVerifier for V {
+    fn verify_receipt(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        self.verify(receipt)
+    }
+}
+
+/// Adapts a [`ZkVmVerifier`], which ignores the metadata, to [`ErasedVerifier`].
+#[derive(Debug)]
+pub(crate) struct UntypedVerifier<V>(pub(crate) V);
+
+impl<V: ZkVmVerifier> ErasedVerifier for UntypedVerifier<V> {
+    fn verify_receipt(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        reject_dev_mode(receipt)?;
+        self.0.verify(receipt.receipt())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RegisteredVerifier {
+    proof_types: Vec<ProofType>,
+    verifier: Arc<dyn ErasedVerifier>,
+}
+
+/// Verifies receipts of several zkVMs and programs through a single entry point.
+///
+/// Verifiers are registered under a [`ZkVm`] and a [`ProgramId`], together with the proof types
+/// they accept. [`verify`](Self::verify) reads the zkVM, program id and proof type from the
+/// [`ProofMetadata`](crate::ProofMetadata) of the receipt and hands the receipt to the matching
+/// verifier, so callers no longer match on the backend themselves.
+///
+/// Any [`ZkVmTypedVerifier`] can be registered, including the hosts of every adapter.
+#[derive(Debug, Clone, Default)]
+pub struct VerifierRegistry {
+    verifiers: HashMap<(ZkVm, ProgramId), RegisteredVerifier>,
+}
+
+impl VerifierRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `verifier` for the receipts of `program_id` on `zkvm` whose proof type is one of
+    /// `proof_types`.
+    ///
+    /// Replaces any verifier previously registered for the same zkVM and program.
+    pub fn register<V: ZkVmTypedVerifier>(
+        &mut self,
+        zkvm: ZkVm,
+        program_id: ProgramId,
+        proof_types: &[ProofType],
+        verifier: V,
+    ) -> &mut Self {
+        let registered = RegisteredVerifier {
+            proof_types: proof_types.to_vec(),
+            verifier: Arc::new(verifier),
+        };
+        self.verifiers.insert((zkvm, program_id), registered);
+        self
+    }
+
+    /// Registers `host` as the verifier of its own program, for every proof type.
+    pub fn register_host<H: ZkVmHost>(&mut self, host: H) -> &mut Self {
+        let zkvm = host.zkvm();
+        let program_id = ZkVmExecutor::program_id(&host);
+        self.register(zkvm, program_id, &ALL_PROOF_TYPES, host)
+    }
+
+    /// Returns `true` if a verifier is registered for `program_id` on `zkvm`.
+    pub fn contains(&self, zkvm: ZkVm, program_id: &ProgramId) -> bool {
+        self.verifiers.contains_key(&(zkvm, program_id.clone()))
+    }
+
+    /// Verifies `receipt` with the verifier registered for the zkVM and program named in its
+    /// metadata.
+    ///
+    /// Returns [`VerifierRegistryError::UnknownProgram`] if no verifier is registered for them
+    /// and [`VerifierRegistryError::UnsupportedProofType`] if the verifier does not accept the
+    /// proof type of the receipt.
+    pub fn verify(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        let metadata = receipt.metadata();
+        let zkvm = *metadata.zkvm();
+        let program_id = metadata.program_id().clone();
+        let proof_type = metadata.proof_type();
+
+        let Some(registered) = self.verifiers.get(&(zkvm, program_id.clone())) else {
+            return Err(VerifierRegistryError::UnknownProgram { zkvm, program_id }.into());
+        };
+        if !registered.proof_types.contains(&proof_type) {
+            return Err(VerifierRegistryError::UnsupportedProofType {
+                zkvm,
+                program_id,
+                proof_type,
+            }
+            .into());
+        }
+        registered.verifier.verify_receipt(receipt)
+    }
+}
@@ -0,0 +1,582 @@
+//! Persistent storage for proof receipts.
+//!
+//! A [`ProofStore`] files receipts under a [`ProofKey`], which identifies the statement a proof
+//! attests to: the program, the exact input it ran on, the kind of proof and the zkVM that
+//! produced it. Two backends ship with the crate:
+//!
+//! - [`InMemoryProofStore`], useful for tests and short-lived caches.
+//! - [`FsProofStore`], a content-addressed store on the local filesystem.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File},
+    io::{self, BufReader, ErrorKind, Write as _},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    AggregationInput, ProgramId, ProofReceiptWithMetadata, ProofStoreError, ProofType, ZkVm,
+    ZkVmError, ZkVmInputBuilder, ZkVmInputResult, ZkVmResult, fs::write_atomic,
+};
+#[cfg(feature = "serde")]
+use crate::{DataFormatError, ZkVmInputError};
+
+/// SHA-256 digest identifying the exact input a program was run on.
+///
+/// Computed with [`InputDigestBuilder`], so it only depends on the values written by the
+/// program's [`prepare_input`](crate::ZkVmProgram::prepare_input), not on any zkVM-specific
+/// input representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct InputDigest(pub [u8; 32]);
+
+impl fmt::Display for InputDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Key under which a receipt is stored in a [`ProofStore`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProofKey {
+    /// Program the proof was generated for.
+    program_id: ProgramId,
+    /// Digest of the input the program was run on.
+    input_digest: InputDigest,
+    /// Kind of proof that was generated.
+    proof_type: ProofType,
+    /// zkVM that generated the proof.
+    zkvm: ZkVm,
+}
+
+impl ProofKey {
+    /// Creates a new `ProofKey`.
+    pub fn new(
+        program_id: ProgramId,
+        input_digest: InputDigest,
+        proof_type: ProofType,
+        zkvm: ZkVm,
+    ) -> Self {
+        Self {
+            program_id,
+            input_digest,
+            proof_type,
+            zkvm,
+        }
+    }
+
+    /// Returns the program identifier.
+    pub fn program_id(&self) -> &ProgramId {
+        &self.program_id
+    }
+
+    /// Returns the input digest.
+    pub fn input_digest(&self) -> &InputDigest {
+        &self.input_digest
+    }
+
+    /// Returns the proof type.
+    pub fn proof_type(&self) -> ProofType {
+        self.proof_type
+    }
+
+    /// Returns the zkVM.
+    pub fn zkvm(&self) -> ZkVm {
+        self.zkvm
+    }
+}
+
+/// Storage backend for proof receipts.
+///
+/// Methods take `&self` so that a single store can be shared between hosts and threads;
+/// implementations are expected to synchronize internally.
+pub trait ProofStore {
+    /// Stores `receipt` under `key`, replacing any receipt previously stored under it.
+    fn put(&self, key: &ProofKey, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()>;
+
+    /// Returns the receipt stored under `key`, if any.
+    fn get(&self, key: &ProofKey) -> ZkVmResult<Option<ProofReceiptWithMetadata>>;
+
+    /// Returns the keys of all stored receipts, in no particular order.
+    fn list(&self) -> ZkVmResult<Vec<ProofKey>>;
+
+    /// Removes the receipt stored under `key`, returning whether one was present.
+    fn delete(&self, key: &ProofKey) -> ZkVmResult<bool>;
+}
+
+/// A [`ProofStore`] that keeps receipts in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryProofStore {
+    receipts: Mutex<HashMap<ProofKey, ProofReceiptWithMetadata>>,
+}
+
+impl InMemoryProofStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn receipts(&self) -> MutexGuard<'_, HashMap<ProofKey, ProofReceiptWithMetadata>> {
+        // The map is never left half-updated, so a poisoned lock is still safe to use.
+        self.receipts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ProofStore for InMemoryProofStore {
+    fn put(&self, key: &ProofKey, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        self.receipts().insert(key.clone(), receipt.clone());
+        Ok(())
+    }
+
+    fn get(&self, key: &ProofKey) -> ZkVmResult<Option<ProofReceiptWithMetadata>> {
+        Ok(self.receipts().get(key).cloned())
+    }
+
+    fn list(&self) -> ZkVmResult<Vec<ProofKey>> {
+        Ok(self.receipts().keys().cloned().collect())
+    }
+
+    fn delete(&self, key: &ProofKey) -> ZkVmResult<bool> {
+        Ok(self.receipts().remove(key).is_some())
+    }
+}
+
+/// A content-addressed [`ProofStore`] on the local filesystem.
+///
+/// Receipts are encoded with [`ProofReceiptWithMetadata::encode`] and stored once per distinct
+/// encoding, named by the SHA-256 of their bytes. Keys are small reference files pointing at
+/// those objects:
+///
+/// ```text
+/// <root>/objects/<sha256[..2]>/<sha256[2..]>
+/// <root>/refs/<zkvm tag>/<proof_type tag>/<program_id>/<input_digest>
+/// ```
+///
+/// All files are written atomically, so a crashed writer never leaves a truncated receipt or
+/// reference behind. Every operation also locks `<root>/lock`, exclusively for writes and shared
+/// for reads, so that concurrent writers, in this process or others, cannot leave a reference to
+/// an object deleted in the meantime.
+#[derive(Debug, Clone)]
+pub struct FsProofStore {
+    root: PathBuf,
+}
+
+impl FsProofStore {
+    /// Opens the store rooted at `root`, creating the directory if it does not exist.
+    pub fn open(root: impl Into<PathBuf>) -> ZkVmResult<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).map_err(|e| io_error(&root, e))?;
+        Ok(Self { root })
+    }
+
+    /// Returns the root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn refs_dir(&self) -> PathBuf {
+        self.root.join("refs")
+    }
+
+    fn ref_path(&self, key: &ProofKey) -> PathBuf {
+        self.refs_dir()
+            .join((key.zkvm as u8).to_string())
+            .join((key.proof_type as u8).to_string())
+            .join(key.program_id.to_string())
+            .join(key.input_digest.to_string())
+    }
+
+    /// Locks the whole store until the returned file is dropped.
+    fn lock(&self, exclusive: bool) -> ZkVmResult<File> {
+        let path = self.root.join("lock");
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        }
+        .map_err(|e| io_error(&path, e))?;
+        Ok(file)
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        let (prefix, rest) = hash.split_at(2);
+        self.root.join("objects").join(prefix).join(rest)
+    }
+
+    /// Reads the object hash a reference file points to, if the reference exists.
+    fn read_ref(&self, path: &Path) -> ZkVmResult<Option<String>> {
+        let hash = match fs::read_to_string(path) {
+            Ok(hash) => hash,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(path, e)),
+        };
+        if parse_hex32(&hash).is_none() {
+            return Err(ProofStoreError::CorruptEntry {
+                path: path.to_path_buf(),
+                reason: "reference does not contain a SHA-256 digest".to_string(),
+            }
+            .into());
+        }
+        Ok(Some(hash))
+    }
+
+    /// Returns every `(key, object hash)` pair in the store.
+    fn refs(&self) -> ZkVmResult<Vec<(ProofKey, String)>> {
+        let mut refs = Vec::new();
+        for (zkvm_tag, zkvm_dir) in read_dir_names(&self.refs_dir())? {
+            for (proof_type_tag, proof_type_dir) in read_dir_names(&zkvm_dir)? {
+                for (program_id, program_dir) in read_dir_names(&proof_type_dir)? {
+                    for (input_digest, path) in read_dir_names(&program_dir)? {
+                        let key = parse_key(&zkvm_tag, &proof_type_tag, &program_id, &input_digest)
+                            .ok_or_else(|| ProofStoreError::CorruptEntry {
+                                path: path.clone(),
+                                reason: "unrecognized reference path".to_string(),
+                            })?;
+                        if let Some(hash) = self.read_ref(&path)? {
+                            refs.push((key, hash));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(refs)
+    }
+}
+
+impl ProofStore for FsProofStore {
+    fn put(&self, key: &ProofKey, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        let encoded = receipt.encode();
+        let hash = Sha256::digest(&encoded)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        let _lock = self.lock(true)?;
+        let object_path = self.object_path(&hash);
+        // Objects are immutable, so an existing one already holds exactly these bytes.
+        if !object_path.exists() {
+            write_file(&object_path, &encoded)?;
+        }
+        write_file(&self.ref_path(key), hash.as_bytes())
+    }
+
+    fn get(&self, key: &ProofKey) -> ZkVmResult<Option<ProofReceiptWithMetadata>> {
+        let _lock = self.lock(false)?;
+        let Some(hash) = self.read_ref(&self.ref_path(key))? else {
+            return Ok(None);
+        };
+
+        let path = self.object_path(&hash);
+        let file = File::open(&path).map_err(|e| io_error(&path, e))?;
+        let receipt = ProofReceiptWithMetadata::decode_from(BufReader::new(file))
+            .map_err(|source| ProofStoreError::Decode { path, source })?;
+        Ok(Some(receipt))
+    }
+
+    fn list(&self) -> ZkVmResult<Vec<ProofKey>> {
+        let _lock = self.lock(false)?;
+        Ok(self.refs()?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    fn delete(&self, key: &ProofKey) -> ZkVmResult<bool> {
+        let _lock = self.lock(true)?;
+        let ref_path = self.ref_path(key);
+        let Some(hash) = self.read_ref(&ref_path)? else {
+            return Ok(false);
+        };
+        fs::remove_file(&ref_path).map_err(|e| io_error(&ref_path, e))?;
+
+        // Drop the object once no other key refers to it.
+        if self.refs()?.iter().all(|(_, other)| *other != hash) {
+            let object_path = self.object_path(&hash);
+            match fs::remove_file(&object_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(io_error(&object_path, e)),
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// A [`ZkVmInputBuilder`] that hashes the inputs written to it instead of preparing them for a
+/// zkVM, producing an [`InputDigest`].
+///
+/// Every write is absorbed as a tagged, length-prefixed frame, so the digest distinguishes
+/// different splits of the same bytes as well as raw buffers from embedded proofs.
+#[derive(Debug, Clone)]
+pub struct InputDigestBuilder {
+    hasher: Sha256,
+}
+
+impl InputDigestBuilder {
+    const BUF_FRAME: u8 = 0;
+    #[cfg(feature = "serde")]
+    const SERDE_FRAME: u8 = 1;
+    const PROOF_FRAME: u8 = 2;
+
+    fn absorb(&mut self, tag: u8, bytes: &[u8]) {
+        self.hasher.update([tag]);
+        self.hasher.update((bytes.len() as u64).to_le_bytes());
+        self.hasher.update(bytes);
+    }
+}
+
+impl ZkVmInputBuilder<'_> for InputDigestBuilder {
+    type Input = InputDigest;
+    type ZkVmProofReceipt = ProofReceiptWithMetadata;
+
+    fn new() -> Self {
+        Self {
+            hasher: Sha256::new(),
+        }
+    }
+
+    fn write_buf(&mut self, item: &[u8]) -> ZkVmInputResult<&mut Self> {
+        self.absorb(Self::BUF_FRAME, item);
+        Ok(self)
+    }
+
+    #[cfg(feature = "serde")]
+    fn write_serde<T: serde::Serialize>(&mut self, item: &T) -> ZkVmInputResult<&mut Self> {
+        let bytes = bincode::serialize(item)
+            .map_err(|e| ZkVmInputError::DataFormat(DataFormatError::Serde(e.to_string())))?;
+        self.absorb(Self::SERDE_FRAME, &bytes);
+        Ok(self)
+    }
+
+    fn write_proof(&mut self, item: &AggregationInput) -> ZkVmInputResult<&mut Self> {
+        let mut bytes = item.receipt().encode();
+        bytes.extend_from_slice(item.vk().as_bytes());
+        self.absorb(Self::PROOF_FRAME, &bytes);
+        Ok(self)
+    }
+
+    fn build(&mut self) -> ZkVmInputResult<Self::Input> {
+        Ok(InputDigest(self.hasher.clone().finalize().into()))
+    }
+}
+
+fn io_error(path: &Path, source: io::Error) -> ZkVmError {
+    ProofStoreError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+    .into()
+}
+
+/// Atomically writes `bytes` to `path`, creating parent directories as needed.
+fn write_file(path: &Path, bytes: &[u8]) -> ZkVmResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    write_atomic(path, |writer| writer.write_all(bytes)).map_err(|e| io_error(path, e))
+}
+
+/// Lists the entries of `dir` as `(file name, path)` pairs, skipping in-flight temporary files.
+///
+/// A missing directory is treated as empty.
+fn read_dir_names(dir: &Path) -> ZkVmResult<Vec<(String, PathBuf)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(dir, e)),
+    };
+
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| io_error(dir, e))?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.ends_with(".tmp") {
+            names.push((name, path));
+        }
+    }
+    Ok(names)
+}
+
+fn parse_key(
+    zkvm_tag: &str,
+    proof_type_tag: &str,
+    program_id: &str,
+    input_digest: &str,
+) -> Option<ProofKey> {
+    Some(ProofKey::new(
+        ProgramId(parse_hex32(program_id)?),
+        InputDigest(parse_hex32(input_digest)?),
+        ProofType::try_from(proof_type_tag.parse::<u8>().ok()?).ok()?,
+        ZkVm::try_from(zkvm_tag.parse::<u8>().ok()?).ok()?,
+    ))
+}
+
+fn parse_hex32(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 || !s.is_ascii() {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{Proof, ProofMetadata, ProofReceipt, PublicValues, VerifyingKey};
+
+    fn receipt(pv: u8) -> ProofReceiptWithMetadata {
+        ProofReceiptWithMetadata::new(
+            ProofReceipt::new(Proof::new(vec![1, 2, 3]), PublicValues::new(vec![pv])),
+            ProofMetadata::new(ZkVm::Native, ProgramId([9; 32]), "0.1.0", ProofType::Core),
+        )
+    }
+
+    fn key(input: u8) -> ProofKey {
+        ProofKey::new(
+            ProgramId([9; 32]),
+            InputDigest([input; 32]),
+            ProofType::Core,
+            ZkVm::Native,
+        )
+    }
+
+    fn exercise(store: &impl ProofStore) {
+        assert_eq!(store.get(&key(1)).unwrap(), None);
+        assert!(store.list().unwrap().is_empty());
+
+        store.put(&key(1), &receipt(1)).unwrap();
+        store.put(&key(2), &receipt(2)).unwrap();
+        assert_eq!(store.get(&key(1)).unwrap(), Some(receipt(1)));
+        assert_eq!(store.get(&key(2)).unwrap(), Some(receipt(2)));
+
+        // Overwriting replaces the receipt.
+        store.put(&key(1), &receipt(3)).unwrap();
+        assert_eq!(store.get(&key(1)).unwrap(), Some(receipt(3)));
+
+        let mut keys = store.list().unwrap();
+        keys.sort_by_key(|k| k.input_digest().0);
+        assert_eq!(keys, vec![key(1), key(2)]);
+
+        assert!(store.delete(&key(1)).unwrap());
+        assert!(!store.delete(&key(1)).unwrap());
+        assert_eq!(store.get(&key(1)).unwrap(), None);
+        assert_eq!(store.list().unwrap(), vec![key(2)]);
+    }
+
+    #[test]
+    fn in_memory_store() {
+        exercise(&InMemoryProofStore::new());
+    }
+
+    #[test]
+    fn fs_store() {
+        let dir = tempfile::tempdir().unwrap();
+        exercise(&FsProofStore::open(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn fs_store_deduplicates_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsProofStore::open(dir.path()).unwrap();
+        let objects = || fs::read_dir(dir.path().join("objects")).unwrap().count();
+
+        store.put(&key(1), &receipt(1)).unwrap();
+        store.put(&key(2), &receipt(1)).unwrap();
+        assert_eq!(objects(), 1);
+
+        // The shared object survives until its last reference is deleted.
+        store.delete(&key(1)).unwrap();
+        assert_eq!(store.get(&key(2)).unwrap(), Some(receipt(1)));
+        store.delete(&key(2)).unwrap();
+        assert_eq!(
+            fs::read_dir(dir.path().join("objects"))
+                .unwrap()
+                .flat_map(|d| fs::read_dir(d.unwrap().path()).unwrap())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn fs_store_serializes_concurrent_writers() {
+        let dir = tempfile::tempdir().unwrap();
+        let stores = [
+            FsProofStore::open(dir.path()).unwrap(),
+            FsProofStore::open(dir.path()).unwrap(),
+        ];
+
+        // Both keys share one object: deleting one key must never drop the object while the
+        // other key is being written.
+        thread::scope(|scope| {
+            let [first, second] = &stores;
+            scope.spawn(|| {
+                for _ in 0..200 {
+                    first.put(&key(1), &receipt(1)).unwrap();
+                    assert_eq!(first.get(&key(1)).unwrap(), Some(receipt(1)));
+                    first.delete(&key(1)).unwrap();
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..200 {
+                    second.put(&key(2), &receipt(1)).unwrap();
+                    second.delete(&key(2)).unwrap();
+                }
+            });
+        });
+        assert!(stores[0].list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fs_store_reports_corrupt_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsProofStore::open(dir.path()).unwrap();
+        store.put(&key(1), &receipt(1)).unwrap();
+
+        let hash = fs::read_to_string(store.ref_path(&key(1))).unwrap();
+        fs::write(store.object_path(&hash), b"garbage").unwrap();
+        let err = store.get(&key(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            ZkVmError::ProofStore(ProofStoreError::Decode { .. })
+        ));
+    }
+
+    #[test]
+    fn input_digest_depends_on_framing() {
+        let digest = |bufs: &[&[u8]]| {
+            let mut builder = InputDigestBuilder::new();
+            for buf in bufs {
+                builder.write_buf(buf).unwrap();
+            }
+            builder.build().unwrap()
+        };
+
+        assert_eq!(digest(&[b"ab", b"c"]), digest(&[b"ab", b"c"]));
+        assert_ne!(digest(&[b"ab", b"c"]), digest(&[b"a", b"bc"]));
+
+        let mut with_proof = InputDigestBuilder::new();
+        with_proof
+            .write_proof(&AggregationInput::new(
+                receipt(1),
+                VerifyingKey::new(vec![0]),
+            ))
+            .unwrap();
+        assert_ne!(with_proof.build().unwrap(), digest(&[]));
+    }
+}
@@ -5,11 +5,10 @@ use borsh::{BorshDeserialize, BorshSerialize};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
-#[cfg(any(feature = "serde", feature = "borsh", feature = "ssz"))]
-use crate::PublicValues;
-#[cfg(any(feature = "borsh", feature = "ssz"))]
-use crate::ZkVmError;
-use crate::{ProofReceipt, ProofReceiptWithMetadata, VerifyingKey, ZkVmProofError, ZkVmResult};
+use crate::{
+    ProofReceipt, ProofReceiptWithMetadata, PublicValues, VerifyingKey, ZkVmCodec, ZkVmError,
+    ZkVmProofError, ZkVmResult,
+};
 
 /// A trait implemented by verifiers that work with typed proof receipts.
 ///
@@ -35,7 +34,16 @@ pub trait ZkVmTypedVerifier: Send + Sync + Clone + Debug + 'static {
     ///
     /// This method handles the conversion from [`ProofReceiptWithMetadata`] to the
     /// verifier's specific proof receipt type, then delegates to `verify_inner`.
+    ///
+    /// Receipts generated in dev mode are rejected with [`ZkVmError::DevModeReceipt`]; wrap the
+    /// verifier in [`AllowDevMode`](crate::AllowDevMode) to accept them.
+    ///
+    /// The dev mode flag only survives the versioned container of
+    /// [`ProofReceiptWithMetadata::encode`]. The serde and Borsh layouts drop it, so a dev-mode
+    /// receipt sent through them arrives unflagged and is rejected by the proof checks of
+    /// `verify_inner` instead, as it carries no real proof.
     fn verify(&self, receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+        reject_dev_mode(receipt)?;
         self.verify_inner(&receipt.clone().try_into()?)
     }
 }
@@ -86,6 +94,25 @@ pub trait ZkVmOutputExtractor: Send + Sync + Clone + Debug + 'static {
         T::from_ssz_bytes(public_values.as_bytes())
             .map_err(|e| ZkVmError::OutputExtractionError { source: e.into() })
     }
+
+    /// Extracts the public output from the public values using the codec `C`.
+    ///
+    /// The output is expected to have been committed with
+    /// [`commit_with`](crate::ZkVmEnvCodec::commit_with) using the same codec.
+    fn extract_public_output<C: ZkVmCodec<T>, T>(public_values: &PublicValues) -> ZkVmResult<T> {
+        C::extract::<Self>(public_values)
+    }
+}
+
+/// Fails with [`ZkVmError::DevModeReceipt`] if `receipt` was generated in dev mode.
+pub(crate) fn reject_dev_mode(receipt: &ProofReceiptWithMetadata) -> ZkVmResult<()> {
+    let metadata = receipt.metadata();
+    if metadata.is_dev_mode() {
+        return Err(ZkVmError::DevModeReceipt {
+            zkvm: *metadata.zkvm(),
+        });
+    }
+    Ok(())
 }
 
 /// A no-op verifier: its `verify` method does nothing and always returns success.